@@ -13,6 +13,7 @@
 //! | 30-39 | Delegate errors |
 //! | 40-49 | Multisig errors |
 //! | 50-59 | Close errors |
+//! | 23+ | Extension errors (appended as features are added) |
 //!
 //! # Usage
 //!
@@ -225,6 +226,131 @@ pub enum TokenError {
     /// Source and destination are the same account.
     #[error("Self transfer not allowed")]
     SelfTransfer,
+
+    // =========================================================================
+    // EXTENSION ERRORS (23+)
+    // =========================================================================
+
+    /// Error 23: Invalid account state for this operation.
+    ///
+    /// The requested state transition isn't allowed,
+    /// e.g. thawing an account that isn't frozen.
+    #[error("Invalid account state for operation")]
+    InvalidState,
+
+    /// Error 24: Multisig threshold too low (strict-multisig feature).
+    ///
+    /// A multisig with more than one signer must require at least 2.
+    /// 1-of-N lets any single key act alone.
+    #[error("Multisig threshold too low")]
+    MultisigThresholdTooLow,
+
+    /// Error 25: Account not created in this transaction (fresh-account-check feature).
+    ///
+    /// InitializeAccount must follow a CreateAccount for the same
+    /// account in the same transaction, so it can't be front-run.
+    #[error("Account not freshly created")]
+    AccountNotFreshlyCreated,
+
+    /// Error 26: UI amount has more fractional digits than the mint's decimals.
+    ///
+    /// "1.1234567" can't be represented with 6 decimals.
+    /// Rejected instead of silently truncated.
+    #[error("UI amount exceeds mint decimal precision")]
+    ExcessPrecision,
+
+    /// Error 27: Tokens of this mint can't be transferred.
+    ///
+    /// The mint was flagged with SetNonTransferable.
+    /// Minting and burning are still allowed.
+    #[error("Token is non-transferable")]
+    NonTransferable,
+
+    /// Error 28: Destination requires a memo on inbound transfers.
+    ///
+    /// No Memo program instruction ran before the transfer, or the
+    /// instructions sysvar wasn't passed.
+    #[error("Memo required for transfers into this account")]
+    MemoRequired,
+
+    /// Error 29: A state invariant doesn't hold.
+    ///
+    /// e.g. strict ReconcileSupply found the token account balances
+    /// don't add up to the mint's supply.
+    #[error("Invariant violation")]
+    InvariantViolation,
+
+    /// Error 30: Native (wrapped SOL) accounts can't be frozen.
+    #[error("Cannot freeze native account")]
+    CannotFreezeNative,
+
+    /// Error 31: Minting would push supply past the mint's supply cap.
+    #[error("Supply cap exceeded")]
+    SupplyCapExceeded,
+
+    /// Error 32: Minting is paused on this mint.
+    #[error("Mint is paused")]
+    MintPaused,
+
+    /// Error 33: A batch instruction has more entries than `MAX_BATCH_LEN`.
+    #[error("Batch too large")]
+    BatchTooLarge,
+
+    /// Error 34: Burning would take supply below zero.
+    ///
+    /// The balance check passed, so the mint's supply is already smaller
+    /// than an account's balance: the supply invariant is broken.
+    #[error("Supply underflow")]
+    SupplyUnderflow,
+
+    /// Error 35: A token account was expected, but another account was passed.
+    ///
+    /// E.g. the mint itself passed as the MintTo destination.
+    #[error("Expected a token account")]
+    ExpectedAccount,
+
+    /// Error 36: MintTo or Burn with amount 0 (reject-zero-amount feature).
+    #[error("Amount must be greater than zero")]
+    ZeroAmount,
+
+    /// Error 37: The mint's transfer hook program returned an error.
+    ///
+    /// Where the runtime aborts the transaction as soon as a CPI fails,
+    /// the hook's own error is reported instead.
+    #[error("Transfer hook failed")]
+    TransferHookFailed,
+
+    /// Error 38: SetAuthority(FreezeAccount) on a mint without a freeze authority.
+    ///
+    /// A mint created without a freeze authority, or whose freeze
+    /// authority was removed, can never get one. This is permanent, as
+    /// in SPL Token, so holders can rely on their accounts never being
+    /// frozen.
+    #[error("Cannot add a freeze authority to a mint without one")]
+    CannotAddFreezeAuthority,
+
+    /// Error 39: A delegate transfer after the approval's expiry.
+    ///
+    /// Set by ApproveWithExpiry. The approval stops working once the
+    /// Clock sysvar's unix timestamp passes the expiry; the owner must
+    /// approve again.
+    #[error("Delegate approval has expired")]
+    DelegateExpired,
+
+    /// Error 40: SetAuthority invoked through CPI (cpi-guard feature).
+    ///
+    /// The top-level instruction running in the transaction belongs to
+    /// another program, so a program is calling in on the signer's
+    /// behalf. Authority changes must be signed for directly.
+    #[error("Instruction must not be invoked through CPI")]
+    CpiGuardViolation,
+
+    /// Error 41: InitializeMint decimals above `MAX_DECIMALS`.
+    ///
+    /// Registries commonly reject tokens with more than 18 decimals, and
+    /// display math on `10^decimals` overflows a u64 from 20 on.
+    #[error("Decimals exceed the maximum allowed")]
+    InvalidDecimals,
 }
 
 // =============================================================================
@@ -259,6 +385,176 @@ impl From<TokenError> for ProgramError {
     }
 }
 
+// =============================================================================
+// ERROR CODES
+// =============================================================================
+
+impl TokenError {
+    /// The numeric code this error is returned as (`Custom(code)`).
+    pub fn code(self) -> u32 {
+        self as u32
+    }
+
+    /// Turn a `Custom(code)` back into the error it came from.
+    ///
+    /// Returns None for codes this program never returns.
+    /// New variants need an arm here as well as in the enum.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if let ProgramError::Custom(code) = err {
+    ///     let token_error = TokenError::from_u32(code);
+    /// }
+    /// ```
+    pub fn from_u32(code: u32) -> Option<TokenError> {
+        let error = match code {
+            0 => TokenError::InvalidAccountOwner,
+            1 => TokenError::InvalidAccountDataLength,
+            2 => TokenError::NotRentExempt,
+            3 => TokenError::AlreadyInitialized,
+            4 => TokenError::UninitializedAccount,
+            5 => TokenError::InvalidAuthority,
+            6 => TokenError::OwnerMismatch,
+            7 => TokenError::MintAuthorityRequired,
+            8 => TokenError::AccountFrozen,
+            9 => TokenError::FreezeAuthorityRequired,
+            10 => TokenError::InsufficientFunds,
+            11 => TokenError::Overflow,
+            12 => TokenError::MintMismatch,
+            13 => TokenError::NonZeroBalance,
+            14 => TokenError::InvalidInstruction,
+            15 => TokenError::NoDelegate,
+            16 => TokenError::InsufficientDelegatedAmount,
+            17 => TokenError::NotEnoughSigners,
+            18 => TokenError::InvalidMultisigConfig,
+            19 => TokenError::InvalidMultisigSigner,
+            20 => TokenError::CloseAuthorityMismatch,
+            21 => TokenError::NativeAccountHasBalance,
+            22 => TokenError::SelfTransfer,
+            23 => TokenError::InvalidState,
+            24 => TokenError::MultisigThresholdTooLow,
+            25 => TokenError::AccountNotFreshlyCreated,
+            26 => TokenError::ExcessPrecision,
+            27 => TokenError::NonTransferable,
+            28 => TokenError::MemoRequired,
+            29 => TokenError::InvariantViolation,
+            30 => TokenError::CannotFreezeNative,
+            31 => TokenError::SupplyCapExceeded,
+            32 => TokenError::MintPaused,
+            33 => TokenError::BatchTooLarge,
+            34 => TokenError::SupplyUnderflow,
+            35 => TokenError::ExpectedAccount,
+            36 => TokenError::ZeroAmount,
+            37 => TokenError::TransferHookFailed,
+            38 => TokenError::CannotAddFreezeAuthority,
+            39 => TokenError::DelegateExpired,
+            40 => TokenError::CpiGuardViolation,
+            41 => TokenError::InvalidDecimals,
+            _ => return None,
+        };
+        Some(error)
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every variant, in declaration order.
+    const ALL: [TokenError; 42] = [
+        TokenError::InvalidAccountOwner,
+        TokenError::InvalidAccountDataLength,
+        TokenError::NotRentExempt,
+        TokenError::AlreadyInitialized,
+        TokenError::UninitializedAccount,
+        TokenError::InvalidAuthority,
+        TokenError::OwnerMismatch,
+        TokenError::MintAuthorityRequired,
+        TokenError::AccountFrozen,
+        TokenError::FreezeAuthorityRequired,
+        TokenError::InsufficientFunds,
+        TokenError::Overflow,
+        TokenError::MintMismatch,
+        TokenError::NonZeroBalance,
+        TokenError::InvalidInstruction,
+        TokenError::NoDelegate,
+        TokenError::InsufficientDelegatedAmount,
+        TokenError::NotEnoughSigners,
+        TokenError::InvalidMultisigConfig,
+        TokenError::InvalidMultisigSigner,
+        TokenError::CloseAuthorityMismatch,
+        TokenError::NativeAccountHasBalance,
+        TokenError::SelfTransfer,
+        TokenError::InvalidState,
+        TokenError::MultisigThresholdTooLow,
+        TokenError::AccountNotFreshlyCreated,
+        TokenError::ExcessPrecision,
+        TokenError::NonTransferable,
+        TokenError::MemoRequired,
+        TokenError::InvariantViolation,
+        TokenError::CannotFreezeNative,
+        TokenError::SupplyCapExceeded,
+        TokenError::MintPaused,
+        TokenError::BatchTooLarge,
+        TokenError::SupplyUnderflow,
+        TokenError::ExpectedAccount,
+        TokenError::ZeroAmount,
+        TokenError::TransferHookFailed,
+        TokenError::CannotAddFreezeAuthority,
+        TokenError::DelegateExpired,
+        TokenError::CpiGuardViolation,
+        TokenError::InvalidDecimals,
+    ];
+
+    #[test]
+    fn test_from_u32_roundtrips_every_variant() {
+        for (index, error) in ALL.into_iter().enumerate() {
+            // Declaration order is the code; catches accidental reordering
+            assert_eq!(error.code(), index as u32);
+            assert_eq!(TokenError::from_u32(error.code()), Some(error));
+            assert_eq!(ProgramError::from(error), ProgramError::Custom(error.code()));
+        }
+    }
+
+    /// Pinned codes. Unlike the test above, these don't move if `ALL`
+    /// is edited along with the enum, so inserting a variant in the
+    /// middle fails here.
+    #[test]
+    fn test_error_codes_are_stable() {
+        let pinned = [
+            (TokenError::InvalidAccountOwner, 0),
+            (TokenError::InvalidAuthority, 5),
+            (TokenError::InsufficientFunds, 10),
+            (TokenError::InvalidInstruction, 14),
+            (TokenError::NotEnoughSigners, 17),
+            (TokenError::SelfTransfer, 22),
+            (TokenError::AccountNotFreshlyCreated, 25),
+            (TokenError::CannotFreezeNative, 30),
+            (TokenError::ZeroAmount, 36),
+            (TokenError::CannotAddFreezeAuthority, 38),
+            (TokenError::DelegateExpired, 39),
+            (TokenError::CpiGuardViolation, 40),
+            (TokenError::InvalidDecimals, 41),
+        ];
+
+        for (error, code) in pinned {
+            assert_eq!(error.code(), code, "{:?} changed code", error);
+            assert_eq!(ProgramError::from(error), ProgramError::Custom(code));
+        }
+    }
+
+    #[test]
+    fn test_from_u32_unknown_code() {
+        assert_eq!(TokenError::from_u32(ALL.len() as u32), None);
+        assert_eq!(TokenError::from_u32(u32::MAX), None);
+    }
+}
+
 /*
 =============================================================================
 DETAILED EXPLANATION
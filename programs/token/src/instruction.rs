@@ -19,7 +19,7 @@
 //! | 0 | InitializeMint |
 //! | 1 | InitializeAccount |
 //! | 2 | InitializeMultisig |
-//! | 3 | Transfer |
+//! | 3 | Transfer (TransferWithReturnData with trailing flag byte 1) |
 //! | 4 | Approve |
 //! | 5 | Revoke |
 //! | 6 | SetAuthority |
@@ -28,9 +28,41 @@
 //! | 9 | CloseAccount |
 //! | 10 | FreezeAccount |
 //! | 11 | ThawAccount |
+//! | 26 | TransferBatch |
+//! | 27 | BurnAll |
+//! | 28 | ApproveAll |
+//! | 29 | Reallocate |
+//! | 30 | SetNonTransferable |
+//! | 31 | TransferWithFee |
+//! | 32 | CreateAccount |
+//! | 33 | FreezeAccounts |
+//! | 34 | GetMintInfo |
+//! | 35 | GetAccountBalance |
+//! | 36 | GetMultisigInfo |
+//! | 37 | MintToBatch |
+//! | 38 | RevokeBatch |
+//! | 39 | MigrateAccount |
+//! | 40 | InitializeAccountIdempotent |
+//! | 41 | SetMemoRequired |
+//! | 42 | SetSupplyCap |
+//! | 43 | SetMintPaused |
+//! | 44 | SetTransferHook |
+//! | 45 | ApproveWithExpiry |
+//! | 56 | InitializeAccountWithCloseAuthority |
+//! | 57 | GetSupply |
+//! | 58 | ReconcileSupply |
+//! | 59 | ApproveAndSetCloseAuthority |
+//! | 60 | VerifyAccount |
+//! | 61 | CloseAccountWithReserve |
 
 use crate::error::TokenError;
-use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
 
 // =============================================================================
 // AUTHORITY TYPE
@@ -45,6 +77,11 @@ use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 /// - `AccountOwner (2)`: Owner of a token account
 /// - `CloseAccount (3)`: Authority to close a token account
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshSerialize, BorshDeserialize),
+    borsh(use_discriminant = true)
+)]
 pub enum AuthorityType {
     /// Permission to mint new tokens (on Mint accounts)
     MintTokens = 0,
@@ -79,6 +116,79 @@ impl AuthorityType {
     }
 }
 
+// =============================================================================
+// BATCH LIMITS
+// =============================================================================
+
+/// Most entries a variable-length batch instruction may carry.
+///
+/// Unpacking a larger batch fails with `BatchTooLarge` before any
+/// account is touched. 32 destinations plus the fixed accounts still
+/// fit in one transaction.
+pub const MAX_BATCH_LEN: usize = 32;
+
+// =============================================================================
+// TRANSFER HOOK INTERFACE
+// =============================================================================
+
+/// First 8 bytes of the instruction data a transfer hook receives.
+///
+/// Long enough that a hook program can keep its own instructions next to
+/// it without the two colliding.
+pub const TRANSFER_HOOK_DISCRIMINATOR: [u8; 8] = *b"tknhook0";
+
+/// Build the instruction Transfer sends to a mint's transfer hook.
+///
+/// The hook runs after the balances are updated, so it sees the
+/// post-transfer state. Returning an error fails the whole transfer.
+///
+/// # Account Requirements
+///
+/// | # | Account | Writable | Signer | Description |
+/// |---|---------|----------|--------|-------------|
+/// | 0 | source | | | Token account debited |
+/// | 1 | destination | | | Token account credited |
+/// | 2 | mint | | | The mint |
+/// | 3 | authority | | | Owner or delegate that authorized the transfer |
+///
+/// # Data Layout
+///
+/// ```text
+/// [0..8]: TRANSFER_HOOK_DISCRIMINATOR
+/// [8..16]: amount (u64, little-endian)
+/// ```
+pub fn transfer_hook_instruction(
+    hook_program: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut data = Vec::with_capacity(16);
+    data.extend_from_slice(&TRANSFER_HOOK_DISCRIMINATOR);
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: *hook_program,
+        accounts: vec![
+            AccountMeta::new_readonly(*source, false),
+            AccountMeta::new_readonly(*destination, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*authority, false),
+        ],
+        data,
+    }
+}
+
+/// Parse the amount out of transfer hook instruction data.
+///
+/// For hook programs. Returns None if `data` isn't a transfer hook call.
+pub fn unpack_transfer_hook_amount(data: &[u8]) -> Option<u64> {
+    let amount = data.strip_prefix(&TRANSFER_HOOK_DISCRIMINATOR)?;
+    Some(u64::from_le_bytes(amount.try_into().ok()?))
+}
+
 // =============================================================================
 // TOKEN INSTRUCTION ENUM
 // =============================================================================
@@ -87,7 +197,14 @@ impl AuthorityType {
 ///
 /// Each variant contains the instruction-specific data.
 /// Account requirements are documented in comments but not encoded.
+///
+/// With the `borsh` feature the enum also derives Borsh (de)serialization
+/// for off-chain interchange. That is a different format: Borsh tags
+/// variants by declaration order, not by the discriminants above, so
+/// Borsh bytes must never be sent as instruction data. Decode them with
+/// Borsh and call `pack` to get the bytes the program reads.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
 pub enum TokenInstruction {
     // =========================================================================
     // INITIALIZATION INSTRUCTIONS
@@ -100,7 +217,7 @@ pub enum TokenInstruction {
     /// | # | Account | Writable | Signer | Description |
     /// |---|---------|----------|--------|-------------|
     /// | 0 | mint | ✓ | | The mint to initialize |
-    /// | 1 | rent | | | Rent sysvar |
+    /// | 1 | rent | | | Rent sysvar (optional, falls back to `Rent::get()`) |
     ///
     /// # Data Layout
     ///
@@ -118,7 +235,7 @@ pub enum TokenInstruction {
     /// - 1 USDC = 1,000,000 base units
     /// - Display: amount / 10^6
     InitializeMint {
-        /// Number of decimals for display purposes
+        /// Number of decimals for display purposes (at most `MAX_DECIMALS`)
         decimals: u8,
 
         /// Authority that can mint new tokens
@@ -137,7 +254,7 @@ pub enum TokenInstruction {
     /// | 0 | account | ✓ | | The account to initialize |
     /// | 1 | mint | | | The mint this account holds |
     /// | 2 | owner | | | The owner of this account |
-    /// | 3 | rent | | | Rent sysvar |
+    /// | 3 | rent | | | Rent sysvar (optional, falls back to `Rent::get()`) |
     ///
     /// # Data Layout
     ///
@@ -187,6 +304,7 @@ pub enum TokenInstruction {
     /// | 0 | source | ✓ | | Source token account |
     /// | 1 | destination | ✓ | | Destination token account |
     /// | 2 | authority | | ✓ | Owner or delegate |
+    /// | 3 | mint | | | The source's mint |
     ///
     /// # Account Requirements (Multisig Authority)
     ///
@@ -195,7 +313,11 @@ pub enum TokenInstruction {
     /// | 0 | source | ✓ | | Source token account |
     /// | 1 | destination | ✓ | | Destination token account |
     /// | 2 | multisig | | | Multisig authority |
-    /// | 3..3+M | signers | | ✓ | M signer accounts |
+    /// | 3 | mint | | | The source's mint |
+    /// | 4..4+M | signers | | ✓ | M signer accounts |
+    ///
+    /// The mint is always required, so its non-transferable flag and
+    /// transfer hook can't be skipped.
     ///
     /// # Data Layout
     ///
@@ -208,6 +330,30 @@ pub enum TokenInstruction {
         amount: u64,
     },
 
+    /// Transfer, then return the source's new balance as return data.
+    ///
+    /// Same accounts and checks as Transfer. Encoded as a Transfer
+    /// with a trailing flag byte, so the plain 9-byte Transfer layout
+    /// is unchanged.
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (3)
+    /// [1..9]: amount (u64, little-endian)
+    /// [9]: return_balance flag (1)
+    /// ```
+    ///
+    /// # Return Data
+    ///
+    /// ```text
+    /// [0..8]: source amount after the transfer (u64, little-endian)
+    /// ```
+    TransferWithReturnData {
+        /// Amount of tokens to transfer
+        amount: u64,
+    },
+
     /// Approve a delegate to transfer tokens.
     ///
     /// # Account Requirements
@@ -230,6 +376,7 @@ pub enum TokenInstruction {
     /// - Replaces any existing delegate
     /// - Amount is the MAXIMUM the delegate can transfer
     /// - Use Revoke to remove the delegate
+    /// - Fails with AccountFrozen on a frozen account
     Approve {
         /// Maximum amount delegate can transfer
         amount: u64,
@@ -271,8 +418,12 @@ pub enum TokenInstruction {
     ///
     /// # Notes
     ///
-    /// - Setting to None is PERMANENT for MintTokens and FreezeAccount
-    /// - Cannot change AccountOwner to None
+    /// - Setting to None is PERMANENT for MintTokens and FreezeAccount; a
+    ///   mint without a freeze authority fails with `CannotAddFreezeAuthority`
+    /// - Cannot change AccountOwner to None, or on a frozen account
+    /// - CloseAccount on a mint sets the mint's close authority; the mint
+    ///   authority may set it while unset, and it needs an extended mint
+    /// - Data must be exactly 3 bytes (None) or 35 bytes (Some)
     SetAuthority {
         /// Which authority to change
         authority_type: AuthorityType,
@@ -288,7 +439,7 @@ pub enum TokenInstruction {
     /// | # | Account | Writable | Signer | Description |
     /// |---|---------|----------|--------|-------------|
     /// | 0 | mint | ✓ | | The mint |
-    /// | 1 | destination | ✓ | | Account to mint to |
+    /// | 1 | destination | ✓ | | Token account to mint to (not the mint) |
     /// | 2 | mint_authority | | ✓ | Mint authority |
     ///
     /// # Data Layout
@@ -323,15 +474,16 @@ pub enum TokenInstruction {
         amount: u64,
     },
 
-    /// Close a token account and reclaim rent.
+    /// Close a token account or mint and reclaim rent.
     ///
     /// # Account Requirements
     ///
     /// | # | Account | Writable | Signer | Description |
     /// |---|---------|----------|--------|-------------|
-    /// | 0 | account | ✓ | | Account to close |
+    /// | 0 | account | ✓ | | Token account or mint to close |
     /// | 1 | destination | ✓ | | Receives the rent lamports |
     /// | 2 | authority | | ✓ | Close authority or owner |
+    /// | 3..3+M | signers | | ✓ | Multisig signers (if applicable) |
     ///
     /// # Data Layout
     ///
@@ -341,8 +493,10 @@ pub enum TokenInstruction {
     ///
     /// # Constraints
     ///
-    /// - Token balance must be 0
-    /// - For native (wrapped SOL), all lamports transferred
+    /// - Token balance must be 0 (except native), and the account must not be frozen
+    /// - For native (wrapped SOL), all lamports transferred; to send the
+    ///   rent reserve elsewhere, use CloseAccountWithReserve
+    /// - A mint needs zero supply and is closed by its close authority
     CloseAccount,
 
     /// Freeze a token account (prevent transfers).
@@ -378,161 +532,1244 @@ pub enum TokenInstruction {
     /// [0]: discriminant (11)
     /// ```
     ThawAccount,
-}
 
-// =============================================================================
-// INSTRUCTION PARSING (UNPACK)
-// =============================================================================
+    // =========================================================================
+    // EXTENSIONS
+    // =========================================================================
 
-impl TokenInstruction {
-    /// Parse instruction data into a TokenInstruction.
+    /// Transfer tokens from one source to many destinations.
     ///
-    /// # Arguments
-    /// * `input` - Raw instruction data bytes
+    /// Equivalent to N Transfer instructions sharing one source and
+    /// authority, but validated and applied as a single unit.
     ///
-    /// # Returns
-    /// * `Ok(TokenInstruction)` - Successfully parsed instruction
-    /// * `Err(InvalidInstruction)` - Could not parse
+    /// # Account Requirements
     ///
-    /// # Format
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | source | ✓ | | Source token account |
+    /// | 1 | authority | | ✓ | Owner or delegate |
+    /// | 2 | mint | | | The source's mint |
+    /// | 3..3+N | destinations | ✓ | | One per amount, same order |
+    /// | 3+N.. | signers | | ✓ | Multisig signers (if applicable) |
     ///
-    /// First byte is the discriminant, remaining bytes are instruction-specific.
-    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
-        // Get the discriminant (first byte)
-        let (&discriminant, rest) = input
-            .split_first()
-            .ok_or(TokenError::InvalidInstruction)?;
-
-        // Parse based on discriminant
-        Ok(match discriminant {
-            // =================================================================
-            // 0: InitializeMint
-            // =================================================================
-            0 => {
-                // Need at least: decimals(1) + mint_authority(32) + option(1) = 34 bytes
-                if rest.len() < 34 {
-                    return Err(TokenError::InvalidInstruction.into());
-                }
-
-                let decimals = rest[0];
+    /// Every leg gets Transfer's checks: non-transferable mints, memo-required
+    /// destinations, and the mint's transfer hook (invoked once per leg).
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (26)
+    /// [1]: count N (u8, at most MAX_BATCH_LEN)
+    /// [2..2+8N]: amounts (N × u64, little-endian)
+    /// ```
+    TransferBatch {
+        /// Amount for each destination, in account order
+        amounts: Vec<u64>,
+    },
 
-                // Parse mint_authority (bytes 1-32)
-                let mint_authority = Pubkey::new_from_array(
-                    rest[1..33]
-                        .try_into()
-                        .map_err(|_| TokenError::InvalidInstruction)?,
-                );
+    /// Burn an account's entire balance.
+    ///
+    /// Same as Burn with `amount = account.amount`, without the
+    /// client having to read the balance first.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | account | ✓ | | Token account to burn from |
+    /// | 1 | mint | ✓ | | The token mint |
+    /// | 2 | authority | | ✓ | Owner or delegate |
+    /// | 3..3+M | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (27)
+    /// ```
+    BurnAll,
 
-                // Parse freeze_authority option
-                let freeze_authority = if rest[33] == 1 {
-                    // Has freeze authority - need 32 more bytes
-                    if rest.len() < 66 {
-                        return Err(TokenError::InvalidInstruction.into());
-                    }
-                    Some(Pubkey::new_from_array(
-                        rest[34..66]
-                            .try_into()
-                            .map_err(|_| TokenError::InvalidInstruction)?,
-                    ))
-                } else if rest[33] == 0 {
-                    None
-                } else {
-                    return Err(TokenError::InvalidInstruction.into());
-                };
+    /// Approve a delegate for the source account's entire balance.
+    ///
+    /// Same as Approve with `amount = source.amount`, read on-chain.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | source | ✓ | | Token account to approve from |
+    /// | 1 | delegate | | | The delegate to approve |
+    /// | 2 | owner | | ✓ | Token account owner |
+    /// | 3..3+M | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (28)
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The allowance is a snapshot; tokens received later aren't included
+    /// - Frozen accounts are rejected
+    ApproveAll,
 
-                TokenInstruction::InitializeMint {
-                    decimals,
-                    mint_authority,
-                    freeze_authority,
-                }
-            }
+    /// Resize a token account to make room for extension data.
+    ///
+    /// `new_len` must be `Account::LEN` or `Account::EXTENDED_LEN`; any
+    /// other size fails with `InvalidAccountDataLength`, as does shrinking
+    /// while an extension field is set. The payer covers any extra rent
+    /// needed to stay rent exempt.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | account | ✓ | | Token account to resize |
+    /// | 1 | payer | ✓ | ✓ | Pays for any extra rent |
+    /// | 2 | system_program | | | System program |
+    /// | 3 | owner | | ✓ | Account owner |
+    /// | 4..4+M | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (29)
+    /// [1..9]: new_len (u64, little-endian)
+    /// ```
+    Reallocate {
+        /// New account data length in bytes
+        new_len: u64,
+    },
 
-            // =================================================================
-            // 1: InitializeAccount
-            // =================================================================
-            1 => TokenInstruction::InitializeAccount,
+    /// Permanently block transfers of a mint's tokens.
+    ///
+    /// For soulbound or loyalty tokens. Minting and burning still work.
+    /// The mint must be an extended mint (`Mint::EXTENDED_LEN` bytes),
+    /// and the flag can't be cleared.
+    ///
+    /// Every transfer instruction takes the mint as a required account
+    /// and fails with `NonTransferable` if it is flagged.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | mint | ✓ | | The extended mint to flag |
+    /// | 1 | authority | | ✓ | Mint authority |
+    /// | 2..2+M | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (30)
+    /// ```
+    SetNonTransferable,
 
-            // =================================================================
-            // 2: InitializeMultisig
-            // =================================================================
-            2 => {
-                if rest.is_empty() {
-                    return Err(TokenError::InvalidInstruction.into());
-                }
-                TokenInstruction::InitializeMultisig { m: rest[0] }
-            }
+    /// Transfer tokens, skimming a basis-point fee to a fee account.
+    ///
+    /// `fee = amount * fee_basis_points / 10_000` (rounded down) goes to
+    /// the fee destination; the destination receives `amount - fee`.
+    /// All three accounts must share a mint and none may be frozen.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | source | ✓ | | Source token account |
+    /// | 1 | destination | ✓ | | Destination token account |
+    /// | 2 | fee_destination | ✓ | | Receives the fee |
+    /// | 3 | authority | | ✓ | Owner or delegate |
+    /// | 4 | mint | | | The source's mint |
+    /// | 5..5+M | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// Both receivers get Transfer's checks: non-transferable mints,
+    /// memo-required accounts, and the mint's transfer hook (invoked once
+    /// per receiver).
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (31)
+    /// [1..9]: amount (u64, little-endian)
+    /// [9..11]: fee_basis_points (u16, little-endian, at most 10_000)
+    /// ```
+    TransferWithFee {
+        /// Amount debited from the source (fee included)
+        amount: u64,
 
-            // =================================================================
-            // 3: Transfer
-            // =================================================================
-            3 => {
-                if rest.len() < 8 {
-                    return Err(TokenError::InvalidInstruction.into());
-                }
-                let amount = u64::from_le_bytes(
-                    rest[..8]
-                        .try_into()
-                        .map_err(|_| TokenError::InvalidInstruction)?,
-                );
-                TokenInstruction::Transfer { amount }
-            }
+        /// Fee rate in basis points (100 = 1%)
+        fee_basis_points: u16,
+    },
 
-            // =================================================================
-            // 4: Approve
-            // =================================================================
-            4 => {
-                if rest.len() < 8 {
-                    return Err(TokenError::InvalidInstruction.into());
-                }
-                let amount = u64::from_le_bytes(
-                    rest[..8]
-                        .try_into()
-                        .map_err(|_| TokenError::InvalidInstruction)?,
-                );
-                TokenInstruction::Approve { amount }
-            }
+    /// Create and initialize a token account in one instruction.
+    ///
+    /// Creates the account through the System Program at
+    /// `Account::LEN`, funded for rent exemption by the payer, then
+    /// initializes it for `mint` and `owner`. Replaces the usual
+    /// create + InitializeAccount pair.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | payer | ✓ | ✓ | Pays the rent-exempt balance |
+    /// | 1 | new_account | ✓ | ✓ | The account to create |
+    /// | 2 | mint | | | The mint this account holds |
+    /// | 3 | system_program | | | System Program |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (32)
+    /// [1..33]: owner (Pubkey, 32 bytes)
+    /// ```
+    CreateAccount {
+        /// Owner of the new account
+        owner: Pubkey,
+    },
 
-            // =================================================================
-            // 5: Revoke
-            // =================================================================
-            5 => TokenInstruction::Revoke,
+    /// Freeze many token accounts of one mint.
+    ///
+    /// Same as FreezeAccount for each listed account, with the freeze
+    /// authority checked once. Every account must belong to the mint;
+    /// if any doesn't (or can't be frozen), nothing is frozen.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | mint | | | The mint |
+    /// | 1 | authority | | ✓ | Freeze authority |
+    /// | 2..2+N | accounts | ✓ | | Token accounts to freeze (N = count) |
+    /// | 2+N.. | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (33)
+    /// [1]: count (u8)
+    /// ```
+    FreezeAccounts {
+        /// Number of token accounts to freeze
+        count: u8,
+    },
 
-            // =================================================================
-            // 6: SetAuthority
-            // =================================================================
-            6 => {
-                if rest.len() < 2 {
-                    return Err(TokenError::InvalidInstruction.into());
-                }
+    /// Return a compact summary of a mint as return data.
+    ///
+    /// Read-only. Lets lightweight clients and explorers read a mint
+    /// without parsing raw account bytes. An uninitialized mint is
+    /// reported through the is_initialized byte rather than failing.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | mint | | | The mint to read |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (34)
+    /// ```
+    ///
+    /// # Return Data
+    ///
+    /// ```text
+    /// [0..8]: supply (u64, little-endian)
+    /// [8]: decimals (u8)
+    /// [9]: is_initialized (0 or 1)
+    /// [10]: has_mint_authority (0 or 1)
+    /// [11]: has_freeze_authority (0 or 1)
+    /// ```
+    GetMintInfo,
+
+    /// Return a token account's balance as return data.
+    ///
+    /// Read-only. A cheap balance read that can be composed with other
+    /// instructions in one transaction, instead of fetching and parsing
+    /// the account off-chain.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | account | | | The token account to read |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (35)
+    /// ```
+    ///
+    /// # Return Data
+    ///
+    /// ```text
+    /// [0..8]: amount (u64, little-endian)
+    /// ```
+    GetAccountBalance,
+
+    /// Return a multisig's configuration as return data.
+    ///
+    /// Read-only. Lets a client or another program verify the M-of-N
+    /// setup and signer set on-chain before trusting the multisig.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | multisig | | | The multisig to read |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (36)
+    /// ```
+    ///
+    /// # Return Data
+    ///
+    /// ```text
+    /// [0]: m (u8)
+    /// [1]: n (u8)
+    /// [2..2+32*n]: the n valid signer pubkeys, in order
+    /// ```
+    GetMultisigInfo,
+
+    /// Mint new tokens to many destinations.
+    ///
+    /// Equivalent to N MintTo instructions sharing one mint and
+    /// authority, with the supply cap checked against the total and the
+    /// supply updated once.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | mint | ✓ | | The mint |
+    /// | 1 | authority | | ✓ | Mint authority |
+    /// | 2..2+N | destinations | ✓ | | One per amount, same order |
+    /// | 2+N.. | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (37)
+    /// [1]: count N (u8, at most MAX_BATCH_LEN)
+    /// [2..2+8N]: amounts (N × u64, little-endian)
+    /// ```
+    MintToBatch {
+        /// Amount for each destination, in account order
+        amounts: Vec<u64>,
+    },
+
+    /// Revoke the delegate of many token accounts at once.
+    ///
+    /// Same as Revoke for each listed account. Every account must be
+    /// owned by the same owner, who signs once. Frozen accounts are
+    /// cleared too: revoking moves no tokens, so freezing doesn't
+    /// block it.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | owner | | ✓ | Owner of every listed account |
+    /// | 1..1+N | accounts | ✓ | | Token accounts to revoke (N = count) |
+    /// | 1+N.. | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (38)
+    /// [1]: count (u8, at most MAX_BATCH_LEN)
+    /// ```
+    RevokeBatch {
+        /// Number of token accounts to revoke
+        count: u8,
+    },
+
+    /// Upgrade a legacy token account to the extended layout.
+    ///
+    /// Grows a `Account::LEN` (165 byte) account to
+    /// `Account::EXTENDED_LEN`, writes the current layout version and
+    /// default extension fields, and has the payer cover the extra rent.
+    /// The account state is unchanged. An account that is already
+    /// extended is rejected.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | account | ✓ | | Legacy token account |
+    /// | 1 | payer | ✓ | ✓ | Pays for the extra rent |
+    /// | 2 | system_program | | | System program |
+    /// | 3 | owner | | ✓ | Account owner |
+    /// | 4..4+M | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (39)
+    /// ```
+    MigrateAccount,
+
+    /// Initialize a token account, or accept one that already matches.
+    ///
+    /// Like InitializeAccount with the owner passed as data, but an
+    /// account that is already initialized for the same mint and owner
+    /// succeeds without changes, so "create if missing" flows can't fail
+    /// on a second attempt. A different mint or owner still fails.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | account | ✓ | | Token account to initialize |
+    /// | 1 | mint | | | Mint this account will hold |
+    /// | 2 | rent | | | Rent sysvar (optional, falls back to `Rent::get()`) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (40)
+    /// [1..33]: owner (Pubkey, 32 bytes)
+    /// ```
+    InitializeAccountIdempotent {
+        /// Owner the account has, or will be given
+        owner: Pubkey,
+    },
+
+    /// Require (or stop requiring) a memo on inbound transfers.
+    ///
+    /// The account must be an extended account (`Account::EXTENDED_LEN`
+    /// bytes). While set, transfers into it must pass the instructions
+    /// sysvar after the authority and run a Memo program instruction
+    /// earlier in the same transaction.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | account | ✓ | | The extended token account |
+    /// | 1 | owner | | ✓ | Account owner |
+    /// | 2..2+M | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (41)
+    /// [1]: required (0 = off, 1 = on)
+    /// ```
+    SetMemoRequired {
+        /// Whether inbound transfers need a memo
+        required: bool,
+    },
+
+    /// Set or clear a mint's maximum supply.
+    ///
+    /// Once set, MintTo fails with `SupplyCapExceeded` if it would push
+    /// supply past the cap. The mint must be an extended mint
+    /// (`Mint::EXTENDED_LEN` bytes), and the cap can't be set below the
+    /// current supply.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | mint | ✓ | | The extended mint |
+    /// | 1 | authority | | ✓ | Mint authority |
+    /// | 2..2+M | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (42)
+    /// [1]: cap_option (0 = None, 1 = Some)
+    /// [2..10]: cap (u64, little-endian, if option = 1)
+    /// ```
+    SetSupplyCap {
+        /// Maximum supply, or None to remove the cap
+        cap: Option<u64>,
+    },
+
+    /// Pause or resume minting.
+    ///
+    /// The mint must be an extended mint (`Mint::EXTENDED_LEN` bytes).
+    /// While paused, MintTo fails with `MintPaused`. Burns and
+    /// transfers are unaffected. Only the mint authority can toggle it.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | mint | ✓ | | The extended mint |
+    /// | 1 | authority | | ✓ | Mint authority |
+    /// | 2..2+M | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (43)
+    /// [1]: paused (0 = resume, 1 = pause)
+    /// ```
+    SetMintPaused {
+        /// Whether minting is paused
+        paused: bool,
+    },
+
+    /// Set or clear a mint's transfer hook program.
+    ///
+    /// While set, Transfer calls the hook after moving the tokens (see
+    /// `transfer_hook_instruction` for what the hook receives) and fails
+    /// with `TransferHookFailed` if it errors. The mint must be an
+    /// extended mint (`Mint::EXTENDED_LEN` bytes). Only the mint
+    /// authority can change it.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | mint | ✓ | | The extended mint |
+    /// | 1 | authority | | ✓ | Mint authority |
+    /// | 2..2+M | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (44)
+    /// [1]: program_option (0 = None, 1 = Some)
+    /// [2..34]: program (Pubkey, if option = 1)
+    /// ```
+    SetTransferHook {
+        /// Hook program, or None to remove the hook
+        program: Option<Pubkey>,
+    },
+
+    /// Approve a delegate until a unix timestamp.
+    ///
+    /// Like Approve, but the delegate can only spend the allowance up to
+    /// and including `expiry_unix_ts`. After that, delegate transfers
+    /// and burns fail with `DelegateExpired` until the owner approves
+    /// again. Delegate transfers against an expiring approval must pass
+    /// the Clock sysvar after the authority.
+    ///
+    /// The source must be an extended account (`Account::EXTENDED_LEN`
+    /// bytes); see MigrateAccount.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | source | ✓ | | Token account to approve from |
+    /// | 1 | delegate | | | The delegate to approve |
+    /// | 2 | owner | | ✓ | Token account owner |
+    /// | 3..3+M | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (45)
+    /// [1..9]: amount (u64, little-endian)
+    /// [9..17]: expiry_unix_ts (i64, little-endian)
+    /// ```
+    ApproveWithExpiry {
+        /// Amount the delegate may transfer
+        amount: u64,
+
+        /// Last unix timestamp at which the delegate may transfer
+        expiry_unix_ts: i64,
+    },
+
+    /// Initialize a new token account with a close authority already set.
+    ///
+    /// Same as InitializeAccount, but saves the follow-up
+    /// SetAuthority(CloseAccount) transaction.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | account | ✓ | | The account to initialize |
+    /// | 1 | mint | | | The mint this account holds |
+    /// | 2 | owner | | | The owner of this account |
+    /// | 3 | rent | | | Rent sysvar (optional, falls back to `Rent::get()`) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (56)
+    /// [1..33]: close_authority (Pubkey, 32 bytes)
+    /// ```
+    InitializeAccountWithCloseAuthority {
+        /// Authority that can close the new account
+        close_authority: Pubkey,
+    },
+
+    /// Return a mint's current supply as return data.
+    ///
+    /// Read-only. Meant for simulated transactions, so a client can
+    /// read supply alongside other instruction results.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | mint | | | The mint to read |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (57)
+    /// ```
+    ///
+    /// # Return Data
+    ///
+    /// ```text
+    /// [0..8]: supply (u64, little-endian)
+    /// ```
+    GetSupply,
+
+    /// Compare a mint's supply with the sum of its token account balances.
+    ///
+    /// Read-only auditing helper. Writes the recorded supply and the
+    /// computed sum as return data so both come back from one call.
+    /// With `strict`, a mismatch fails with `InvariantViolation`.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | mint | | | The mint to audit |
+    /// | 1..1+N | accounts | | | Token accounts of this mint |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (58)
+    /// [1]: strict (0 = report only, 1 = fail on mismatch)
+    /// ```
+    ///
+    /// # Return Data
+    ///
+    /// ```text
+    /// [0..8]: supply (u64, little-endian)
+    /// [8..16]: sum of balances (u64, little-endian)
+    /// ```
+    ReconcileSupply {
+        /// Fail with InvariantViolation if the sum differs from supply
+        strict: bool,
+    },
+
+    /// Approve a delegate and set the close authority in one call.
+    ///
+    /// Same as Approve followed by SetAuthority(CloseAccount), for
+    /// custodial flows that need both. Only the owner can call it;
+    /// a delegate can't. Fails if the account is frozen, or with
+    /// `CloseAuthorityMismatch` if the close authority already belongs to
+    /// someone other than the owner.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | source | ✓ | | Token account |
+    /// | 1 | delegate | | | Account to approve |
+    /// | 2 | owner | | ✓ | Token account owner |
+    /// | 3..3+M | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (59)
+    /// [1..9]: amount (u64, little-endian)
+    /// [9..41]: close_authority (Pubkey, 32 bytes)
+    /// ```
+    ApproveAndSetCloseAuthority {
+        /// Amount the delegate may transfer
+        amount: u64,
+
+        /// New close authority
+        close_authority: Pubkey,
+    },
+
+    /// Check a token account against an expected mint and owner.
+    ///
+    /// Read-only: writes a status byte as return data so clients can
+    /// validate an account in a single call. A mismatch is reported,
+    /// not raised as an error.
+    ///
+    /// | Status | Meaning |
+    /// |--------|---------|
+    /// | 0 | Mint and owner both match |
+    /// | 1 | Mint mismatch |
+    /// | 2 | Owner mismatch |
+    /// | 3 | Account is uninitialized or frozen |
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | account | | | Token account to check |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (60)
+    /// [1..33]: expected_mint (Pubkey, 32 bytes)
+    /// [33..65]: expected_owner (Pubkey, 32 bytes)
+    /// ```
+    VerifyAccount {
+        /// Mint the account should belong to
+        expected_mint: Pubkey,
+
+        /// Owner the account should have
+        expected_owner: Pubkey,
+    },
+
+    /// Close a wrapped SOL account, splitting off its rent reserve.
+    ///
+    /// Same as CloseAccount, but the rent-exempt reserve goes to the
+    /// reserve destination and only the wrapped SOL above it goes to the
+    /// destination. The reserve destination has its own fixed slot, so
+    /// it can't be confused with a multisig signer. Fails with
+    /// `InvalidState` for anything but a native token account.
+    ///
+    /// # Account Requirements
+    ///
+    /// | # | Account | Writable | Signer | Description |
+    /// |---|---------|----------|--------|-------------|
+    /// | 0 | account | ✓ | | Wrapped SOL account to close |
+    /// | 1 | destination | ✓ | | Receives the wrapped SOL |
+    /// | 2 | authority | | ✓ | Close authority or owner |
+    /// | 3 | reserve_destination | ✓ | | Receives the rent reserve |
+    /// | 4..4+M | signers | | ✓ | Multisig signers (if applicable) |
+    ///
+    /// # Data Layout
+    ///
+    /// ```text
+    /// [0]: discriminant (61)
+    /// ```
+    CloseAccountWithReserve,
+}
+
+// =============================================================================
+// INSTRUCTION PARSING (UNPACK)
+// =============================================================================
+
+impl TokenInstruction {
+    /// Parse instruction data into a TokenInstruction.
+    ///
+    /// # Arguments
+    /// * `input` - Raw instruction data bytes
+    ///
+    /// # Returns
+    /// * `Ok(TokenInstruction)` - Successfully parsed instruction
+    /// * `Err(InvalidInstruction)` - Could not parse
+    ///
+    /// # Format
+    ///
+    /// First byte is the discriminant, remaining bytes are instruction-specific.
+    /// Empty input fails with `InvalidInstruction` before any matching.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        // Get the discriminant (first byte)
+        let (&discriminant, rest) = input
+            .split_first()
+            .ok_or(TokenError::InvalidInstruction)?;
+
+        // Parse based on discriminant
+        Ok(match discriminant {
+            // =================================================================
+            // 0: InitializeMint
+            // =================================================================
+            0 => {
+                // Need at least: decimals(1) + mint_authority(32) + option(1) = 34 bytes
+                if rest.len() < 34 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+
+                let decimals = rest[0];
+
+                // Parse mint_authority (bytes 1-32)
+                let mint_authority = Pubkey::new_from_array(
+                    rest[1..33]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+
+                // Parse freeze_authority option
+                let freeze_authority = if rest[33] == 1 {
+                    // Has freeze authority - need 32 more bytes
+                    if rest.len() < 66 {
+                        return Err(TokenError::InvalidInstruction.into());
+                    }
+                    Some(Pubkey::new_from_array(
+                        rest[34..66]
+                            .try_into()
+                            .map_err(|_| TokenError::InvalidInstruction)?,
+                    ))
+                } else if rest[33] == 0 {
+                    None
+                } else {
+                    return Err(TokenError::InvalidInstruction.into());
+                };
+
+                TokenInstruction::InitializeMint {
+                    decimals,
+                    mint_authority,
+                    freeze_authority,
+                }
+            }
+
+            // =================================================================
+            // 1: InitializeAccount
+            // =================================================================
+            1 => TokenInstruction::InitializeAccount,
+
+            // =================================================================
+            // 2: InitializeMultisig
+            // =================================================================
+            2 => {
+                if rest.is_empty() {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                TokenInstruction::InitializeMultisig { m: rest[0] }
+            }
+
+            // =================================================================
+            // 3: Transfer
+            // =================================================================
+            3 => {
+                if rest.len() < 8 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                let amount = u64::from_le_bytes(
+                    rest[..8]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                // Optional trailing flag: 1 = return the new source balance.
+                // Nothing may follow it
+                match &rest[8..] {
+                    [] | [0] => TokenInstruction::Transfer { amount },
+                    [1] => TokenInstruction::TransferWithReturnData { amount },
+                    _ => return Err(TokenError::InvalidInstruction.into()),
+                }
+            }
+
+            // =================================================================
+            // 4: Approve
+            // =================================================================
+            4 => {
+                if rest.len() < 8 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                let amount = u64::from_le_bytes(
+                    rest[..8]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                TokenInstruction::Approve { amount }
+            }
+
+            // =================================================================
+            // 5: Revoke
+            // =================================================================
+            5 => TokenInstruction::Revoke,
+
+            // =================================================================
+            // 6: SetAuthority
+            // =================================================================
+            6 => {
+                if rest.len() < 2 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+
+                let authority_type = AuthorityType::from_u8(rest[0])?;
+
+                // Exact lengths: trailing bytes are rejected
+                let new_authority = if rest[1] == 1 {
+                    if rest.len() != 34 {
+                        return Err(TokenError::InvalidInstruction.into());
+                    }
+                    Some(Pubkey::new_from_array(
+                        rest[2..34]
+                            .try_into()
+                            .map_err(|_| TokenError::InvalidInstruction)?,
+                    ))
+                } else if rest[1] == 0 {
+                    if rest.len() != 2 {
+                        return Err(TokenError::InvalidInstruction.into());
+                    }
+                    None
+                } else {
+                    return Err(TokenError::InvalidInstruction.into());
+                };
+
+                TokenInstruction::SetAuthority {
+                    authority_type,
+                    new_authority,
+                }
+            }
+
+            // =================================================================
+            // 7: MintTo
+            // =================================================================
+            7 => {
+                if rest.len() < 8 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                let amount = u64::from_le_bytes(
+                    rest[..8]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                TokenInstruction::MintTo { amount }
+            }
+
+            // =================================================================
+            // 8: Burn
+            // =================================================================
+            8 => {
+                if rest.len() < 8 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                let amount = u64::from_le_bytes(
+                    rest[..8]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                TokenInstruction::Burn { amount }
+            }
+
+            // =================================================================
+            // 9: CloseAccount
+            // =================================================================
+            9 => TokenInstruction::CloseAccount,
+
+            // =================================================================
+            // 10: FreezeAccount
+            // =================================================================
+            10 => TokenInstruction::FreezeAccount,
+
+            // =================================================================
+            // 11: ThawAccount
+            // =================================================================
+            11 => TokenInstruction::ThawAccount,
+
+            // =================================================================
+            // 26: TransferBatch
+            // =================================================================
+            26 => {
+                let (&count, rest) = rest
+                    .split_first()
+                    .ok_or(TokenError::InvalidInstruction)?;
+                let count = count as usize;
+                if count > MAX_BATCH_LEN {
+                    return Err(TokenError::BatchTooLarge.into());
+                }
+                // Exact length: trailing bytes are rejected
+                if rest.len() != count * 8 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                let amounts = rest
+                    .chunks_exact(8)
+                    .map(|chunk| {
+                        chunk
+                            .try_into()
+                            .map(u64::from_le_bytes)
+                            .map_err(|_| TokenError::InvalidInstruction)
+                    })
+                    .collect::<Result<Vec<u64>, _>>()?;
+                TokenInstruction::TransferBatch { amounts }
+            }
+
+            // =================================================================
+            // 27: BurnAll
+            // =================================================================
+            27 => {
+                // No payload: trailing bytes are rejected
+                if !rest.is_empty() {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                TokenInstruction::BurnAll
+            }
+
+            // =================================================================
+            // 28: ApproveAll
+            // =================================================================
+            28 => {
+                // No payload: trailing bytes are rejected
+                if !rest.is_empty() {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                TokenInstruction::ApproveAll
+            }
+
+            // =================================================================
+            // 29: Reallocate
+            // =================================================================
+            29 => {
+                // Exact length: trailing bytes are rejected
+                if rest.len() != 8 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                let new_len = u64::from_le_bytes(
+                    rest[..8]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                TokenInstruction::Reallocate { new_len }
+            }
+
+            // =================================================================
+            // 30: SetNonTransferable
+            // =================================================================
+            30 => {
+                // No payload: trailing bytes are rejected
+                if !rest.is_empty() {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                TokenInstruction::SetNonTransferable
+            }
+
+            // =================================================================
+            // 31: TransferWithFee
+            // =================================================================
+            31 => {
+                // Exact length: trailing bytes are rejected
+                if rest.len() != 10 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                let amount = u64::from_le_bytes(
+                    rest[..8]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                let fee_basis_points = u16::from_le_bytes(
+                    rest[8..10]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                TokenInstruction::TransferWithFee {
+                    amount,
+                    fee_basis_points,
+                }
+            }
+
+            // =================================================================
+            // 32: CreateAccount
+            // =================================================================
+            32 => {
+                // Exact length: trailing bytes are rejected
+                if rest.len() != 32 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                let owner = Pubkey::new_from_array(
+                    rest[..32]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                TokenInstruction::CreateAccount { owner }
+            }
+
+            // =================================================================
+            // 33: FreezeAccounts
+            // =================================================================
+            33 => {
+                // Exact length: trailing bytes are rejected
+                if rest.len() != 1 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                TokenInstruction::FreezeAccounts { count: rest[0] }
+            }
+
+            // =================================================================
+            // 34: GetMintInfo
+            // =================================================================
+            34 => {
+                // No payload: trailing bytes are rejected
+                if !rest.is_empty() {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                TokenInstruction::GetMintInfo
+            }
+
+            // =================================================================
+            // 35: GetAccountBalance
+            // =================================================================
+            35 => {
+                // No payload: trailing bytes are rejected
+                if !rest.is_empty() {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                TokenInstruction::GetAccountBalance
+            }
+
+            // =================================================================
+            // 36: GetMultisigInfo
+            // =================================================================
+            36 => {
+                // No payload: trailing bytes are rejected
+                if !rest.is_empty() {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                TokenInstruction::GetMultisigInfo
+            }
+
+            // =================================================================
+            // 37: MintToBatch
+            // =================================================================
+            37 => {
+                let (&count, rest) = rest
+                    .split_first()
+                    .ok_or(TokenError::InvalidInstruction)?;
+                let count = count as usize;
+                if count > MAX_BATCH_LEN {
+                    return Err(TokenError::BatchTooLarge.into());
+                }
+                // Exact length: trailing bytes are rejected
+                if rest.len() != count * 8 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                let amounts = rest
+                    .chunks_exact(8)
+                    .map(|chunk| {
+                        chunk
+                            .try_into()
+                            .map(u64::from_le_bytes)
+                            .map_err(|_| TokenError::InvalidInstruction)
+                    })
+                    .collect::<Result<Vec<u64>, _>>()?;
+                TokenInstruction::MintToBatch { amounts }
+            }
+
+            // =================================================================
+            // 38: RevokeBatch
+            // =================================================================
+            38 => {
+                // Exact length: trailing bytes are rejected
+                let count = match rest {
+                    [count] => *count,
+                    _ => return Err(TokenError::InvalidInstruction.into()),
+                };
+                if count as usize > MAX_BATCH_LEN {
+                    return Err(TokenError::BatchTooLarge.into());
+                }
+                TokenInstruction::RevokeBatch { count }
+            }
 
-                let authority_type = AuthorityType::from_u8(rest[0])?;
+            // =================================================================
+            // 39: MigrateAccount
+            // =================================================================
+            39 => {
+                // No payload: trailing bytes are rejected
+                if !rest.is_empty() {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                TokenInstruction::MigrateAccount
+            }
 
-                let new_authority = if rest[1] == 1 {
-                    if rest.len() < 34 {
-                        return Err(TokenError::InvalidInstruction.into());
-                    }
-                    Some(Pubkey::new_from_array(
-                        rest[2..34]
-                            .try_into()
-                            .map_err(|_| TokenError::InvalidInstruction)?,
-                    ))
-                } else if rest[1] == 0 {
-                    None
-                } else {
+            // =================================================================
+            // 40: InitializeAccountIdempotent
+            // =================================================================
+            40 => {
+                // Exact length: trailing bytes are rejected
+                if rest.len() != 32 {
                     return Err(TokenError::InvalidInstruction.into());
+                }
+                let owner = Pubkey::new_from_array(
+                    rest[..32]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                TokenInstruction::InitializeAccountIdempotent { owner }
+            }
+
+            // =================================================================
+            // 41: SetMemoRequired
+            // =================================================================
+            41 => {
+                // Exact length: trailing bytes are rejected
+                let required = match rest {
+                    [0] => false,
+                    [1] => true,
+                    _ => return Err(TokenError::InvalidInstruction.into()),
                 };
+                TokenInstruction::SetMemoRequired { required }
+            }
 
-                TokenInstruction::SetAuthority {
-                    authority_type,
-                    new_authority,
-                }
+            // =================================================================
+            // 42: SetSupplyCap
+            // =================================================================
+            42 => {
+                let (&option, rest) = rest
+                    .split_first()
+                    .ok_or(TokenError::InvalidInstruction)?;
+
+                // Exact lengths: trailing bytes are rejected
+                let cap = match option {
+                    0 if rest.is_empty() => None,
+                    1 => {
+                        if rest.len() != 8 {
+                            return Err(TokenError::InvalidInstruction.into());
+                        }
+                        Some(u64::from_le_bytes(
+                            rest[..8]
+                                .try_into()
+                                .map_err(|_| TokenError::InvalidInstruction)?,
+                        ))
+                    }
+                    _ => return Err(TokenError::InvalidInstruction.into()),
+                };
+
+                TokenInstruction::SetSupplyCap { cap }
             }
 
             // =================================================================
-            // 7: MintTo
+            // 43: SetMintPaused
             // =================================================================
-            7 => {
-                if rest.len() < 8 {
+            43 => {
+                // Exact length: trailing bytes are rejected
+                let paused = match rest {
+                    [0] => false,
+                    [1] => true,
+                    _ => return Err(TokenError::InvalidInstruction.into()),
+                };
+                TokenInstruction::SetMintPaused { paused }
+            }
+
+            // =================================================================
+            // 44: SetTransferHook
+            // =================================================================
+            44 => {
+                let (&option, rest) = rest
+                    .split_first()
+                    .ok_or(TokenError::InvalidInstruction)?;
+
+                // Exact lengths: trailing bytes are rejected
+                let program = match option {
+                    0 if rest.is_empty() => None,
+                    1 => {
+                        if rest.len() != 32 {
+                            return Err(TokenError::InvalidInstruction.into());
+                        }
+                        Some(Pubkey::new_from_array(
+                            rest[..32]
+                                .try_into()
+                                .map_err(|_| TokenError::InvalidInstruction)?,
+                        ))
+                    }
+                    _ => return Err(TokenError::InvalidInstruction.into()),
+                };
+
+                TokenInstruction::SetTransferHook { program }
+            }
+
+            // =================================================================
+            // 45: ApproveWithExpiry
+            // =================================================================
+            45 => {
+                // Exact length: trailing bytes are rejected
+                if rest.len() != 16 {
                     return Err(TokenError::InvalidInstruction.into());
                 }
                 let amount = u64::from_le_bytes(
@@ -540,38 +1777,115 @@ impl TokenInstruction {
                         .try_into()
                         .map_err(|_| TokenError::InvalidInstruction)?,
                 );
-                TokenInstruction::MintTo { amount }
+                let expiry_unix_ts = i64::from_le_bytes(
+                    rest[8..16]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                TokenInstruction::ApproveWithExpiry {
+                    amount,
+                    expiry_unix_ts,
+                }
             }
 
             // =================================================================
-            // 8: Burn
+            // 56: InitializeAccountWithCloseAuthority
             // =================================================================
-            8 => {
-                if rest.len() < 8 {
+            56 => {
+                // Exact length: trailing bytes are rejected
+                if rest.len() != 32 {
                     return Err(TokenError::InvalidInstruction.into());
                 }
-                let amount = u64::from_le_bytes(
-                    rest[..8]
+                let close_authority = Pubkey::new_from_array(
+                    rest[..32]
                         .try_into()
                         .map_err(|_| TokenError::InvalidInstruction)?,
                 );
-                TokenInstruction::Burn { amount }
+                TokenInstruction::InitializeAccountWithCloseAuthority { close_authority }
             }
 
             // =================================================================
-            // 9: CloseAccount
+            // 57: GetSupply
             // =================================================================
-            9 => TokenInstruction::CloseAccount,
+            57 => {
+                // No payload: trailing bytes are rejected
+                if !rest.is_empty() {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                TokenInstruction::GetSupply
+            }
 
             // =================================================================
-            // 10: FreezeAccount
+            // 58: ReconcileSupply
             // =================================================================
-            10 => TokenInstruction::FreezeAccount,
+            58 => {
+                // Exact length: trailing bytes are rejected
+                let strict = match rest {
+                    [0] => false,
+                    [1] => true,
+                    _ => return Err(TokenError::InvalidInstruction.into()),
+                };
+                TokenInstruction::ReconcileSupply { strict }
+            }
 
             // =================================================================
-            // 11: ThawAccount
+            // 59: ApproveAndSetCloseAuthority
             // =================================================================
-            11 => TokenInstruction::ThawAccount,
+            59 => {
+                // Exact length: trailing bytes are rejected
+                if rest.len() != 40 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                let amount = u64::from_le_bytes(
+                    rest[..8]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                let close_authority = Pubkey::new_from_array(
+                    rest[8..40]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                TokenInstruction::ApproveAndSetCloseAuthority {
+                    amount,
+                    close_authority,
+                }
+            }
+
+            // =================================================================
+            // 60: VerifyAccount
+            // =================================================================
+            60 => {
+                // Exact length: trailing bytes are rejected
+                if rest.len() != 64 {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                let expected_mint = Pubkey::new_from_array(
+                    rest[..32]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                let expected_owner = Pubkey::new_from_array(
+                    rest[32..64]
+                        .try_into()
+                        .map_err(|_| TokenError::InvalidInstruction)?,
+                );
+                TokenInstruction::VerifyAccount {
+                    expected_mint,
+                    expected_owner,
+                }
+            }
+
+            // =================================================================
+            // 61: CloseAccountWithReserve
+            // =================================================================
+            61 => {
+                // No payload: trailing bytes are rejected
+                if !rest.is_empty() {
+                    return Err(TokenError::InvalidInstruction.into());
+                }
+                TokenInstruction::CloseAccountWithReserve
+            }
 
             // =================================================================
             // Unknown instruction
@@ -625,6 +1939,12 @@ impl TokenInstruction {
                 buf.extend_from_slice(&amount.to_le_bytes());
             }
 
+            TokenInstruction::TransferWithReturnData { amount } => {
+                buf.push(3);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.push(1); // return_balance flag
+            }
+
             TokenInstruction::Approve { amount } => {
                 buf.push(4);
                 buf.extend_from_slice(&amount.to_le_bytes());
@@ -672,12 +1992,631 @@ impl TokenInstruction {
             TokenInstruction::ThawAccount => {
                 buf.push(11);
             }
+
+            TokenInstruction::TransferBatch { amounts } => {
+                buf.push(26);
+                buf.push(amounts.len() as u8);
+                for amount in amounts {
+                    buf.extend_from_slice(&amount.to_le_bytes());
+                }
+            }
+
+            TokenInstruction::BurnAll => {
+                buf.push(27);
+            }
+
+            TokenInstruction::ApproveAll => {
+                buf.push(28);
+            }
+
+            TokenInstruction::Reallocate { new_len } => {
+                buf.push(29);
+                buf.extend_from_slice(&new_len.to_le_bytes());
+            }
+
+            TokenInstruction::SetNonTransferable => {
+                buf.push(30);
+            }
+
+            TokenInstruction::TransferWithFee {
+                amount,
+                fee_basis_points,
+            } => {
+                buf.push(31);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(&fee_basis_points.to_le_bytes());
+            }
+
+            TokenInstruction::CreateAccount { owner } => {
+                buf.push(32);
+                buf.extend_from_slice(owner.as_ref());
+            }
+
+            TokenInstruction::FreezeAccounts { count } => {
+                buf.push(33);
+                buf.push(*count);
+            }
+
+            TokenInstruction::GetMintInfo => {
+                buf.push(34);
+            }
+
+            TokenInstruction::GetAccountBalance => {
+                buf.push(35);
+            }
+
+            TokenInstruction::GetMultisigInfo => {
+                buf.push(36);
+            }
+
+            TokenInstruction::MintToBatch { amounts } => {
+                buf.push(37);
+                buf.push(amounts.len() as u8);
+                for amount in amounts {
+                    buf.extend_from_slice(&amount.to_le_bytes());
+                }
+            }
+
+            TokenInstruction::RevokeBatch { count } => {
+                buf.push(38);
+                buf.push(*count);
+            }
+
+            TokenInstruction::MigrateAccount => {
+                buf.push(39);
+            }
+
+            TokenInstruction::InitializeAccountIdempotent { owner } => {
+                buf.push(40);
+                buf.extend_from_slice(owner.as_ref());
+            }
+
+            TokenInstruction::SetMemoRequired { required } => {
+                buf.push(41);
+                buf.push(*required as u8);
+            }
+
+            TokenInstruction::SetSupplyCap { cap } => {
+                buf.push(42);
+                match cap {
+                    Some(cap) => {
+                        buf.push(1);
+                        buf.extend_from_slice(&cap.to_le_bytes());
+                    }
+                    None => buf.push(0),
+                }
+            }
+
+            TokenInstruction::SetMintPaused { paused } => {
+                buf.push(43);
+                buf.push(*paused as u8);
+            }
+
+            TokenInstruction::SetTransferHook { program } => {
+                buf.push(44);
+                match program {
+                    Some(program) => {
+                        buf.push(1);
+                        buf.extend_from_slice(program.as_ref());
+                    }
+                    None => buf.push(0),
+                }
+            }
+
+            TokenInstruction::ApproveWithExpiry {
+                amount,
+                expiry_unix_ts,
+            } => {
+                buf.push(45);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(&expiry_unix_ts.to_le_bytes());
+            }
+
+            TokenInstruction::InitializeAccountWithCloseAuthority { close_authority } => {
+                buf.push(56);
+                buf.extend_from_slice(close_authority.as_ref());
+            }
+
+            TokenInstruction::GetSupply => {
+                buf.push(57);
+            }
+
+            TokenInstruction::ReconcileSupply { strict } => {
+                buf.push(58);
+                buf.push(*strict as u8);
+            }
+
+            TokenInstruction::ApproveAndSetCloseAuthority {
+                amount,
+                close_authority,
+            } => {
+                buf.push(59);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(close_authority.as_ref());
+            }
+
+            TokenInstruction::VerifyAccount {
+                expected_mint,
+                expected_owner,
+            } => {
+                buf.push(60);
+                buf.extend_from_slice(expected_mint.as_ref());
+                buf.extend_from_slice(expected_owner.as_ref());
+            }
+
+            TokenInstruction::CloseAccountWithReserve => {
+                buf.push(61);
+            }
         }
 
         buf
     }
 }
 
+// =============================================================================
+// ACCOUNT METAS TEMPLATE
+// =============================================================================
+
+impl TokenInstruction {
+    /// The fixed accounts this instruction requires, in order, as
+    /// `(name, is_signer, is_writable)`.
+    ///
+    /// Mirrors the "Account Requirements" table of each variant, so SDKs
+    /// can check a transaction's accounts before sending it. Only
+    /// required, fixed-position accounts are listed: optional accounts
+    /// (like the rent sysvar) and variable-length tails (batch entries,
+    /// multisig signers) are left to the caller.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let template = TokenInstruction::Revoke.account_metas_template();
+    /// assert_eq!(template, vec![("source", false, true), ("owner", true, false)]);
+    /// ```
+    pub fn account_metas_template(&self) -> Vec<(&'static str, bool, bool)> {
+        match self {
+            TokenInstruction::InitializeMint { .. } => vec![("mint", false, true)],
+
+            TokenInstruction::InitializeAccount
+            | TokenInstruction::InitializeAccountWithCloseAuthority { .. } => vec![
+                ("account", false, true),
+                ("mint", false, false),
+                ("owner", false, false),
+            ],
+
+            TokenInstruction::InitializeAccountIdempotent { .. } => {
+                vec![("account", false, true), ("mint", false, false)]
+            }
+
+            TokenInstruction::InitializeMultisig { .. } => {
+                vec![("multisig", false, true), ("rent", false, false)]
+            }
+
+            TokenInstruction::Transfer { .. }
+            | TokenInstruction::TransferWithReturnData { .. } => vec![
+                ("source", false, true),
+                ("destination", false, true),
+                ("authority", true, false),
+                ("mint", false, false),
+            ],
+
+            TokenInstruction::Approve { .. }
+            | TokenInstruction::ApproveAll
+            | TokenInstruction::ApproveAndSetCloseAuthority { .. }
+            | TokenInstruction::ApproveWithExpiry { .. } => vec![
+                ("source", false, true),
+                ("delegate", false, false),
+                ("owner", true, false),
+            ],
+
+            TokenInstruction::Revoke => vec![("source", false, true), ("owner", true, false)],
+
+            TokenInstruction::RevokeBatch { .. } => vec![("owner", true, false)],
+
+            TokenInstruction::SetAuthority { .. } => vec![
+                ("account", false, true),
+                ("current_authority", true, false),
+            ],
+
+            TokenInstruction::MintTo { .. } => vec![
+                ("mint", false, true),
+                ("destination", false, true),
+                ("mint_authority", true, false),
+            ],
+
+            TokenInstruction::Burn { .. } | TokenInstruction::BurnAll => vec![
+                ("account", false, true),
+                ("mint", false, true),
+                ("authority", true, false),
+            ],
+
+            TokenInstruction::CloseAccount => vec![
+                ("account", false, true),
+                ("destination", false, true),
+                ("authority", true, false),
+            ],
+
+            TokenInstruction::CloseAccountWithReserve => vec![
+                ("account", false, true),
+                ("destination", false, true),
+                ("authority", true, false),
+                ("reserve_destination", false, true),
+            ],
+
+            TokenInstruction::FreezeAccount | TokenInstruction::ThawAccount => vec![
+                ("account", false, true),
+                ("mint", false, false),
+                ("freeze_authority", true, false),
+            ],
+
+            TokenInstruction::TransferBatch { .. } => vec![
+                ("source", false, true),
+                ("authority", true, false),
+                ("mint", false, false),
+            ],
+
+            TokenInstruction::Reallocate { .. } | TokenInstruction::MigrateAccount => vec![
+                ("account", false, true),
+                ("payer", true, true),
+                ("system_program", false, false),
+                ("owner", true, false),
+            ],
+
+            TokenInstruction::SetNonTransferable
+            | TokenInstruction::MintToBatch { .. }
+            | TokenInstruction::SetSupplyCap { .. }
+            | TokenInstruction::SetMintPaused { .. }
+            | TokenInstruction::SetTransferHook { .. } => {
+                vec![("mint", false, true), ("authority", true, false)]
+            }
+
+            TokenInstruction::TransferWithFee { .. } => vec![
+                ("source", false, true),
+                ("destination", false, true),
+                ("fee_destination", false, true),
+                ("authority", true, false),
+                ("mint", false, false),
+            ],
+
+            TokenInstruction::CreateAccount { .. } => vec![
+                ("payer", true, true),
+                ("new_account", true, true),
+                ("mint", false, false),
+                ("system_program", false, false),
+            ],
+
+            TokenInstruction::FreezeAccounts { .. } => {
+                vec![("mint", false, false), ("authority", true, false)]
+            }
+
+            TokenInstruction::GetMintInfo
+            | TokenInstruction::GetSupply
+            | TokenInstruction::ReconcileSupply { .. } => vec![("mint", false, false)],
+
+            TokenInstruction::GetAccountBalance | TokenInstruction::VerifyAccount { .. } => {
+                vec![("account", false, false)]
+            }
+
+            TokenInstruction::GetMultisigInfo => vec![("multisig", false, false)],
+
+            TokenInstruction::SetMemoRequired { .. } => {
+                vec![("account", false, true), ("owner", true, false)]
+            }
+        }
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that empty instruction data is rejected.
+    #[test]
+    fn test_unpack_empty_data_fails() {
+        assert_eq!(
+            TokenInstruction::unpack(&[]).unwrap_err(),
+            TokenError::InvalidInstruction.into()
+        );
+    }
+
+    /// Test TransferBatch accepts MAX_BATCH_LEN amounts and no more.
+    #[test]
+    fn test_transfer_batch_rejects_oversized_batch() {
+        let full = TokenInstruction::TransferBatch {
+            amounts: vec![1; MAX_BATCH_LEN],
+        };
+        assert_eq!(TokenInstruction::unpack(&full.pack()).unwrap(), full);
+
+        let oversized = TokenInstruction::TransferBatch {
+            amounts: vec![1; MAX_BATCH_LEN + 1],
+        }
+        .pack();
+        assert_eq!(
+            TokenInstruction::unpack(&oversized).unwrap_err(),
+            TokenError::BatchTooLarge.into()
+        );
+
+        // Largest count byte with every amount present
+        let mut enormous = vec![26, u8::MAX];
+        enormous.resize(2 + u8::MAX as usize * 8, 0);
+        assert_eq!(
+            TokenInstruction::unpack(&enormous).unwrap_err(),
+            TokenError::BatchTooLarge.into()
+        );
+    }
+
+    /// Test the Transfer template matches its documented table.
+    #[test]
+    fn test_account_metas_template_transfer() {
+        let expected = vec![
+            ("source", false, true),
+            ("destination", false, true),
+            ("authority", true, false),
+            ("mint", false, false),
+        ];
+        assert_eq!(
+            TokenInstruction::Transfer { amount: 1 }.account_metas_template(),
+            expected
+        );
+        assert_eq!(
+            TokenInstruction::TransferWithReturnData { amount: 1 }.account_metas_template(),
+            expected
+        );
+    }
+
+    /// Test the MintTo template matches its documented table.
+    #[test]
+    fn test_account_metas_template_mint_to() {
+        assert_eq!(
+            TokenInstruction::MintTo { amount: 1 }.account_metas_template(),
+            vec![
+                ("mint", false, true),
+                ("destination", false, true),
+                ("mint_authority", true, false),
+            ]
+        );
+    }
+
+    /// Test the InitializeMint template leaves out the optional rent sysvar.
+    #[test]
+    fn test_account_metas_template_initialize_mint() {
+        let ix = TokenInstruction::InitializeMint {
+            decimals: 6,
+            mint_authority: Pubkey::new_unique(),
+            freeze_authority: None,
+        };
+        assert_eq!(ix.account_metas_template(), vec![("mint", false, true)]);
+    }
+
+    /// Test MintToBatch shares TransferBatch's size limit.
+    #[test]
+    fn test_mint_to_batch_rejects_oversized_batch() {
+        let full = TokenInstruction::MintToBatch {
+            amounts: vec![1; MAX_BATCH_LEN],
+        };
+        assert_eq!(TokenInstruction::unpack(&full.pack()).unwrap(), full);
+
+        let oversized = TokenInstruction::MintToBatch {
+            amounts: vec![1; MAX_BATCH_LEN + 1],
+        }
+        .pack();
+        assert_eq!(
+            TokenInstruction::unpack(&oversized).unwrap_err(),
+            TokenError::BatchTooLarge.into()
+        );
+    }
+
+    /// Test RevokeBatch counts are capped at MAX_BATCH_LEN.
+    #[test]
+    fn test_revoke_batch_rejects_oversized_batch() {
+        let full = TokenInstruction::RevokeBatch {
+            count: MAX_BATCH_LEN as u8,
+        };
+        assert_eq!(TokenInstruction::unpack(&full.pack()).unwrap(), full);
+
+        assert_eq!(
+            TokenInstruction::unpack(&[38, MAX_BATCH_LEN as u8 + 1]).unwrap_err(),
+            TokenError::BatchTooLarge.into()
+        );
+    }
+
+    /// Test a hook program can read back the amount Transfer sends it.
+    #[test]
+    fn test_transfer_hook_instruction_amount() {
+        let keys: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let ix = transfer_hook_instruction(&keys[0], &keys[1], &keys[2], &keys[3], &keys[4], 1_500);
+
+        assert_eq!(ix.program_id, keys[0]);
+        assert_eq!(ix.accounts.len(), 4);
+        assert!(ix.accounts.iter().all(|meta| !meta.is_writable && !meta.is_signer));
+        assert_eq!(unpack_transfer_hook_amount(&ix.data), Some(1_500));
+
+        // Not a hook call: wrong prefix or truncated amount
+        assert_eq!(unpack_transfer_hook_amount(&[0u8; 16]), None);
+        assert_eq!(unpack_transfer_hook_amount(&ix.data[..15]), None);
+    }
+
+    /// Test SetAuthority roundtrip with a new authority.
+    #[test]
+    fn test_set_authority_some_roundtrip() {
+        let instruction = TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::AccountOwner,
+            new_authority: Some(Pubkey::new_unique()),
+        };
+
+        let packed = instruction.pack();
+        assert_eq!(packed.len(), 35);
+        assert_eq!(TokenInstruction::unpack(&packed).unwrap(), instruction);
+    }
+
+    /// Test SetAuthority roundtrip removing the authority.
+    #[test]
+    fn test_set_authority_none_roundtrip() {
+        let instruction = TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::MintTokens,
+            new_authority: None,
+        };
+
+        let packed = instruction.pack();
+        assert_eq!(packed.len(), 3);
+        assert_eq!(TokenInstruction::unpack(&packed).unwrap(), instruction);
+    }
+
+    /// Test SetAuthority rejects trailing bytes after either variant.
+    #[test]
+    fn test_set_authority_rejects_over_long_data() {
+        let mut none = TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::MintTokens,
+            new_authority: None,
+        }
+        .pack();
+        none.push(0);
+        assert_eq!(
+            TokenInstruction::unpack(&none).unwrap_err(),
+            TokenError::InvalidInstruction.into()
+        );
+
+        let mut some = TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::CloseAccount,
+            new_authority: Some(Pubkey::new_unique()),
+        }
+        .pack();
+        some.push(0);
+        assert_eq!(
+            TokenInstruction::unpack(&some).unwrap_err(),
+            TokenError::InvalidInstruction.into()
+        );
+    }
+
+    /// Test fixed-size instructions reject trailing bytes.
+    #[test]
+    fn test_fixed_size_instructions_reject_over_long_data() {
+        let instructions = [
+            TokenInstruction::CreateAccount {
+                owner: Pubkey::new_unique(),
+            },
+            TokenInstruction::InitializeAccountIdempotent {
+                owner: Pubkey::new_unique(),
+            },
+            TokenInstruction::InitializeAccountWithCloseAuthority {
+                close_authority: Pubkey::new_unique(),
+            },
+            TokenInstruction::ApproveAndSetCloseAuthority {
+                amount: 42,
+                close_authority: Pubkey::new_unique(),
+            },
+            TokenInstruction::VerifyAccount {
+                expected_mint: Pubkey::new_unique(),
+                expected_owner: Pubkey::new_unique(),
+            },
+            TokenInstruction::TransferBatch {
+                amounts: vec![1, 2],
+            },
+            TokenInstruction::BurnAll,
+            TokenInstruction::ApproveAll,
+            TokenInstruction::Reallocate { new_len: 179 },
+            TokenInstruction::SetNonTransferable,
+            TokenInstruction::TransferWithFee {
+                amount: 1000,
+                fee_basis_points: 50,
+            },
+            TokenInstruction::FreezeAccounts { count: 2 },
+            TokenInstruction::GetMintInfo,
+            TokenInstruction::GetAccountBalance,
+            TokenInstruction::GetMultisigInfo,
+            TokenInstruction::MintToBatch {
+                amounts: vec![1, 2],
+            },
+            TokenInstruction::RevokeBatch { count: 2 },
+            TokenInstruction::MigrateAccount,
+            TokenInstruction::SetMemoRequired { required: true },
+            TokenInstruction::SetSupplyCap { cap: None },
+            TokenInstruction::SetSupplyCap { cap: Some(1000) },
+            TokenInstruction::SetMintPaused { paused: true },
+            TokenInstruction::SetTransferHook { program: None },
+            TokenInstruction::SetTransferHook {
+                program: Some(Pubkey::new_unique()),
+            },
+            TokenInstruction::ApproveWithExpiry {
+                amount: 42,
+                expiry_unix_ts: 1_700_000_000,
+            },
+            TokenInstruction::GetSupply,
+            TokenInstruction::ReconcileSupply { strict: true },
+            TokenInstruction::CloseAccountWithReserve,
+            TokenInstruction::TransferWithReturnData { amount: 42 },
+        ];
+
+        for instruction in instructions {
+            let mut data = instruction.pack();
+            assert_eq!(TokenInstruction::unpack(&data).unwrap(), instruction);
+
+            data.push(0);
+            assert_eq!(
+                TokenInstruction::unpack(&data).unwrap_err(),
+                TokenError::InvalidInstruction.into()
+            );
+        }
+    }
+
+    /// Test SetAuthority rejects a truncated pubkey.
+    #[test]
+    fn test_set_authority_rejects_short_data() {
+        let some = TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::FreezeAccount,
+            new_authority: Some(Pubkey::new_unique()),
+        }
+        .pack();
+        assert_eq!(
+            TokenInstruction::unpack(&some[..34]).unwrap_err(),
+            TokenError::InvalidInstruction.into()
+        );
+    }
+
+    /// Test Borsh encoding round-trips and converts to the wire format
+    /// through `pack`.
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh_roundtrip_and_pack() {
+        let instructions = [
+            TokenInstruction::Transfer { amount: 42 },
+            TokenInstruction::SetAuthority {
+                authority_type: AuthorityType::FreezeAccount,
+                new_authority: Some(Pubkey::new_unique()),
+            },
+            TokenInstruction::MintToBatch {
+                amounts: vec![1, 2, 3],
+            },
+        ];
+
+        for instruction in instructions {
+            let bytes = borsh::to_vec(&instruction).unwrap();
+            let decoded = TokenInstruction::try_from_slice(&bytes).unwrap();
+            assert_eq!(decoded, instruction);
+            assert_eq!(TokenInstruction::unpack(&decoded.pack()).unwrap(), instruction);
+        }
+    }
+
+    /// Test Borsh bytes are not instruction data: the variant tag is the
+    /// declaration index, which `unpack` reads as a different discriminant.
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh_bytes_are_not_wire_format() {
+        let instruction = TokenInstruction::TransferWithReturnData { amount: 42 };
+        let bytes = borsh::to_vec(&instruction).unwrap();
+
+        assert_ne!(bytes, instruction.pack());
+        assert_eq!(
+            TokenInstruction::unpack(&bytes).unwrap(),
+            TokenInstruction::Approve { amount: 42 }
+        );
+    }
+}
+
 /*
 =============================================================================
 DETAILED EXPLANATION
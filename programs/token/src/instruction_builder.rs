@@ -0,0 +1,148 @@
+//! Instruction Builders
+//!
+//! Helpers that build complete `Instruction`s (program id, account
+//! metas and packed data) so clients don't assemble them by hand.
+//!
+//! # Single vs Multisig Authorities
+//!
+//! Every builder that takes an authority also takes `signers`:
+//!
+//! - `signers` empty: the authority itself signs
+//! - `signers` non-empty: the authority is a multisig account (not a
+//!   signer) and each of `signers` is appended as a signer
+//!
+//! # Example
+//!
+//! ```ignore
+//! // Hand the mint authority to a new key
+//! let ix = instruction_builder::set_authority(
+//!     &mint,
+//!     &current_authority,
+//!     &[],
+//!     AuthorityType::MintTokens,
+//!     Some(new_authority),
+//! );
+//! ```
+
+use crate::instruction::{AuthorityType, TokenInstruction};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+// =============================================================================
+// AUTHORITY
+// =============================================================================
+
+/// Build a SetAuthority instruction.
+///
+/// `new_authority = None` removes the authority (packed with option
+/// byte 0); `Some` sets it (option byte 1 followed by the pubkey).
+///
+/// # Arguments
+///
+/// * `account` - Mint or token account to change
+/// * `current_authority` - Current authority (single key or multisig)
+/// * `signers` - Multisig signers, empty for a single-key authority
+/// * `authority_type` - Which authority to change
+/// * `new_authority` - New authority, or None to remove it
+pub fn set_authority(
+    account: &Pubkey,
+    current_authority: &Pubkey,
+    signers: &[&Pubkey],
+    authority_type: AuthorityType,
+    new_authority: Option<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(*account, false)];
+    accounts.extend(authority_metas(current_authority, signers));
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenInstruction::SetAuthority {
+            authority_type,
+            new_authority,
+        }
+        .pack(),
+    }
+}
+
+/// Account metas for an authority followed by its multisig signers.
+fn authority_metas(authority: &Pubkey, signers: &[&Pubkey]) -> Vec<AccountMeta> {
+    let mut metas = vec![AccountMeta::new_readonly(*authority, signers.is_empty())];
+    metas.extend(
+        signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(**signer, true)),
+    );
+    metas
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_authority_change() {
+        let account = Pubkey::new_unique();
+        let current = Pubkey::new_unique();
+        let new_authority = Pubkey::new_unique();
+
+        let ix = set_authority(
+            &account,
+            &current,
+            &[],
+            AuthorityType::MintTokens,
+            Some(new_authority),
+        );
+
+        assert_eq!(ix.program_id, crate::id());
+        assert_eq!(
+            ix.accounts,
+            vec![
+                AccountMeta::new(account, false),
+                AccountMeta::new_readonly(current, true),
+            ]
+        );
+
+        // [6][authority_type][option = 1][pubkey]
+        assert_eq!(ix.data.len(), 35);
+        assert_eq!(ix.data[0], 6);
+        assert_eq!(ix.data[1], AuthorityType::MintTokens as u8);
+        assert_eq!(ix.data[2], 1);
+        assert_eq!(&ix.data[3..35], new_authority.as_ref());
+    }
+
+    #[test]
+    fn test_set_authority_remove_multisig() {
+        let account = Pubkey::new_unique();
+        let multisig = Pubkey::new_unique();
+        let signer_a = Pubkey::new_unique();
+        let signer_b = Pubkey::new_unique();
+
+        let ix = set_authority(
+            &account,
+            &multisig,
+            &[&signer_a, &signer_b],
+            AuthorityType::FreezeAccount,
+            None,
+        );
+
+        assert_eq!(
+            ix.accounts,
+            vec![
+                AccountMeta::new(account, false),
+                AccountMeta::new_readonly(multisig, false),
+                AccountMeta::new_readonly(signer_a, true),
+                AccountMeta::new_readonly(signer_b, true),
+            ]
+        );
+
+        // [6][authority_type][option = 0]
+        assert_eq!(ix.data, vec![6, AuthorityType::FreezeAccount as u8, 0]);
+    }
+}
@@ -29,7 +29,7 @@
 //! | 0 | InitializeMint | Create a new token mint |
 //! | 1 | InitializeAccount | Create a new token account |
 //! | 2 | InitializeMultisig | Create a multisig authority |
-//! | 3 | Transfer | Transfer tokens |
+//! | 3 | Transfer | Transfer tokens (trailing flag 1 = return new balance) |
 //! | 4 | Approve | Approve a delegate |
 //! | 5 | Revoke | Revoke a delegate |
 //! | 6 | SetAuthority | Change an authority |
@@ -38,6 +38,32 @@
 //! | 9 | CloseAccount | Close and reclaim rent |
 //! | 10 | FreezeAccount | Freeze an account |
 //! | 11 | ThawAccount | Thaw a frozen account |
+//! | 26 | TransferBatch | Transfer from one source to many destinations |
+//! | 27 | BurnAll | Burn an account's entire balance |
+//! | 28 | ApproveAll | Approve a delegate for the full balance |
+//! | 29 | Reallocate | Resize a token account for extension data |
+//! | 30 | SetNonTransferable | Permanently block transfers of a mint's tokens |
+//! | 31 | TransferWithFee | Transfer tokens, skimming a basis-point fee |
+//! | 32 | CreateAccount | Create and initialize a token account in one instruction |
+//! | 33 | FreezeAccounts | Freeze many token accounts of one mint |
+//! | 34 | GetMintInfo | Return a compact mint summary as return data |
+//! | 35 | GetAccountBalance | Return a token account's balance as return data |
+//! | 36 | GetMultisigInfo | Return a multisig's M, N and signers as return data |
+//! | 37 | MintToBatch | Mint to many destinations in one instruction |
+//! | 38 | RevokeBatch | Revoke the delegates of many accounts with one owner |
+//! | 39 | MigrateAccount | Upgrade a legacy token account to the extended layout |
+//! | 40 | InitializeAccountIdempotent | Initialize a token account, succeeding if it already matches |
+//! | 41 | SetMemoRequired | Require a memo on inbound transfers |
+//! | 42 | SetSupplyCap | Set or clear a mint's maximum supply |
+//! | 43 | SetMintPaused | Pause or resume minting on an extended mint |
+//! | 44 | SetTransferHook | Set or clear the program a mint calls on every transfer |
+//! | 45 | ApproveWithExpiry | Approve a delegate until a unix timestamp |
+//! | 56 | InitializeAccountWithCloseAuthority | Create a token account with a close authority |
+//! | 57 | GetSupply | Return a mint's supply as return data |
+//! | 58 | ReconcileSupply | Return supply and the sum of given balances |
+//! | 59 | ApproveAndSetCloseAuthority | Approve a delegate and set the close authority |
+//! | 60 | VerifyAccount | Check a token account against an expected mint and owner |
+//! | 61 | CloseAccountWithReserve | Close a wrapped SOL account, splitting off its rent reserve |
 
 // =============================================================================
 // MODULE DECLARATIONS
@@ -52,6 +78,12 @@ pub mod error;
 /// Instruction definitions and parsing
 pub mod instruction;
 
+/// Builders for complete instructions (accounts + data)
+pub mod instruction_builder;
+
+/// One-import surface for clients building transactions
+pub mod prelude;
+
 /// Instruction processors (business logic)
 pub mod processor;
 
@@ -61,6 +93,10 @@ pub mod state;
 /// Utility functions for validation and math
 pub mod utils;
 
+/// Typed encoders/decoders for query instruction return data
+/// (decoders need the client feature)
+pub mod return_data;
+
 // =============================================================================
 // RE-EXPORTS
 // =============================================================================
@@ -72,7 +108,7 @@ pub mod utils;
 pub use error::TokenError;
 pub use instruction::{AuthorityType, TokenInstruction};
 pub use processor::Processor;
-pub use state::{Account, AccountState, Mint, Multisig, Pack};
+pub use state::{Account, AccountState, IsInitialized, Mint, Multisig, Pack};
 
 // =============================================================================
 // PROGRAM ID
@@ -114,6 +150,9 @@ src/
 ├── entrypoint.rs    <- pub mod entrypoint
 ├── error.rs         <- pub mod error
 ├── instruction.rs   <- pub mod instruction
+├── instruction_builder.rs <- pub mod instruction_builder
+├── prelude.rs       <- pub mod prelude
+├── return_data.rs   <- pub mod return_data (decoders: client feature)
 ├── processor/       <- pub mod processor (uses mod.rs)
 │   └── mod.rs
 ├── state/           <- pub mod state (uses mod.rs)
@@ -0,0 +1,61 @@
+//! Client Prelude
+//!
+//! One import for building transactions against this program:
+//!
+//! ```
+//! use spl_token_from_scratch::prelude::*;
+//! ```
+//!
+//! Nothing here touches the entrypoint, so it works the same for crates
+//! depending on this one with `features = ["no-entrypoint"]`.
+//!
+//! # Example
+//!
+//! ```
+//! use solana_program::{
+//!     instruction::{AccountMeta, Instruction},
+//!     pubkey::Pubkey,
+//! };
+//! use spl_token_from_scratch::prelude::*;
+//!
+//! let (source, destination, owner, mint) = (
+//!     Pubkey::new_unique(),
+//!     Pubkey::new_unique(),
+//!     Pubkey::new_unique(),
+//!     Pubkey::new_unique(),
+//! );
+//!
+//! // Transfer 1.5 tokens of a 6-decimal mint
+//! let transfer_ix = Instruction {
+//!     program_id: id(),
+//!     accounts: vec![
+//!         AccountMeta::new(source, false),
+//!         AccountMeta::new(destination, false),
+//!         AccountMeta::new_readonly(owner, true),
+//!         AccountMeta::new_readonly(mint, false),
+//!     ],
+//!     data: TokenInstruction::Transfer { amount: 1_500_000 }.pack(),
+//! };
+//!
+//! assert_eq!(
+//!     TokenInstruction::unpack(&transfer_ix.data).unwrap(),
+//!     TokenInstruction::Transfer { amount: 1_500_000 }
+//! );
+//!
+//! // Builders fill in the accounts for you
+//! let set_owner_ix = set_authority(
+//!     &source,
+//!     &owner,
+//!     &[],
+//!     AuthorityType::AccountOwner,
+//!     Some(Pubkey::new_unique()),
+//! );
+//! assert_eq!(set_owner_ix.program_id, id());
+//! ```
+
+pub use crate::id;
+pub use crate::instruction::{AuthorityType, TokenInstruction};
+pub use crate::instruction_builder::*;
+pub use crate::state::{Account, AccountState, Mint, Multisig, Pack};
+pub use crate::utils::rent::{account_rent, mint_rent, multisig_rent};
+pub use crate::utils::ui_amount::{base_to_ui, try_ui_to_base};
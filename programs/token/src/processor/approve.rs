@@ -17,11 +17,36 @@ use solana_program::{
 /// 0. `[writable]` Source token account
 /// 1. `[]` Delegate
 /// 2. `[signer]` Owner
-/// 3..3+M. `[signer]` Multisig signers (if applicable)
+/// 3. ..3+M `[signer]` Multisig signers (if applicable)
+///
+/// Clears any expiry left by an earlier ApproveWithExpiry.
 pub fn process(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     amount: u64,
+) -> ProgramResult {
+    approve(program_id, accounts, amount, COption::none())
+}
+
+/// Process ApproveWithExpiry instruction
+///
+/// Same accounts as Approve. The source must be an extended account
+/// (`Account::EXTENDED_LEN` bytes) to store the expiry.
+pub fn process_with_expiry(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    expiry_unix_ts: i64,
+) -> ProgramResult {
+    approve(program_id, accounts, amount, COption::some(expiry_unix_ts))
+}
+
+/// Shared approve logic.
+fn approve(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    expiry: COption<i64>,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
 
@@ -40,7 +65,7 @@ pub fn process(
     // Validate source account
     assert_owned_by(source_info, program_id)?;
     assert_writable(source_info)?;
-    assert_data_length(source_info, Account::LEN)?;
+    assert_account_data_length(source_info)?;
 
     // Load source account
     let mut source = Account::unpack_from_slice(&source_info.data.borrow())?;
@@ -50,6 +75,9 @@ pub fn process(
         return Err(TokenError::UninitializedAccount.into());
     }
 
+    // A frozen account's delegate can't change
+    assert_not_frozen(&source)?;
+
     // Validate owner authority (only owner can approve, not delegate)
     validate_authority(
         program_id,
@@ -61,6 +89,7 @@ pub fn process(
     // Set delegate
     source.delegate = COption::some(*delegate_info.key);
     source.delegated_amount = amount;
+    source.delegate_expiry = expiry;
 
     // Save sourcess
     source.pack_into_slice(&mut source_info.data.borrow_mut())?;
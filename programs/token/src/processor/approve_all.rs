@@ -0,0 +1,71 @@
+//! ApproveAll Instruction Processor
+//!
+//! Approves a delegate for the source account's entire current balance.
+
+use crate::error::TokenError;
+use crate::state::{Account, COption, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+/// Process ApproveAll instruction
+///
+/// Accounts expected:
+/// 0. `[writable]` Source token account
+/// 1. `[]` Delegate
+/// 2. `[signer]` Owner
+/// 3. ..3+M `[signer]` Multisig signers (if applicable)
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Source token account
+    let source_info = next_account_info(account_info_iter)?;
+
+    // Account 1: Delegate
+    let delegate_info = next_account_info(account_info_iter)?;
+
+    // Account 2: Owner
+    let owner_info = next_account_info(account_info_iter)?;
+
+    // Remaining: Multisig signers
+    let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    // Validate source account
+    assert_owned_by(source_info, program_id)?;
+    assert_writable(source_info)?;
+    assert_account_data_length(source_info)?;
+
+    // Load source account
+    let mut source = Account::unpack_from_slice(&source_info.data.borrow())?;
+
+    // Validate initialization
+    if !source.is_initialized() {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Validate not frozen
+    if source.is_frozen() {
+        return Err(TokenError::AccountFrozen.into());
+    }
+
+    // Validate owner authority (only owner can approve, not delegate)
+    validate_authority(
+        program_id,
+        &source.owner,
+        owner_info,
+        &signer_accounts,
+    )?;
+
+    // Set delegate for the full current balance
+    source.delegate = COption::some(*delegate_info.key);
+    source.delegated_amount = source.amount;
+    source.delegate_expiry = COption::none();
+
+    // Save source
+    source.pack_into_slice(&mut source_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//! ApproveAndSetCloseAuthority Instruction Processor
+//!
+//! Approves a delegate and sets the close authority in one call.
+
+use crate::error::TokenError;
+use crate::state::{Account, COption, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+/// Process ApproveAndSetCloseAuthority instruction
+///
+/// Accounts expected:
+/// 0. `[writable]` Source token account
+/// 1. `[]` Delegate
+/// 2. `[signer]` Owner
+/// 3. ..3+M `[signer]` Multisig signers (if applicable)
+///
+/// The close authority is only replaced while the owner holds it (unset
+/// or set to the owner). Once it belongs to someone else, only they can
+/// move it, through SetAuthority; this fails with `CloseAuthorityMismatch`.
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    close_authority: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Source token account
+    let source_info = next_account_info(account_info_iter)?;
+
+    // Account 1: Delegate
+    let delegate_info = next_account_info(account_info_iter)?;
+
+    // Account 2: Owner
+    let owner_info = next_account_info(account_info_iter)?;
+
+    // Remaining: Multisig signers
+    let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    // Validate source account
+    assert_owned_by(source_info, program_id)?;
+    assert_writable(source_info)?;
+    assert_account_data_length(source_info)?;
+
+    // Load source account
+    let mut source = Account::unpack_from_slice(&source_info.data.borrow())?;
+
+    // Validate initialization
+    if !source.is_initialized() {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Validate not frozen
+    if source.is_frozen() {
+        return Err(TokenError::AccountFrozen.into());
+    }
+
+    // Validate owner authority (only owner, never the delegate)
+    validate_authority(
+        program_id,
+        &source.owner,
+        owner_info,
+        &signer_accounts,
+    )?;
+
+    // Validate the owner still holds the close authority, as
+    // SetAuthority(CloseAccount) would require
+    if let Some(current_close_authority) = source.close_authority.as_ref() {
+        if *current_close_authority != source.owner {
+            return Err(TokenError::CloseAuthorityMismatch.into());
+        }
+    }
+
+    // Set delegate and close authority together
+    source.delegate = COption::some(*delegate_info.key);
+    source.delegated_amount = amount;
+    source.delegate_expiry = COption::none();
+    source.close_authority = COption::some(close_authority);
+
+    // Save source
+    source.pack_into_slice(&mut source_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -3,7 +3,7 @@
 //! Burns (destroys) tokens, decreasing supply.
 
 use crate::error::TokenError;
-use crate::state::{Account, COption, Mint, Pack};
+use crate::state::COption;
 use crate::utils::*;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -17,12 +17,24 @@ use solana_program::{
 /// 0. `[writable]` Token account to burn from
 /// 1. `[writable]` Mint
 /// 2. `[signer]` Owner or delegate
-/// 3..3+M. `[signer]` Multisig signers (if applicable)
+/// 3. ..3+M `[signer]` Multisig signers (if applicable)
+///
+/// A delegate whose approval expires must also pass the Clock sysvar
+/// after the authority; past the expiry this fails with `DelegateExpired`.
+///
+/// With the `reject-zero-amount` feature, an amount of 0 fails with
+/// `ZeroAmount`; otherwise it succeeds without changing anything.
 pub fn process(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     amount: u64,
 ) -> ProgramResult {
+    // Burning nothing is treated as a client bug
+    #[cfg(feature = "reject-zero-amount")]
+    if amount == 0 {
+        return Err(TokenError::ZeroAmount.into());
+    }
+
     let account_info_iter = &mut accounts.iter();
 
     // Account 0: Token account
@@ -37,27 +49,9 @@ pub fn process(
     // Remaining: Multisig signers
     let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
 
-    // Validate token account
-    assert_owned_by(account_info, program_id)?;
-    assert_writable(account_info)?;
-    assert_data_length(account_info, Account::LEN)?;
-
-    // Validate mint
-    assert_owned_by(mint_info, program_id)?;
-    assert_writable(mint_info)?;
-    assert_data_length(mint_info, Mint::LEN)?;
-
-    // Load states
-    let mut account = Account::unpack_from_slice(&account_info.data.borrow())?;
-    let mut mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
-
-    // Validate initialization
-    if !account.is_initialized() {
-        return Err(TokenError::UninitializedAccount.into());
-    }
-    if !mint.is_initialized {
-        return Err(TokenError::UninitializedAccount.into());
-    }
+    // Load and validate token account and mint
+    let mut account = load_token_account(account_info, program_id, true)?;
+    let mut mint = load_mint(mint_info, program_id, true)?;
 
     // Validate not frozen
     if account.is_frozen() {
@@ -65,9 +59,7 @@ pub fn process(
     }
 
     // Validate mint matches
-    if account.mint != *mint_info.key {
-        return Err(TokenError::MintMismatch.into());
-    }
+    assert_account_mint(&account, mint_info.key)?;
 
     // Validate sufficient funds
     if account.amount < amount {
@@ -76,7 +68,7 @@ pub fn process(
 
     // Validate authority
     let delegate_pubkey: Option<&Pubkey> = account.delegate.as_ref();
-    let used_delegate = validate_owner_or_delegate(
+    let authority_kind = validate_owner_or_delegate_kind(
         program_id,
         &account.owner,
         delegate_pubkey,
@@ -84,24 +76,34 @@ pub fn process(
         &signer_accounts,
     )?;
 
-    // Handle delegate allowance
-    if used_delegate {
-        if account.delegated_amount < amount {
-            return Err(TokenError::InsufficientDelegatedAmount.into());
-        }
-        account.delegated_amount = checked_sub(account.delegated_amount, amount)?;
-        if account.delegated_amount == 0 {
-            account.delegate = COption::none();
+    // Handle delegate allowance. The balance was checked above, so when
+    // the allowance is the limit this reports InsufficientDelegatedAmount.
+    match authority_kind {
+        AuthorityKind::Owner => {}
+        AuthorityKind::Delegate => {
+            assert_delegate_not_expired(&account, &signer_accounts)?;
+            if account.delegated_amount < amount {
+                return Err(TokenError::InsufficientDelegatedAmount.into());
+            }
+            account.delegated_amount = checked_sub(account.delegated_amount, amount)?;
+            if account.delegated_amount == 0 {
+                account.delegate = COption::none();
+                account.delegate_expiry = COption::none();
+            }
         }
     }
 
-    // Burn tokens
+    // Burn tokens. The balance covers `amount`, so a supply that
+    // doesn't means supply and balances have drifted apart.
     account.amount = checked_sub(account.amount, amount)?;
-    mint.supply = checked_sub(mint.supply, amount)?;
+    mint.supply = mint
+        .supply
+        .checked_sub(amount)
+        .ok_or(TokenError::SupplyUnderflow)?;
 
     // Save states
-    account.pack_into_slice(&mut account_info.data.borrow_mut())?;
-    mint.pack_into_slice(&mut mint_info.data.borrow_mut())?;
+    store_token_account(account_info, &account)?;
+    store_mint(mint_info, &mint)?;
 
     Ok(())
 }
\ No newline at end of file
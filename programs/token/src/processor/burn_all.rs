@@ -0,0 +1,36 @@
+//! BurnAll Instruction Processor
+//!
+//! Burns an account's entire balance, decreasing supply.
+
+use crate::processor::burn;
+use crate::state::{Account, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Process BurnAll instruction
+///
+/// Accounts expected (same as Burn):
+/// 0. `[writable]` Token account to burn from
+/// 1. `[writable]` Mint
+/// 2. `[signer]` Owner or delegate
+/// 3. ..3+M `[signer]` Multisig signers (if applicable)
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // Account 0: Token account
+    let account_info = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // Validate before trusting the stored balance
+    assert_owned_by(account_info, program_id)?;
+    assert_account_data_length(account_info)?;
+
+    // Read the current balance
+    let amount = Account::unpack_from_slice(&account_info.data.borrow())?.amount;
+
+    // Burn exactly that, with all of Burn's checks
+    // (initialized, frozen, mint match, owner/delegate authority)
+    burn::process(program_id, accounts, amount)
+}
@@ -1,9 +1,9 @@
 //! CloseAccount Instruction Processor
 //!
-//! Closes a token account and reclaims the rent.
+//! Closes a token account or a zero-supply mint and reclaims the rent.
 
 use crate::error::TokenError;
-use crate::state::{Account, Pack};
+use crate::state::{Account, Mint, Pack};
 use crate::utils::*;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -14,14 +14,23 @@ use solana_program::{
 /// Process CloseAccount instruction
 ///
 /// Accounts expected:
-/// 0. `[writable]` Token account to close
+/// 0. `[writable]` Token account or mint to close
 /// 1. `[writable]` Destination for rent lamports
 /// 2. `[signer]` Close authority or owner
-/// 3..3+M. `[signer]` Multisig signers (if applicable)
+/// 3. ..3+M `[signer]` Multisig signers (if applicable)
+///
+/// A frozen token account can't be closed until it is thawed.
+///
+/// A native (wrapped SOL) account may be closed with a balance: its
+/// lamports are the wrapped SOL, so they all leave with the rent for the
+/// destination. CloseAccountWithReserve splits the rent reserve off.
+///
+/// A mint-sized account 0 is closed as a mint: its supply must be zero
+/// and the signer must be the mint's close authority.
 pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
 
-    // Account 0: Token account to close
+    // Account 0: Token account or mint to close
     let account_info = next_account_info(account_info_iter)?;
 
     // Account 1: Destination for lamports
@@ -33,10 +42,68 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     // Remaining: Multisig signers
     let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
 
+    close(
+        program_id,
+        account_info,
+        dest_info,
+        authority_info,
+        None,
+        &signer_accounts,
+    )
+}
+
+/// Process CloseAccountWithReserve instruction
+///
+/// Accounts expected:
+/// 0. `[writable]` Wrapped SOL account to close
+/// 1. `[writable]` Destination for the wrapped SOL
+/// 2. `[signer]` Close authority or owner
+/// 3. `[writable]` Destination for the rent reserve
+/// 4. ..4+M `[signer]` Multisig signers (if applicable)
+///
+/// Only a native token account has a reserve to split off; anything
+/// else fails with `InvalidState`.
+pub fn process_with_reserve(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Wrapped SOL account to close
+    let account_info = next_account_info(account_info_iter)?;
+
+    // Account 1: Destination for the wrapped SOL
+    let dest_info = next_account_info(account_info_iter)?;
+
+    // Account 2: Authority
+    let authority_info = next_account_info(account_info_iter)?;
+
+    // Account 3: Destination for the rent reserve
+    let reserve_dest_info = next_account_info(account_info_iter)?;
+
+    // Remaining: Multisig signers
+    let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    close(
+        program_id,
+        account_info,
+        dest_info,
+        authority_info,
+        Some(reserve_dest_info),
+        &signer_accounts,
+    )
+}
+
+/// Shared close logic. With a reserve destination, the account must be
+/// native and its rent reserve goes there.
+fn close(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    dest_info: &AccountInfo,
+    authority_info: &AccountInfo,
+    reserve_dest_info: Option<&AccountInfo>,
+    signer_accounts: &[AccountInfo],
+) -> ProgramResult {
     // Validate account to close
     assert_owned_by(account_info, program_id)?;
     assert_writable(account_info)?;
-    assert_data_length(account_info, Account::LEN)?;
 
     // Validate destination
     assert_writable(dest_info)?;
@@ -46,6 +113,22 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         return Err(TokenError::InvalidAuthority.into());
     }
 
+    if Mint::is_valid_len(account_info.data_len()) {
+        // A mint holds no wrapped SOL, so there's no reserve to split off
+        if reserve_dest_info.is_some() {
+            return Err(TokenError::InvalidState.into());
+        }
+        return process_close_mint(
+            program_id,
+            account_info,
+            dest_info,
+            authority_info,
+            signer_accounts,
+        );
+    }
+
+    assert_account_data_length(account_info)?;
+
     // Load account
     let account = Account::unpack_from_slice(&account_info.data.borrow())?;
 
@@ -54,8 +137,11 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         return Err(TokenError::UninitializedAccount.into());
     }
 
-    // Must have zero balance
-    if account.amount != 0 {
+    // Frozen tokens stay locked, even in an empty account
+    assert_not_frozen(&account)?;
+
+    // Must have zero balance, unless the balance is wrapped SOL
+    if !account.is_native() && account.amount != 0 {
         return Err(TokenError::NonZeroBalance.into());
     }
 
@@ -69,15 +155,81 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         program_id,
         close_authority,
         authority_info,
-        &signer_accounts,
+        signer_accounts,
     )?;
 
+    match (reserve_dest_info, account.is_native.as_ref()) {
+        (None, _) => reclaim(account_info, dest_info),
+        (Some(reserve_dest_info), Some(&rent_reserve)) => {
+            assert_writable(reserve_dest_info)?;
+            if reserve_dest_info.key == account_info.key {
+                return Err(TokenError::InvalidAuthority.into());
+            }
+            reclaim_split(account_info, dest_info, reserve_dest_info, rent_reserve)
+        }
+        // Only a wrapped SOL account has a reserve to split off
+        (Some(_), None) => Err(TokenError::InvalidState.into()),
+    }
+}
+
+fn process_close_mint(
+    program_id: &Pubkey,
+    mint_info: &AccountInfo,
+    dest_info: &AccountInfo,
+    authority_info: &AccountInfo,
+    signer_accounts: &[AccountInfo],
+) -> ProgramResult {
+    let mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
+
+    if !mint.is_initialized {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Outstanding tokens would be left without a mint
+    if mint.supply != 0 {
+        return Err(TokenError::NonZeroBalance.into());
+    }
+
+    // Only an explicitly set close authority can close a mint
+    let close_authority = mint
+        .close_authority
+        .as_ref()
+        .ok_or(TokenError::InvalidAuthority)?;
+
+    validate_authority(program_id, close_authority, authority_info, signer_accounts)?;
+
+    reclaim(mint_info, dest_info)
+}
+
+/// Move every lamport to `dest_info` and zero the closed account's data.
+fn reclaim(account_info: &AccountInfo, dest_info: &AccountInfo) -> ProgramResult {
     // Transfer lamports to destination
     let account_lamports = account_info.lamports();
-    **dest_info.lamports.borrow_mut() = dest_info
-        .lamports()
-        .checked_add(account_lamports)
-        .ok_or(TokenError::Overflow)?;
+    credit(dest_info, account_lamports)?;
+    **account_info.lamports.borrow_mut() = 0;
+
+    // Zero out account data
+    let mut account_data = account_info.data.borrow_mut();
+    account_data.fill(0);
+
+    Ok(())
+}
+
+/// Like `reclaim`, but the rent reserve goes to `reserve_dest_info` and
+/// only the wrapped SOL above it goes to `dest_info`.
+fn reclaim_split(
+    account_info: &AccountInfo,
+    dest_info: &AccountInfo,
+    reserve_dest_info: &AccountInfo,
+    rent_reserve: u64,
+) -> ProgramResult {
+    // An underfunded account has no wrapped SOL, only (part of) a reserve
+    let account_lamports = account_info.lamports();
+    let reserve = account_lamports.min(rent_reserve);
+    let wrapped = checked_sub(account_lamports, reserve)?;
+
+    credit(reserve_dest_info, reserve)?;
+    credit(dest_info, wrapped)?;
     **account_info.lamports.borrow_mut() = 0;
 
     // Zero out account data
@@ -85,4 +237,13 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     account_data.fill(0);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Add `lamports` to `info`'s balance.
+fn credit(info: &AccountInfo, lamports: u64) -> ProgramResult {
+    **info.lamports.borrow_mut() = info
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(TokenError::Overflow)?;
+    Ok(())
+}
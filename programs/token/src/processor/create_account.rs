@@ -0,0 +1,96 @@
+//! CreateAccount Instruction Processor
+//!
+//! Creates and initializes a token account in one instruction.
+
+use crate::error::TokenError;
+use crate::state::{is_native_mint, Account, AccountState, COption, Mint, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction, system_program,
+    sysvar::Sysvar,
+};
+
+/// Process CreateAccount instruction
+///
+/// Accounts expected:
+/// 0. `[writable, signer]` Payer for the new account's rent
+/// 1. `[writable, signer]` New token account
+/// 2. `[]` Mint the new account will hold
+/// 3. `[]` System program
+///
+/// Bundles the System Program `create_account` (at `Account::LEN`,
+/// funded for rent exemption) with InitializeAccount, so clients
+/// don't need two instructions.
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], owner: Pubkey) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Payer
+    let payer_info = next_account_info(account_info_iter)?;
+
+    // Account 1: New token account
+    let account_info = next_account_info(account_info_iter)?;
+
+    // Account 2: Mint
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // Account 3: System program
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    // Both the payer and the new account sign the system transfer
+    assert_signer(payer_info)?;
+    assert_signer(account_info)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Validate mint before spending anything
+    assert_owned_by(mint_info, program_id)?;
+    assert_mint_data_length(mint_info)?;
+
+    let mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
+    if !mint.is_initialized {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Create the account, owned by this program
+    let rent = Rent::get()?;
+    let rent_exempt_reserve = rent.minimum_balance(Account::LEN);
+    invoke(
+        &system_instruction::create_account(
+            payer_info.key,
+            account_info.key,
+            rent_exempt_reserve,
+            Account::LEN as u64,
+            program_id,
+        ),
+        &[
+            payer_info.clone(),
+            account_info.clone(),
+            system_program_info.clone(),
+        ],
+    )?;
+
+    // Initialize account
+    let mut account = Account::unpack_unchecked(&account_info.data.borrow())?;
+    account.mint = *mint_info.key;
+    account.owner = owner;
+    account.state = AccountState::Initialized;
+
+    // Native accounts start with no wrapped balance above the reserve
+    if is_native_mint(mint_info.key) {
+        account.is_native = COption::some(rent_exempt_reserve);
+        account.amount = checked_sub(account_info.lamports(), rent_exempt_reserve)?;
+    }
+
+    // Save account
+    account.pack_into_slice(&mut account_info.data.borrow_mut())?;
+
+    Ok(())
+}
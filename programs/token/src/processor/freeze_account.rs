@@ -3,7 +3,7 @@
 //! Freezes a token account, preventing transfers out.
 
 use crate::error::TokenError;
-use crate::state::{Account, AccountState, Mint, Pack};
+use crate::state::{Account, Mint, Pack};
 use crate::utils::*;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -17,7 +17,7 @@ use solana_program::{
 /// 0. `[writable]` Token account to freeze
 /// 1. `[]` Mint
 /// 2. `[signer]` Freeze authority
-/// 3..3+M. `[signer]` Multisig signers (if applicable)
+/// 3. ..3+M `[signer]` Multisig signers (if applicable)
 pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
 
@@ -36,11 +36,11 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     // Validate token account
     assert_owned_by(account_info, program_id)?;
     assert_writable(account_info)?;
-    assert_data_length(account_info, Account::LEN)?;
+    assert_account_data_length(account_info)?;
 
     // Validate mint
     assert_owned_by(mint_info, program_id)?;
-    assert_data_length(mint_info, Mint::LEN)?;
+    assert_mint_data_length(mint_info)?;
 
     // Load states
     let mut account = Account::unpack_from_slice(&account_info.data.borrow())?;
@@ -54,11 +54,14 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         return Err(TokenError::UninitializedAccount.into());
     }
 
-    // Validate account is for this mint
-    if account.mint != *mint_info.key {
-        return Err(TokenError::MintMismatch.into());
+    // Wrapped SOL accounts can't be frozen
+    if account.is_native() {
+        return Err(TokenError::CannotFreezeNative.into());
     }
 
+    // Validate account is for this mint
+    assert_account_mint(&account, mint_info.key)?;
+
     // Get freeze authority
     let freeze_authority = mint
         .freeze_authority
@@ -73,8 +76,8 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         &signer_accounts,
     )?;
 
-    // Freeze the account
-    account.state = AccountState::Frozen;
+    // Freeze the account (rejects an already-frozen account)
+    account.freeze()?;
 
     // Save account
     account.pack_into_slice(&mut account_info.data.borrow_mut())?;
@@ -0,0 +1,92 @@
+//! FreezeAccounts Instruction Processor
+//!
+//! Freezes many token accounts of one mint in a single instruction.
+
+use crate::error::TokenError;
+use crate::state::{Account, Mint, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+/// Process FreezeAccounts instruction
+///
+/// Accounts expected:
+/// 0. `[]` Mint
+/// 1. `[signer]` Freeze authority
+/// 2. ..2+N `[writable]` Token accounts to freeze (N = `count`)
+/// 3. 2+N.. `[signer]` Multisig signers (if applicable)
+///
+/// The freeze authority is checked once against the mint. Every
+/// account is validated before any is frozen, so one bad account
+/// fails the whole batch.
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], count: u8) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Mint
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // Account 1: Freeze authority
+    let authority_info = next_account_info(account_info_iter)?;
+
+    // Accounts 2..2+N: Token accounts
+    let mut token_infos = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        token_infos.push(next_account_info(account_info_iter)?);
+    }
+
+    // Remaining: Multisig signers
+    let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    // Validate mint
+    assert_owned_by(mint_info, program_id)?;
+    assert_mint_data_length(mint_info)?;
+
+    let mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
+    if !mint.is_initialized {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Validate freeze authority once for the whole batch
+    let freeze_authority = mint
+        .freeze_authority
+        .as_ref()
+        .ok_or(TokenError::FreezeAuthorityRequired)?;
+
+    validate_authority(
+        program_id,
+        freeze_authority,
+        authority_info,
+        &signer_accounts,
+    )?;
+
+    // Validate every account before freezing anything
+    let mut token_accounts = Vec::with_capacity(token_infos.len());
+    for token_info in &token_infos {
+        assert_owned_by(token_info, program_id)?;
+        assert_writable(token_info)?;
+        assert_account_data_length(token_info)?;
+
+        let account = Account::unpack_from_slice(&token_info.data.borrow())?;
+        if !account.is_initialized() {
+            return Err(TokenError::UninitializedAccount.into());
+        }
+
+        // Wrapped SOL accounts can't be frozen
+        if account.is_native() {
+            return Err(TokenError::CannotFreezeNative.into());
+        }
+        token_accounts.push(account);
+    }
+    assert_same_mint(&token_accounts, mint_info.key)?;
+
+    // Freeze and save each account (rejects an already-frozen account)
+    for (token_info, mut account) in token_infos.into_iter().zip(token_accounts) {
+        account.freeze()?;
+        account.pack_into_slice(&mut token_info.data.borrow_mut())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+//! GetAccountBalance Instruction Processor
+//!
+//! Returns a token account's balance as return data.
+
+use crate::error::TokenError;
+use crate::return_data::encode_account_balance;
+use crate::state::{Account, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    pubkey::Pubkey,
+};
+
+/// Process GetAccountBalance instruction
+///
+/// Accounts expected:
+/// 0. `[]` Token account
+///
+/// Return data: amount (u64, little-endian)
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Token account
+    let account_info = next_account_info(account_info_iter)?;
+
+    // Validate token account
+    assert_owned_by(account_info, program_id)?;
+    assert_account_data_length(account_info)?;
+
+    // Load and verify account is initialized
+    let account = Account::unpack_from_slice(&account_info.data.borrow())?;
+    if !account.is_initialized() {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Read-only: report balance, change nothing
+    set_return_data(&encode_account_balance(account.amount));
+
+    Ok(())
+}
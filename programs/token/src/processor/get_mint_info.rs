@@ -0,0 +1,55 @@
+//! GetMintInfo Instruction Processor
+//!
+//! Returns a compact summary of a mint as return data.
+
+use crate::return_data::{encode_mint_info, MintInfo};
+use crate::state::{Mint, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    pubkey::Pubkey,
+};
+
+/// Process GetMintInfo instruction
+///
+/// Accounts expected:
+/// 0. `[]` Mint
+///
+/// Return data (12 bytes):
+///
+/// ```text
+/// [0..8]: supply (u64, little-endian)
+/// [8]: decimals (u8)
+/// [9]: is_initialized (0 or 1)
+/// [10]: has_mint_authority (0 or 1)
+/// [11]: has_freeze_authority (0 or 1)
+/// ```
+///
+/// An uninitialized mint isn't an error here; it's reported through
+/// the is_initialized byte.
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Mint
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // Validate mint
+    assert_owned_by(mint_info, program_id)?;
+    assert_mint_data_length(mint_info)?;
+
+    let mint = Mint::unpack_unchecked(&mint_info.data.borrow())?;
+
+    // Read-only: report the summary, change nothing
+    let info = MintInfo {
+        supply: mint.supply,
+        decimals: mint.decimals,
+        is_initialized: mint.is_initialized,
+        has_mint_authority: mint.mint_authority.is_some(),
+        has_freeze_authority: mint.freeze_authority.is_some(),
+    };
+    set_return_data(&encode_mint_info(&info));
+
+    Ok(())
+}
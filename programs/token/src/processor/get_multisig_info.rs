@@ -0,0 +1,52 @@
+//! GetMultisigInfo Instruction Processor
+//!
+//! Returns a multisig's M, N and signer set as return data.
+
+use crate::error::TokenError;
+use crate::return_data::{encode_multisig_info, MultisigInfo};
+use crate::state::{Multisig, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    pubkey::Pubkey,
+};
+
+/// Process GetMultisigInfo instruction
+///
+/// Accounts expected:
+/// 0. `[]` Multisig
+///
+/// Return data (2 + 32*n bytes):
+///
+/// ```text
+/// [0]: m (u8)
+/// [1]: n (u8)
+/// [2..2+32*n]: the n valid signer pubkeys, in order
+/// ```
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Multisig
+    let multisig_info = next_account_info(account_info_iter)?;
+
+    // Validate multisig
+    assert_owned_by(multisig_info, program_id)?;
+    assert_data_length(multisig_info, Multisig::LEN)?;
+
+    // Load and verify multisig is initialized
+    let multisig = Multisig::unpack_unchecked(&multisig_info.data.borrow())?;
+    if !multisig.is_initialized {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Read-only: report the configuration, change nothing
+    let info = MultisigInfo {
+        m: multisig.m,
+        signers: multisig.valid_signers().to_vec(),
+    };
+    set_return_data(&encode_multisig_info(&info));
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+//! GetSupply Instruction Processor
+//!
+//! Returns a mint's current supply as return data.
+
+use crate::error::TokenError;
+use crate::return_data::encode_supply;
+use crate::state::{Mint, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    pubkey::Pubkey,
+};
+
+/// Process GetSupply instruction
+///
+/// Accounts expected:
+/// 0. `[]` Mint
+///
+/// Return data: supply (u64, little-endian)
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Mint
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // Validate mint
+    assert_owned_by(mint_info, program_id)?;
+    assert_mint_data_length(mint_info)?;
+
+    // Load and verify mint is initialized
+    let mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
+    if !mint.is_initialized {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Read-only: report supply, change nothing
+    set_return_data(&encode_supply(mint.supply));
+
+    Ok(())
+}
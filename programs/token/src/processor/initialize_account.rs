@@ -3,14 +3,14 @@
 //! Creates a new token account (wallet for a specific token).
 
 use crate::error::TokenError;
-use crate::state::{Account, AccountState, COption, Mint, Pack};
+use crate::state::{is_native_mint, Account, AccountState, COption, Mint, Pack};
 use crate::utils::*;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     rent::Rent,
-    sysvar::Sysvar,
+    sysvar::{self, Sysvar},
 };
 
 /// Process InitializeAccount instruction
@@ -19,8 +19,54 @@ use solana_program::{
 /// 0. `[writable]` Token account to initialize
 /// 1. `[]` Mint this account will hold
 /// 2. `[]` Owner of the new account
-/// 3. `[]` Rent sysvar
+/// 3. `[]` Rent sysvar (optional)
+/// 4. `[]` Instructions sysvar (only read with the fresh-account-check feature)
+///
+/// The rent sysvar account is only kept for older clients. If it's
+/// missing, rent is read with `Rent::get()` instead.
+///
+/// With the `fresh-account-check` feature, the token account must have
+/// been created by a System Program instruction earlier in the same
+/// transaction, or this fails with `AccountNotFreshlyCreated`.
 pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    initialize(program_id, accounts, None, COption::none())
+}
+
+/// Process InitializeAccountWithCloseAuthority instruction
+///
+/// Same accounts as InitializeAccount. The new account starts with
+/// `close_authority` already set instead of defaulting to the owner.
+pub fn process_with_close_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    close_authority: Pubkey,
+) -> ProgramResult {
+    initialize(program_id, accounts, None, COption::some(close_authority))
+}
+
+/// Initialize with the owner given as instruction data
+///
+/// Accounts expected:
+/// 0. `[writable]` Token account to initialize
+/// 1. `[]` Mint this account will hold
+/// 2. `[]` Rent sysvar (optional)
+///
+/// Otherwise the same as InitializeAccount.
+pub fn process_with_owner(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    owner: Pubkey,
+) -> ProgramResult {
+    initialize(program_id, accounts, Some(owner), COption::none())
+}
+
+/// Shared initialization logic for all InitializeAccount variants
+fn initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    owner: Option<Pubkey>,
+    close_authority: COption<Pubkey>,
+) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
 
     // Account 0: Token account
@@ -29,22 +75,46 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     // Account 1: Mint
     let mint_info = next_account_info(account_info_iter)?;
 
-    // Account 2: Owner
-    let owner_info = next_account_info(account_info_iter)?;
+    // Account 2: Owner, unless it came in the instruction data
+    let owner = match owner {
+        Some(owner) => owner,
+        None => *next_account_info(account_info_iter)?.key,
+    };
 
-    // Account 3: Rent sysvar
-    let rent_info = next_account_info(account_info_iter)?;
-    let rent = Rent::from_account_info(rent_info)?;
+    // Account 3 (optional): Rent sysvar, otherwise read via syscall
+    let rent = match account_info_iter.as_slice().first() {
+        Some(rent_info) if sysvar::rent::check_id(rent_info.key) => {
+            account_info_iter.next();
+            Rent::from_account_info(rent_info)?
+        }
+        _ => Rent::get()?,
+    };
 
     // Validate token account
     assert_owned_by(account_info, program_id)?;
     assert_writable(account_info)?;
-    assert_data_length(account_info, Account::LEN)?;
+    assert_account_data_length(account_info)?;
     assert_rent_exempt(&rent, account_info)?;
 
+    // Load token account (may be uninitialized)
+    let mut account = Account::unpack_unchecked(&account_info.data.borrow())?;
+
+    // Prevent double initialization, before anything else can fail
+    assert_uninitialized_account(&account)?;
+
+    // Front-running guard: creation must be in this same transaction
+    #[cfg(feature = "fresh-account-check")]
+    {
+        let instructions_info = accounts
+            .iter()
+            .find(|info| sysvar::instructions::check_id(info.key))
+            .ok_or(solana_program::program_error::ProgramError::NotEnoughAccountKeys)?;
+        assert_freshly_created(account_info.key, instructions_info)?;
+    }
+
     // Validate mint
     assert_owned_by(mint_info, program_id)?;
-    assert_data_length(mint_info, Mint::LEN)?;
+    assert_mint_data_length(mint_info)?;
 
     // Load and verify mint is initialized
     let mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
@@ -52,23 +122,24 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         return Err(TokenError::UninitializedAccount.into());
     }
 
-    // Load token account
-    let mut account = Account::unpack_from_slice(&account_info.data.borrow())?;
-
-    // Prevent double initialization
-    if account.is_initialized() {
-        return Err(TokenError::AlreadyInitialized.into());
-    }
-
     // Initialize account
     account.mint = *mint_info.key;
-    account.owner = *owner_info.key;
+    account.owner = owner;
     account.amount = 0;
     account.delegate = COption::none();
     account.state = AccountState::Initialized;
-    account.is_native = COption::none();
     account.delegated_amount = 0;
-    account.close_authority = COption::none();
+    account.close_authority = close_authority;
+
+    // Native (wrapped SOL) accounts hold their balance as lamports.
+    // Everything above the rent-exempt reserve counts as tokens.
+    if is_native_mint(mint_info.key) {
+        let rent_exempt_reserve = rent.minimum_balance(account_info.data_len());
+        account.is_native = COption::some(rent_exempt_reserve);
+        account.amount = checked_sub(account_info.lamports(), rent_exempt_reserve)?;
+    } else {
+        account.is_native = COption::none();
+    }
 
     // Save account
     account.pack_into_slice(&mut account_info.data.borrow_mut())?;
@@ -0,0 +1,54 @@
+//! InitializeAccountIdempotent Instruction Processor
+//!
+//! Initializes a token account unless it already holds the same mint
+//! for the same owner.
+
+use crate::error::TokenError;
+use crate::processor::initialize_account;
+use crate::state::{Account, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+/// Process InitializeAccountIdempotent instruction
+///
+/// Accounts expected:
+/// 0. `[writable]` Token account to initialize
+/// 1. `[]` Mint this account will hold
+/// 2. `[]` Rent sysvar (optional)
+///
+/// An uninitialized account is initialized exactly as InitializeAccount
+/// would. An initialized one is left untouched: the call succeeds if it
+/// already belongs to `mint` and `owner`, and fails with `MintMismatch`
+/// or `OwnerMismatch` otherwise.
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], owner: Pubkey) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Token account
+    let account_info = next_account_info(account_info_iter)?;
+
+    // Account 1: Mint
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // Validate token account
+    assert_owned_by(account_info, program_id)?;
+    assert_account_data_length(account_info)?;
+
+    // Load token account (may be uninitialized)
+    let account = Account::unpack_unchecked(&account_info.data.borrow())?;
+
+    if !account.is_initialized() {
+        return initialize_account::process_with_owner(program_id, accounts, owner);
+    }
+
+    // Already initialized: fine only if it's the account asked for
+    assert_account_mint(&account, mint_info.key)?;
+    if account.owner != owner {
+        return Err(TokenError::OwnerMismatch.into());
+    }
+
+    Ok(())
+}
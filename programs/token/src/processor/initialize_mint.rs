@@ -3,21 +3,26 @@
 //! Creates a new token mint (defines a new token type).
 
 use crate::error::TokenError;
-use crate::state::{COption, Mint, Pack};
+use crate::state::{COption, Mint, Pack, MAX_DECIMALS};
 use crate::utils::*;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     rent::Rent,
-    sysvar::Sysvar,
+    sysvar::{self, Sysvar},
 };
 
 /// Process InitializeMint instruction
 ///
 /// Accounts expected:
 /// 0. `[writable]` Mint account to initialize
-/// 1. `[]` Rent sysvar
+/// 1. `[]` Rent sysvar (optional)
+///
+/// The rent sysvar account is only kept for older clients. If it's
+/// missing, rent is read with `Rent::get()` instead.
+///
+/// Fails with `InvalidDecimals` if `decimals` is above `MAX_DECIMALS`.
 pub fn process(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -30,22 +35,30 @@ pub fn process(
     // Account 0: Mint account
     let mint_info = next_account_info(account_info_iter)?;
 
-    // Account 1: Rent sysvar
-    let rent_info = next_account_info(account_info_iter)?;
-    let rent = Rent::from_account_info(rent_info)?;
+    // Account 1 (optional): Rent sysvar, otherwise read via syscall
+    let rent = match account_info_iter.as_slice().first() {
+        Some(rent_info) if sysvar::rent::check_id(rent_info.key) => {
+            account_info_iter.next();
+            Rent::from_account_info(rent_info)?
+        }
+        _ => Rent::get()?,
+    };
 
     // Validate mint account
     assert_owned_by(mint_info, program_id)?;
     assert_writable(mint_info)?;
-    assert_data_length(mint_info, Mint::LEN)?;
+    assert_mint_data_length(mint_info)?;
     assert_rent_exempt(&rent, mint_info)?;
 
-    // Load mint
-    let mut mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
+    // Load mint (may be uninitialized)
+    let mut mint = Mint::unpack_unchecked(&mint_info.data.borrow())?;
 
     // Prevent double initialization
-    if mint.is_initialized {
-        return Err(TokenError::AlreadyInitialized.into());
+    assert_uninitialized_mint(&mint)?;
+
+    // Reject decimals too large to display
+    if decimals > MAX_DECIMALS {
+        return Err(TokenError::InvalidDecimals.into());
     }
 
     // Initialize mint
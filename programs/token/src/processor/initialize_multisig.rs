@@ -18,7 +18,9 @@ use solana_program::{
 /// Accounts expected:
 /// 0. `[writable]` Multisig account to initialize
 /// 1. `[]` Rent sysvar
-/// 2..2+N. `[]` Signer accounts
+/// 2. ..2+N `[]` Signer accounts
+///
+/// Fails with `InvalidMultisigConfig` unless `1 <= m <= n <= MAX_SIGNERS`.
 pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], m: u8) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
 
@@ -38,23 +40,25 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], m: u8) -> ProgramR
     assert_data_length(multisig_info, Multisig::LEN)?;
     assert_rent_exempt(&rent, multisig_info)?;
 
-    // Validate signer count
+    // Load multisig (may be uninitialized)
+    let mut multisig = Multisig::unpack_unchecked(&multisig_info.data.borrow())?;
+
+    // Prevent double initialization, before validating the new config
+    assert_uninitialized_multisig(&multisig)?;
+
+    // N is the number of signer accounts passed, so the stored signer
+    // list always matches it exactly
     let n = signer_infos.len();
-    if n < 1 || n > MAX_SIGNERS {
-        return Err(TokenError::InvalidMultisigConfig.into());
-    }
 
-    // Validate m
-    if m < 1 || m as usize > n {
+    // Validate 1 <= m <= n <= MAX_SIGNERS
+    if m < 1 || m as usize > n || n > MAX_SIGNERS {
         return Err(TokenError::InvalidMultisigConfig.into());
     }
 
-    // Load multisig
-    let mut multisig = Multisig::unpack_from_slice(&multisig_info.data.borrow())?;
-
-    // Prevent double initialization
-    if multisig.is_initialized {
-        return Err(TokenError::AlreadyInitialized.into());
+    // Strict policy: no 1-of-N (a genuine 1-of-1 is still fine)
+    #[cfg(feature = "strict-multisig")]
+    if m == 1 && n > 1 {
+        return Err(TokenError::MultisigThresholdTooLow.into());
     }
 
     // Initialize multisig
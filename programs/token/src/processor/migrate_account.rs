@@ -0,0 +1,99 @@
+//! MigrateAccount Instruction Processor
+//!
+//! Upgrades a legacy token account to the extended layout.
+
+use crate::error::TokenError;
+use crate::state::{Account, COption, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction, system_program,
+    sysvar::Sysvar,
+};
+
+/// Process MigrateAccount instruction
+///
+/// Accounts expected:
+/// 0. `[writable]` Legacy token account (`Account::LEN` bytes)
+/// 1. `[writable, signer]` Payer for the extra rent
+/// 2. `[]` System program
+/// 3. `[signer]` Owner
+/// 4. ..4+M `[signer]` Multisig signers (if applicable)
+///
+/// Unlike Reallocate, the result is always `Account::EXTENDED_LEN`
+/// with the layout version written, so the extension fields read back
+/// as their defaults rather than as an unversioned area.
+///
+/// A wrapped SOL account's rent reserve (`is_native`) is raised to the
+/// extended size's minimum and the payer covers it, so the balance
+/// stays `lamports - reserve`.
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Token account
+    let account_info = next_account_info(account_info_iter)?;
+
+    // Account 1: Payer
+    let payer_info = next_account_info(account_info_iter)?;
+
+    // Account 2: System program
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    // Account 3: Owner
+    let owner_info = next_account_info(account_info_iter)?;
+
+    // Remaining: Multisig signers
+    let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    // Validate token account (legacy layout only)
+    assert_owned_by(account_info, program_id)?;
+    assert_writable(account_info)?;
+    assert_data_length(account_info, Account::LEN)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load account state
+    let mut account = Account::unpack_from_slice(&account_info.data.borrow())?;
+    if !account.is_initialized() {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Only the owner may migrate
+    validate_authority(program_id, &account.owner, owner_info, &signer_accounts)?;
+
+    // Rent for the new size, plus the balance of a wrapped SOL account
+    // on top of its reserve
+    let rent = Rent::get()?;
+    let mut required = rent.minimum_balance(Account::EXTENDED_LEN);
+    let current = account_info.lamports();
+    if account.is_native() {
+        account.is_native = COption::some(required);
+        required = checked_add(required, account.amount)?;
+    }
+
+    // Grow, then rewrite the state with the version byte and defaults
+    account_info.realloc(Account::EXTENDED_LEN, true)?;
+    store_token_account(account_info, &account)?;
+
+    // Top up rent for the new size
+    if current < required {
+        assert_signer(payer_info)?;
+        invoke(
+            &system_instruction::transfer(payer_info.key, account_info.key, required - current),
+            &[
+                payer_info.clone(),
+                account_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
@@ -3,7 +3,6 @@
 //! Mints new tokens to a token account.
 
 use crate::error::TokenError;
-use crate::state::{Account, Mint, Pack};
 use crate::utils::*;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -17,12 +16,33 @@ use solana_program::{
 /// 0. `[writable]` Mint
 /// 1. `[writable]` Destination token account
 /// 2. `[signer]` Mint authority
-/// 3..3+M. `[signer]` Multisig signers (if applicable)
+/// 3. ..3+M `[signer]` Multisig signers (if applicable)
+///
+/// The destination must be a token account, never the mint itself
+/// (`ExpectedAccount`).
+///
+/// With the `reject-zero-amount` feature, an amount of 0 fails with
+/// `ZeroAmount`; otherwise it succeeds without changing anything.
+///
+/// A frozen destination fails with `AccountFrozen`, as it does for
+/// Transfer. With the `allow-mint-to-frozen` feature the tokens are
+/// minted anyway and stay locked until the account is thawed, for
+/// issuers that settle to frozen holders.
+///
+/// Only the mint authority can mint. Delegation doesn't apply here:
+/// the destination's delegate is rejected with `InvalidAuthority`
+/// like any other key.
 pub fn process(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     amount: u64,
 ) -> ProgramResult {
+    // Minting nothing is treated as a client bug
+    #[cfg(feature = "reject-zero-amount")]
+    if amount == 0 {
+        return Err(TokenError::ZeroAmount.into());
+    }
+
     let account_info_iter = &mut accounts.iter();
 
     // Account 0: Mint
@@ -37,41 +57,26 @@ pub fn process(
     // Remaining: Multisig signers
     let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
 
-    // Validate mint
-    assert_owned_by(mint_info, program_id)?;
-    assert_writable(mint_info)?;
-    assert_data_length(mint_info, Mint::LEN)?;
-
-    // Validate destination
-    assert_owned_by(dest_info, program_id)?;
-    assert_writable(dest_info)?;
-    assert_data_length(dest_info, Account::LEN)?;
-
-    // Load states
-    let mut mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
-    let mut dest_account = Account::unpack_from_slice(&dest_info.data.borrow())?;
-
-    // Validate mint is initialized
-    if !mint.is_initialized {
-        return Err(TokenError::UninitializedAccount.into());
+    // The mint can't be its own destination: packing an Account over it
+    // would corrupt it
+    if dest_info.key == mint_info.key {
+        return Err(TokenError::ExpectedAccount.into());
     }
 
-    // Validate destination is initialized
-    if !dest_account.is_initialized() {
-        return Err(TokenError::UninitializedAccount.into());
-    }
+    // Load and validate mint and destination. `load_token_account`
+    // rejects a read-only destination (`InvalidAccountData`) and an
+    // uninitialized one (`UninitializedAccount`) before anything is written
+    let mut mint = load_mint(mint_info, program_id, true)?;
+    let mut dest_account = load_token_account(dest_info, program_id, true)?;
 
-    // Validate destination is not frozen
-    if dest_account.is_frozen() {
-        return Err(TokenError::AccountFrozen.into());
-    }
+    // Frozen destinations can't receive tokens unless the issuer opts in
+    #[cfg(not(feature = "allow-mint-to-frozen"))]
+    assert_not_frozen(&dest_account)?;
 
     // Validate destination mint matches
-    if dest_account.mint != *mint_info.key {
-        return Err(TokenError::MintMismatch.into());
-    }
+    assert_account_mint(&dest_account, mint_info.key)?;
 
-    // Get and validate mint authority
+    // Get and validate mint authority (never the destination's delegate)
     let mint_authority = mint
         .mint_authority
         .as_ref()
@@ -84,13 +89,24 @@ pub fn process(
         &signer_accounts,
     )?;
 
+    // Minting is halted while paused (extended mints only)
+    if mint.mint_paused {
+        return Err(TokenError::MintPaused.into());
+    }
+
+    // Enforce the supply cap (extended mints only)
+    mint.check_supply_cap(amount)?;
+
     // Update balances
     mint.supply = checked_add(mint.supply, amount)?;
     dest_account.amount = checked_add(dest_account.amount, amount)?;
 
     // Save states
-    mint.pack_into_slice(&mut mint_info.data.borrow_mut())?;
-    dest_account.pack_into_slice(&mut dest_info.data.borrow_mut())?;
+    store_mint(mint_info, &mint)?;
+    store_token_account(dest_info, &dest_account)?;
+
+    #[cfg(feature = "events")]
+    events::emit_mint_to(mint_info.key, dest_info.key, amount);
 
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,101 @@
+//! MintToBatch Instruction Processor
+//!
+//! Mints new tokens to many token accounts at once.
+
+use crate::error::TokenError;
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+/// Process MintToBatch instruction
+///
+/// Accounts expected:
+/// 0. `[writable]` Mint
+/// 1. `[signer]` Mint authority
+/// 2. ..2+N `[writable]` Destination token accounts (one per amount)
+/// 3. 2+N.. `[signer]` Multisig signers (if applicable)
+///
+/// Every destination is validated as in MintTo before anything is
+/// minted, including the frozen check unless `allow-mint-to-frozen`.
+/// The batch size is bounded by `MAX_BATCH_LEN` at unpack time.
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amounts: &[u64],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Mint
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // Account 1: Authority
+    let authority_info = next_account_info(account_info_iter)?;
+
+    // Accounts 2..2+N: Destinations
+    let mut dest_infos = Vec::with_capacity(amounts.len());
+    for _ in amounts {
+        dest_infos.push(next_account_info(account_info_iter)?);
+    }
+
+    // Remaining: Multisig signers
+    let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    // Load and validate mint
+    let mut mint = load_mint(mint_info, program_id, true)?;
+
+    // Validate every destination before minting anything
+    let mut total: u64 = 0;
+    for (dest_info, &amount) in dest_infos.iter().zip(amounts) {
+        if dest_info.key == mint_info.key {
+            return Err(TokenError::ExpectedAccount.into());
+        }
+
+        let dest_account = load_token_account(dest_info, program_id, true)?;
+        #[cfg(not(feature = "allow-mint-to-frozen"))]
+        assert_not_frozen(&dest_account)?;
+        assert_account_mint(&dest_account, mint_info.key)?;
+
+        total = checked_add(total, amount)?;
+    }
+
+    // Get and validate mint authority
+    let mint_authority = mint
+        .mint_authority
+        .as_ref()
+        .ok_or(TokenError::MintAuthorityRequired)?;
+
+    validate_authority(
+        program_id,
+        mint_authority,
+        authority_info,
+        &signer_accounts,
+    )?;
+
+    // Minting is halted while paused (extended mints only)
+    if mint.mint_paused {
+        return Err(TokenError::MintPaused.into());
+    }
+
+    // Enforce the supply cap against the whole batch
+    mint.check_supply_cap(total)?;
+
+    // Update supply once
+    mint.supply = checked_add(mint.supply, total)?;
+    store_mint(mint_info, &mint)?;
+
+    // Credit destinations. Each one is re-read so a destination
+    // listed twice receives both amounts.
+    for (dest_info, &amount) in dest_infos.iter().zip(amounts) {
+        let mut dest_account = load_token_account(dest_info, program_id, true)?;
+        dest_account.amount = checked_add(dest_account.amount, amount)?;
+        store_token_account(dest_info, &dest_account)?;
+
+        #[cfg(feature = "events")]
+        events::emit_mint_to(mint_info.key, dest_info.key, amount);
+    }
+
+    Ok(())
+}
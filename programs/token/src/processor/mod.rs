@@ -4,17 +4,40 @@
 //! Each instruction has its own file for clarity and maintainability.
 
 pub mod approve;
+pub mod approve_all;
+pub mod approve_and_set_close_authority;
 pub mod burn;
+pub mod burn_all;
 pub mod close_account;
+pub mod create_account;
 pub mod freeze_account;
+pub mod freeze_accounts;
+pub mod get_account_balance;
+pub mod get_mint_info;
+pub mod get_multisig_info;
+pub mod get_supply;
 pub mod initialize_account;
+pub mod initialize_account_idempotent;
 pub mod initialize_mint;
 pub mod initialize_multisig;
+pub mod migrate_account;
 pub mod mint_to;
+pub mod mint_to_batch;
+pub mod reallocate;
+pub mod reconcile_supply;
 pub mod revoke;
+pub mod revoke_batch;
 pub mod set_authority;
+pub mod set_memo_required;
+pub mod set_mint_paused;
+pub mod set_non_transferable;
+pub mod set_supply_cap;
+pub mod set_transfer_hook;
 pub mod thaw_account;
 pub mod transfer;
+pub mod transfer_batch;
+pub mod transfer_with_fee;
+pub mod verify_account;
 
 use crate::instruction::TokenInstruction;
 use solana_program::{
@@ -69,6 +92,11 @@ impl Processor {
                 transfer::process(program_id, accounts, amount)
             }
 
+            TokenInstruction::TransferWithReturnData { amount } => {
+                msg!("Instruction: TransferWithReturnData");
+                transfer::process_with_return_data(program_id, accounts, amount)
+            }
+
             TokenInstruction::Approve { amount } => {
                 msg!("Instruction: Approve");
                 approve::process(program_id, accounts, amount)
@@ -111,6 +139,157 @@ impl Processor {
                 msg!("Instruction: ThawAccount");
                 thaw_account::process(program_id, accounts)
             }
+
+            TokenInstruction::TransferBatch { amounts } => {
+                msg!("Instruction: TransferBatch");
+                transfer_batch::process(program_id, accounts, &amounts)
+            }
+
+            TokenInstruction::BurnAll => {
+                msg!("Instruction: BurnAll");
+                burn_all::process(program_id, accounts)
+            }
+
+            TokenInstruction::ApproveAll => {
+                msg!("Instruction: ApproveAll");
+                approve_all::process(program_id, accounts)
+            }
+
+            TokenInstruction::Reallocate { new_len } => {
+                msg!("Instruction: Reallocate");
+                reallocate::process(program_id, accounts, new_len)
+            }
+
+            TokenInstruction::SetNonTransferable => {
+                msg!("Instruction: SetNonTransferable");
+                set_non_transferable::process(program_id, accounts)
+            }
+
+            TokenInstruction::TransferWithFee {
+                amount,
+                fee_basis_points,
+            } => {
+                msg!("Instruction: TransferWithFee");
+                transfer_with_fee::process(program_id, accounts, amount, fee_basis_points)
+            }
+
+            TokenInstruction::CreateAccount { owner } => {
+                msg!("Instruction: CreateAccount");
+                create_account::process(program_id, accounts, owner)
+            }
+
+            TokenInstruction::FreezeAccounts { count } => {
+                msg!("Instruction: FreezeAccounts");
+                freeze_accounts::process(program_id, accounts, count)
+            }
+
+            TokenInstruction::GetMintInfo => {
+                msg!("Instruction: GetMintInfo");
+                get_mint_info::process(program_id, accounts)
+            }
+
+            TokenInstruction::GetAccountBalance => {
+                msg!("Instruction: GetAccountBalance");
+                get_account_balance::process(program_id, accounts)
+            }
+
+            TokenInstruction::GetMultisigInfo => {
+                msg!("Instruction: GetMultisigInfo");
+                get_multisig_info::process(program_id, accounts)
+            }
+
+            TokenInstruction::MintToBatch { amounts } => {
+                msg!("Instruction: MintToBatch");
+                mint_to_batch::process(program_id, accounts, &amounts)
+            }
+
+            TokenInstruction::RevokeBatch { count } => {
+                msg!("Instruction: RevokeBatch");
+                revoke_batch::process(program_id, accounts, count)
+            }
+
+            TokenInstruction::MigrateAccount => {
+                msg!("Instruction: MigrateAccount");
+                migrate_account::process(program_id, accounts)
+            }
+
+            TokenInstruction::InitializeAccountIdempotent { owner } => {
+                msg!("Instruction: InitializeAccountIdempotent");
+                initialize_account_idempotent::process(program_id, accounts, owner)
+            }
+
+            TokenInstruction::SetMemoRequired { required } => {
+                msg!("Instruction: SetMemoRequired");
+                set_memo_required::process(program_id, accounts, required)
+            }
+
+            TokenInstruction::SetSupplyCap { cap } => {
+                msg!("Instruction: SetSupplyCap");
+                set_supply_cap::process(program_id, accounts, cap)
+            }
+
+            TokenInstruction::SetMintPaused { paused } => {
+                msg!("Instruction: SetMintPaused");
+                set_mint_paused::process(program_id, accounts, paused)
+            }
+
+            TokenInstruction::SetTransferHook { program } => {
+                msg!("Instruction: SetTransferHook");
+                set_transfer_hook::process(program_id, accounts, program)
+            }
+
+            TokenInstruction::ApproveWithExpiry {
+                amount,
+                expiry_unix_ts,
+            } => {
+                msg!("Instruction: ApproveWithExpiry");
+                approve::process_with_expiry(program_id, accounts, amount, expiry_unix_ts)
+            }
+
+            TokenInstruction::InitializeAccountWithCloseAuthority { close_authority } => {
+                msg!("Instruction: InitializeAccountWithCloseAuthority");
+                initialize_account::process_with_close_authority(
+                    program_id,
+                    accounts,
+                    close_authority,
+                )
+            }
+
+            TokenInstruction::GetSupply => {
+                msg!("Instruction: GetSupply");
+                get_supply::process(program_id, accounts)
+            }
+
+            TokenInstruction::ReconcileSupply { strict } => {
+                msg!("Instruction: ReconcileSupply");
+                reconcile_supply::process(program_id, accounts, strict)
+            }
+
+            TokenInstruction::ApproveAndSetCloseAuthority {
+                amount,
+                close_authority,
+            } => {
+                msg!("Instruction: ApproveAndSetCloseAuthority");
+                approve_and_set_close_authority::process(
+                    program_id,
+                    accounts,
+                    amount,
+                    close_authority,
+                )
+            }
+
+            TokenInstruction::VerifyAccount {
+                expected_mint,
+                expected_owner,
+            } => {
+                msg!("Instruction: VerifyAccount");
+                verify_account::process(program_id, accounts, expected_mint, expected_owner)
+            }
+
+            TokenInstruction::CloseAccountWithReserve => {
+                msg!("Instruction: CloseAccountWithReserve");
+                close_account::process_with_reserve(program_id, accounts)
+            }
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,106 @@
+//! Reallocate Instruction Processor
+//!
+//! Resizes a token account to make room for extension data.
+
+use crate::error::TokenError;
+use crate::state::{Account, COption};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction, system_program,
+    sysvar::Sysvar,
+};
+
+/// Process Reallocate instruction
+///
+/// Accounts expected:
+/// 0. `[writable]` Token account to resize
+/// 1. `[writable, signer]` Payer for any extra rent
+/// 2. `[]` System program
+/// 3. `[signer]` Owner
+/// 4. ..4+M `[signer]` Multisig signers (if applicable)
+///
+/// `new_len` must be `Account::LEN` or `Account::EXTENDED_LEN`, the
+/// only sizes later instructions can read. Shrinking back to the base
+/// layout fails while an extension field is set. If the larger account
+/// is no longer rent exempt, the payer tops it up through the System
+/// Program.
+///
+/// A wrapped SOL account's rent reserve (`is_native`) is recomputed for
+/// the new size, keeping `amount == lamports - reserve`: the payer
+/// covers a larger reserve, and lamports freed by a smaller one are
+/// added to the balance.
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], new_len: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Token account
+    let account_info = next_account_info(account_info_iter)?;
+
+    // Account 1: Payer
+    let payer_info = next_account_info(account_info_iter)?;
+
+    // Account 2: System program
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    // Account 3: Owner
+    let owner_info = next_account_info(account_info_iter)?;
+
+    // Remaining: Multisig signers
+    let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    // Only the base and extended layouts can be read back
+    let new_len = usize::try_from(new_len).map_err(|_| ProgramError::InvalidArgument)?;
+    if new_len != Account::LEN && new_len != Account::EXTENDED_LEN {
+        return Err(TokenError::InvalidAccountDataLength.into());
+    }
+
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and validate token account
+    let mut account = load_token_account(account_info, program_id, true)?;
+
+    // Shrinking would drop extension fields that are still in use
+    if new_len == Account::LEN && account.has_extensions() {
+        return Err(TokenError::InvalidAccountDataLength.into());
+    }
+
+    // Only the owner may resize
+    validate_authority(program_id, &account.owner, owner_info, &signer_accounts)?;
+
+    // Rent for the new size, plus the balance of a wrapped SOL account
+    // on top of its reserve
+    let rent = Rent::get()?;
+    let mut required = rent.minimum_balance(new_len);
+    let current = account_info.lamports();
+    if account.is_native() {
+        account.is_native = COption::some(required);
+        account.amount = account.amount.max(current.saturating_sub(required));
+        required = checked_add(required, account.amount)?;
+    }
+
+    // Resize, then repack so the extension area has a layout version
+    account_info.realloc(new_len, true)?;
+    store_token_account(account_info, &account)?;
+
+    // Top up rent for the new size
+    if current < required {
+        assert_signer(payer_info)?;
+        invoke(
+            &system_instruction::transfer(payer_info.key, account_info.key, required - current),
+            &[
+                payer_info.clone(),
+                account_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
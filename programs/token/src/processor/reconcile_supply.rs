@@ -0,0 +1,78 @@
+//! ReconcileSupply Instruction Processor
+//!
+//! Compares a mint's recorded supply against the sum of its token accounts.
+
+use crate::error::TokenError;
+use crate::return_data::encode_supply_reconciliation;
+use crate::state::{Account, Mint, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Process ReconcileSupply instruction
+///
+/// Accounts expected:
+/// 0. `[]` Mint
+/// 1. ..1+N `[]` Token accounts of this mint
+///
+/// Return data: supply (u64, little-endian) followed by the sum of
+/// the given balances (u64, little-endian).
+///
+/// Read-only. With `strict`, a sum that differs from the supply fails
+/// with `InvariantViolation`, so only pass `strict` when every token
+/// account of the mint is included.
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], strict: bool) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Mint
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // Remaining: Token accounts
+    let token_infos = account_info_iter.as_slice();
+
+    // Validate mint
+    assert_owned_by(mint_info, program_id)?;
+    assert_mint_data_length(mint_info)?;
+
+    let mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
+    if !mint.is_initialized {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Sum balances
+    let mut total: u64 = 0;
+    for (index, token_info) in token_infos.iter().enumerate() {
+        // A repeated account would be counted twice
+        if token_infos[..index]
+            .iter()
+            .any(|earlier| earlier.key == token_info.key)
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        assert_owned_by(token_info, program_id)?;
+        assert_account_data_length(token_info)?;
+
+        let account = Account::unpack_from_slice(&token_info.data.borrow())?;
+        if !account.is_initialized() {
+            return Err(TokenError::UninitializedAccount.into());
+        }
+        assert_account_mint(&account, mint_info.key)?;
+
+        total = checked_add(total, account.amount)?;
+    }
+
+    if strict && total != mint.supply {
+        return Err(TokenError::InvariantViolation.into());
+    }
+
+    // Read-only: report both values, change nothing
+    set_return_data(&encode_supply_reconciliation(mint.supply, total));
+
+    Ok(())
+}
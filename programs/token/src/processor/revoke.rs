@@ -16,7 +16,10 @@ use solana_program::{
 /// Accounts expected:
 /// 0. `[writable]` Source token account
 /// 1. `[signer]` Owner
-/// 2..2+M. `[signer]` Multisig signers (if applicable)
+/// 2. ..2+M `[signer]` Multisig signers (if applicable)
+///
+/// Only the owner can revoke; the delegate itself is rejected with
+/// `InvalidAuthority` like any other non-owner key.
 pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
 
@@ -32,7 +35,7 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     // Validate source account
     assert_owned_by(source_info, program_id)?;
     assert_writable(source_info)?;
-    assert_data_length(source_info, Account::LEN)?;
+    assert_account_data_length(source_info)?;
 
     // Load source account
     let mut source = Account::unpack_from_slice(&source_info.data.borrow())?;
@@ -42,7 +45,7 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         return Err(TokenError::UninitializedAccount.into());
     }
 
-    // Validate owner authority
+    // Validate owner authority (never the delegate)
     validate_authority(
         program_id,
         &source.owner,
@@ -53,6 +56,7 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     // Revoke delegate
     source.delegate = COption::none();
     source.delegated_amount = 0;
+    source.delegate_expiry = COption::none();
 
     // Save source
     source.pack_into_slice(&mut source_info.data.borrow_mut())?;
@@ -0,0 +1,58 @@
+//! RevokeBatch Instruction Processor
+//!
+//! Revokes the delegates of many token accounts with one owner.
+
+use crate::state::COption;
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+/// Process RevokeBatch instruction
+///
+/// Accounts expected:
+/// 0. `[signer]` Owner
+/// 1. ..1+N `[writable]` Token accounts to revoke (N = `count`)
+/// 2. 1+N.. `[signer]` Multisig signers (if applicable)
+///
+/// The owner is checked against every account before any is changed,
+/// so one account with a different owner fails the whole batch.
+/// Frozen accounts are revoked like any other.
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], count: u8) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Owner
+    let owner_info = next_account_info(account_info_iter)?;
+
+    // Accounts 1..1+N: Token accounts
+    let mut token_infos = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        token_infos.push(next_account_info(account_info_iter)?);
+    }
+
+    // Remaining: Multisig signers
+    let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    // Validate every account before revoking anything
+    let mut token_accounts = Vec::with_capacity(token_infos.len());
+    for token_info in &token_infos {
+        let account = load_token_account(token_info, program_id, true)?;
+
+        // Owner only, never the delegate
+        validate_authority(program_id, &account.owner, owner_info, &signer_accounts)?;
+
+        token_accounts.push(account);
+    }
+
+    // Revoke and save each account
+    for (token_info, mut account) in token_infos.into_iter().zip(token_accounts) {
+        account.delegate = COption::none();
+        account.delegated_amount = 0;
+        account.delegate_expiry = COption::none();
+        store_token_account(token_info, &account)?;
+    }
+
+    Ok(())
+}
@@ -9,6 +9,7 @@ use crate::utils::*;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
+    msg,
     pubkey::Pubkey,
 };
 
@@ -17,7 +18,10 @@ use solana_program::{
 /// Accounts expected:
 /// 0. `[writable]` Mint or token account
 /// 1. `[signer]` Current authority
-/// 2..2+M. `[signer]` Multisig signers (if applicable)
+/// 2. ..2+M `[signer]` Multisig signers (if applicable)
+///
+/// With the `cpi-guard` feature, the call fails with `CpiGuardViolation`
+/// when made through CPI.
 pub fn process(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -35,6 +39,10 @@ pub fn process(
     // Remaining: Multisig signers
     let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
 
+    // Authority changes must be top-level instructions (cpi-guard feature)
+    #[cfg(feature = "cpi-guard")]
+    assert_top_level_call()?;
+
     // Validate account
     assert_owned_by(account_info, program_id)?;
     assert_writable(account_info)?;
@@ -68,6 +76,16 @@ pub fn process(
                 new_authority,
             )
         }
+        // A mint-sized account means the mint's own close authority
+        AuthorityType::CloseAccount if Mint::is_valid_len(account_info.data_len()) => {
+            process_set_mint_close_authority(
+                program_id,
+                account_info,
+                authority_info,
+                &signer_accounts,
+                new_authority,
+            )
+        }
         AuthorityType::CloseAccount => {
             process_set_close_authority(
                 program_id,
@@ -87,7 +105,7 @@ fn process_set_mint_authority(
     signer_accounts: &[AccountInfo],
     new_authority: Option<Pubkey>,
 ) -> ProgramResult {
-    assert_data_length(mint_info, Mint::LEN)?;
+    assert_mint_data_length(mint_info)?;
 
     let mut mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
 
@@ -104,6 +122,7 @@ fn process_set_mint_authority(
 
     mint.mint_authority = new_authority.into();
     mint.pack_into_slice(&mut mint_info.data.borrow_mut())?;
+    log_authority_change(AuthorityType::MintTokens, &new_authority);
 
     Ok(())
 }
@@ -115,7 +134,7 @@ fn process_set_freeze_authority(
     signer_accounts: &[AccountInfo],
     new_authority: Option<Pubkey>,
 ) -> ProgramResult {
-    assert_data_length(mint_info, Mint::LEN)?;
+    assert_mint_data_length(mint_info)?;
 
     let mut mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
 
@@ -123,15 +142,17 @@ fn process_set_freeze_authority(
         return Err(TokenError::UninitializedAccount.into());
     }
 
+    // No freeze authority now means none ever
     let current_authority = mint
         .freeze_authority
         .as_ref()
-        .ok_or(TokenError::FreezeAuthorityRequired)?;
+        .ok_or(TokenError::CannotAddFreezeAuthority)?;
 
     validate_authority(program_id, current_authority, authority_info, signer_accounts)?;
 
     mint.freeze_authority = new_authority.into();
     mint.pack_into_slice(&mut mint_info.data.borrow_mut())?;
+    log_authority_change(AuthorityType::FreezeAccount, &new_authority);
 
     Ok(())
 }
@@ -143,7 +164,7 @@ fn process_set_account_owner(
     signer_accounts: &[AccountInfo],
     new_authority: Option<Pubkey>,
 ) -> ProgramResult {
-    assert_data_length(account_info, Account::LEN)?;
+    assert_account_data_length(account_info)?;
 
     let mut account = Account::unpack_from_slice(&account_info.data.borrow())?;
 
@@ -151,6 +172,9 @@ fn process_set_account_owner(
         return Err(TokenError::UninitializedAccount.into());
     }
 
+    // A frozen account's owner can't change
+    assert_not_frozen(&account)?;
+
     validate_authority(program_id, &account.owner, authority_info, signer_accounts)?;
 
     // Owner cannot be set to None
@@ -161,8 +185,10 @@ fn process_set_account_owner(
     // Clear delegate when owner changes
     account.delegate = COption::none();
     account.delegated_amount = 0;
+    account.delegate_expiry = COption::none();
 
     account.pack_into_slice(&mut account_info.data.borrow_mut())?;
+    log_authority_change(AuthorityType::AccountOwner, &new_authority);
 
     Ok(())
 }
@@ -174,7 +200,7 @@ fn process_set_close_authority(
     signer_accounts: &[AccountInfo],
     new_authority: Option<Pubkey>,
 ) -> ProgramResult {
-    assert_data_length(account_info, Account::LEN)?;
+    assert_account_data_length(account_info)?;
 
     let mut account = Account::unpack_from_slice(&account_info.data.borrow())?;
 
@@ -192,6 +218,50 @@ fn process_set_close_authority(
 
     account.close_authority = new_authority.into();
     account.pack_into_slice(&mut account_info.data.borrow_mut())?;
+    log_authority_change(AuthorityType::CloseAccount, &new_authority);
+
+    Ok(())
+}
+
+fn process_set_mint_close_authority(
+    program_id: &Pubkey,
+    mint_info: &AccountInfo,
+    authority_info: &AccountInfo,
+    signer_accounts: &[AccountInfo],
+    new_authority: Option<Pubkey>,
+) -> ProgramResult {
+    let mut mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
+
+    if !mint.is_initialized {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Until one is set, the mint authority may set the close authority
+    let current_authority = mint
+        .close_authority
+        .as_ref()
+        .or(mint.mint_authority.as_ref())
+        .ok_or(TokenError::InvalidAuthority)?;
+
+    validate_authority(program_id, current_authority, authority_info, signer_accounts)?;
+
+    // Packing fails for a base 82-byte mint, which has nowhere to store it
+    mint.close_authority = new_authority.into();
+    mint.pack_into_slice(&mut mint_info.data.borrow_mut())?;
+    log_authority_change(AuthorityType::CloseAccount, &new_authority);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Log which authority changed and whether it was set or removed.
+///
+/// The new key isn't logged; "removed" is what indexers watch for
+/// (e.g. a mint authority revoked for good).
+fn log_authority_change(authority_type: AuthorityType, new_authority: &Option<Pubkey>) {
+    let change = if new_authority.is_some() {
+        "set"
+    } else {
+        "removed"
+    };
+    msg!("SetAuthority: {:?} {}", authority_type, change);
+}
@@ -0,0 +1,56 @@
+//! SetMemoRequired Instruction Processor
+//!
+//! Turns the inbound-transfer memo requirement on or off for a token account.
+
+use crate::error::TokenError;
+use crate::state::{Account, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+/// Process SetMemoRequired instruction
+///
+/// Accounts expected:
+/// 0. `[writable]` Token account (must be `Account::EXTENDED_LEN` bytes)
+/// 1. `[signer]` Owner
+/// 2. ..2+M `[signer]` Multisig signers (if applicable)
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], required: bool) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Token account
+    let account_info = next_account_info(account_info_iter)?;
+
+    // Account 1: Owner
+    let owner_info = next_account_info(account_info_iter)?;
+
+    // Remaining: Multisig signers
+    let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    // Validate account (base accounts have no room for the flag)
+    assert_owned_by(account_info, program_id)?;
+    assert_writable(account_info)?;
+    assert_data_length(account_info, Account::EXTENDED_LEN)?;
+
+    // Load and verify account is initialized
+    let mut account = Account::unpack_from_slice(&account_info.data.borrow())?;
+    if !account.is_initialized() {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Validate owner authority (only owner, not delegate)
+    validate_authority(
+        program_id,
+        &account.owner,
+        owner_info,
+        &signer_accounts,
+    )?;
+
+    // Update flag
+    account.memo_required = required;
+    account.pack_into_slice(&mut account_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! SetNonTransferable Instruction Processor
+//!
+//! Flags a mint so its tokens can never be transferred.
+
+use crate::error::TokenError;
+use crate::state::{Mint, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+/// Process SetNonTransferable instruction
+///
+/// Accounts expected:
+/// 0. `[writable]` Mint (must be `Mint::EXTENDED_LEN` bytes)
+/// 1. `[signer]` Mint authority
+/// 2. ..2+M `[signer]` Multisig signers (if applicable)
+///
+/// The flag is permanent. Calling this on an already flagged mint
+/// is a no-op.
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Mint
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // Account 1: Mint authority
+    let authority_info = next_account_info(account_info_iter)?;
+
+    // Remaining: Multisig signers
+    let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    // Validate mint (base mints have no room for the flag)
+    assert_owned_by(mint_info, program_id)?;
+    assert_writable(mint_info)?;
+    assert_data_length(mint_info, Mint::EXTENDED_LEN)?;
+
+    // Load and verify mint is initialized
+    let mut mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
+    if !mint.is_initialized {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Validate mint authority
+    let mint_authority = mint
+        .mint_authority
+        .as_ref()
+        .ok_or(TokenError::MintAuthorityRequired)?;
+
+    validate_authority(
+        program_id,
+        mint_authority,
+        authority_info,
+        &signer_accounts,
+    )?;
+
+    // Flag the mint
+    mint.non_transferable = true;
+    mint.pack_into_slice(&mut mint_info.data.borrow_mut())?;
+
+    Ok(())
+}
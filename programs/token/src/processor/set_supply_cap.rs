@@ -0,0 +1,74 @@
+//! SetSupplyCap Instruction Processor
+//!
+//! Sets or clears the maximum supply of a mint.
+
+use crate::error::TokenError;
+use crate::state::{COption, Mint, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+/// Process SetSupplyCap instruction
+///
+/// Accounts expected:
+/// 0. `[writable]` Mint (must be `Mint::EXTENDED_LEN` bytes)
+/// 1. `[signer]` Mint authority
+/// 2. ..2+M `[signer]` Multisig signers (if applicable)
+///
+/// A cap below the current supply is rejected with
+/// `SupplyCapExceeded`. `None` removes the cap.
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], cap: Option<u64>) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Mint
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // Account 1: Mint authority
+    let authority_info = next_account_info(account_info_iter)?;
+
+    // Remaining: Multisig signers
+    let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    // Validate mint (base mints have no room for the cap)
+    assert_owned_by(mint_info, program_id)?;
+    assert_writable(mint_info)?;
+    assert_data_length(mint_info, Mint::EXTENDED_LEN)?;
+
+    // Load and verify mint is initialized
+    let mut mint = Mint::unpack_from_slice(&mint_info.data.borrow())?;
+    if !mint.is_initialized {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    // Validate mint authority
+    let mint_authority = mint
+        .mint_authority
+        .as_ref()
+        .ok_or(TokenError::MintAuthorityRequired)?;
+
+    validate_authority(
+        program_id,
+        mint_authority,
+        authority_info,
+        &signer_accounts,
+    )?;
+
+    // The cap can't already be exceeded
+    if let Some(cap) = cap {
+        if mint.supply > cap {
+            return Err(TokenError::SupplyCapExceeded.into());
+        }
+    }
+
+    // Store the cap
+    mint.supply_cap = match cap {
+        Some(cap) => COption::some(cap),
+        None => COption::none(),
+    };
+    mint.pack_into_slice(&mut mint_info.data.borrow_mut())?;
+
+    Ok(())
+}
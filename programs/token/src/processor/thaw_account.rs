@@ -3,7 +3,7 @@
 //! Thaws (unfreezes) a frozen token account.
 
 use crate::error::TokenError;
-use crate::state::{Account, AccountState, Mint, Pack};
+use crate::state::{Account, Mint, Pack};
 use crate::utils::*;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -17,7 +17,7 @@ use solana_program::{
 /// 0. `[writable]` Token account to thaw
 /// 1. `[]` Mint
 /// 2. `[signer]` Freeze authority
-/// 3..3+M. `[signer]` Multisig signers (if applicable)
+/// 3. ..3+M `[signer]` Multisig signers (if applicable)
 pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
 
@@ -36,11 +36,11 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     // Validate token account
     assert_owned_by(account_info, program_id)?;
     assert_writable(account_info)?;
-    assert_data_length(account_info, Account::LEN)?;
+    assert_account_data_length(account_info)?;
 
     // Validate mint
     assert_owned_by(mint_info, program_id)?;
-    assert_data_length(mint_info, Mint::LEN)?;
+    assert_mint_data_length(mint_info)?;
 
     // Load states
     let mut account = Account::unpack_from_slice(&account_info.data.borrow())?;
@@ -55,9 +55,7 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     }
 
     // Validate account is for this mint
-    if account.mint != *mint_info.key {
-        return Err(TokenError::MintMismatch.into());
-    }
+    assert_account_mint(&account, mint_info.key)?;
 
     // Get freeze authority
     let freeze_authority = mint
@@ -73,8 +71,8 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         &signer_accounts,
     )?;
 
-    // Thaw the account
-    account.state = AccountState::Initialized;
+    // Thaw the account (rejects an account that isn't frozen)
+    account.thaw()?;
 
     // Save account
     account.pack_into_slice(&mut account_info.data.borrow_mut())?;
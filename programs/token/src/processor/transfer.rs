@@ -3,11 +3,14 @@
 //! Transfers tokens from one account to another.
 
 use crate::error::TokenError;
-use crate::state::{Account, COption, Pack};
+use crate::return_data::encode_transfer_balance;
+use crate::state::COption;
 use crate::utils::*;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
+    program::set_return_data,
+    program_error::ProgramError,
     pubkey::Pubkey,
 };
 
@@ -17,12 +20,56 @@ use solana_program::{
 /// 0. `[writable]` Source token account
 /// 1. `[writable]` Destination token account
 /// 2. `[signer]` Owner or delegate
-/// 3..3+M. `[signer]` Multisig signers (if applicable)
+/// 3. `[]` The source's mint
+/// 4. ..4+M `[signer]` Multisig signers (if applicable)
+///
+/// If the mint is non-transferable, the transfer fails with
+/// `NonTransferable`. If the mint has a transfer hook, the hook program
+/// must be passed after the mint; it is invoked after the balances are
+/// updated and the transfer fails with `TransferHookFailed` if it errors.
+///
+/// If the destination requires memos, the instructions sysvar must also
+/// be passed after the authority, and a Memo instruction must run
+/// earlier in the transaction, or this fails with `MemoRequired`.
+///
+/// If the source's delegate approval has an expiry (ApproveWithExpiry),
+/// a delegate must also pass the Clock sysvar after the authority. Past
+/// the expiry the transfer fails with `DelegateExpired`. Owners and
+/// approvals without an expiry need no extra account.
+///
+/// Between wrapped SOL accounts (the mints match, so both are native or
+/// neither is), `amount` lamports move along with the tokens. A normal
+/// token transfer moves no lamports, but a wrapped SOL balance *is* the
+/// lamports above the rent reserve, so moving only `amount` would leave
+/// both accounts out of sync with what they actually hold.
 pub fn process(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     amount: u64,
 ) -> ProgramResult {
+    transfer(program_id, accounts, amount).map(|_| ())
+}
+
+/// Process TransferWithReturnData instruction
+///
+/// Same accounts as Transfer. Afterwards, the source's new `amount`
+/// is written as return data (u64, little-endian).
+pub fn process_with_return_data(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let new_source_amount = transfer(program_id, accounts, amount)?;
+    set_return_data(&encode_transfer_balance(new_source_amount));
+    Ok(())
+}
+
+/// Shared transfer logic. Returns the source's new balance.
+fn transfer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> Result<u64, ProgramError> {
     let account_info_iter = &mut accounts.iter();
 
     // Account 0: Source
@@ -34,36 +81,21 @@ pub fn process(
     // Account 2: Authority
     let authority_info = next_account_info(account_info_iter)?;
 
+    // Account 3: Mint
+    let mint_info = next_account_info(account_info_iter)?;
+
     // Remaining: Multisig signers
     let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
 
-    // Validate source
-    assert_owned_by(source_info, program_id)?;
-    assert_writable(source_info)?;
-    assert_data_length(source_info, Account::LEN)?;
-
-    // Validate destination
-    assert_owned_by(dest_info, program_id)?;
-    assert_writable(dest_info)?;
-    assert_data_length(dest_info, Account::LEN)?;
+    // Load and validate source and destination
+    let mut source = load_token_account(source_info, program_id, true)?;
+    let mut dest = load_token_account(dest_info, program_id, true)?;
 
     // Prevent self-transfer
-    if source_info.key == dest_info.key {
+    if is_self_transfer(source_info.key, dest_info.key) {
         return Err(TokenError::SelfTransfer.into());
     }
 
-    // Load states
-    let mut source = Account::unpack_from_slice(&source_info.data.borrow())?;
-    let mut dest = Account::unpack_from_slice(&dest_info.data.borrow())?;
-
-    // Validate initialization
-    if !source.is_initialized() {
-        return Err(TokenError::UninitializedAccount.into());
-    }
-    if !dest.is_initialized() {
-        return Err(TokenError::UninitializedAccount.into());
-    }
-
     // Validate not frozen
     if source.is_frozen() {
         return Err(TokenError::AccountFrozen.into());
@@ -73,9 +105,13 @@ pub fn process(
     }
 
     // Validate mints match
-    if source.mint != dest.mint {
-        return Err(TokenError::MintMismatch.into());
-    }
+    assert_account_mint(&dest, &source.mint)?;
+
+    // Validate mint allows transfers
+    let mint = load_transfer_mint(mint_info, program_id, &source.mint)?;
+
+    // Validate memo (when the destination requires one)
+    assert_memo_if_required(&dest, &signer_accounts)?;
 
     // Validate sufficient funds
     if source.amount < amount {
@@ -84,7 +120,7 @@ pub fn process(
 
     // Validate authority
     let delegate_pubkey: Option<&Pubkey> = source.delegate.as_ref();
-    let used_delegate = validate_owner_or_delegate(
+    let authority_kind = validate_owner_or_delegate_kind(
         program_id,
         &source.owner,
         delegate_pubkey,
@@ -93,23 +129,52 @@ pub fn process(
     )?;
 
     // Handle delegate allowance
-    if used_delegate {
-        if source.delegated_amount < amount {
-            return Err(TokenError::InsufficientDelegatedAmount.into());
-        }
-        source.delegated_amount = checked_sub(source.delegated_amount, amount)?;
-        if source.delegated_amount == 0 {
-            source.delegate = COption::none();
+    match authority_kind {
+        AuthorityKind::Owner => {}
+        AuthorityKind::Delegate => {
+            assert_delegate_not_expired(&source, &signer_accounts)?;
+            if source.delegated_amount < amount {
+                return Err(TokenError::InsufficientDelegatedAmount.into());
+            }
+            source.delegated_amount = checked_sub(source.delegated_amount, amount)?;
+            if source.delegated_amount == 0 {
+                source.delegate = COption::none();
+                source.delegate_expiry = COption::none();
+            }
         }
     }
 
-    // Transfer tokens
-    source.amount = checked_sub(source.amount, amount)?;
-    dest.amount = checked_add(dest.amount, amount)?;
+    // Transfer tokens. Both balances are computed before either is
+    // assigned or packed, so an overflow on the destination can never
+    // leave the source debited.
+    let new_source_amount = checked_sub(source.amount, amount)?;
+    let new_dest_amount = checked_add(dest.amount, amount)?;
+    source.amount = new_source_amount;
+    dest.amount = new_dest_amount;
+
+    // Wrapped SOL: the tokens are the lamports above the rent reserve,
+    // so move those too, keeping `amount == lamports - reserve` on both
+    if source.is_native() {
+        move_native_lamports(source_info, dest_info, amount)?;
+    }
 
     // Save states
-    source.pack_into_slice(&mut source_info.data.borrow_mut())?;
-    dest.pack_into_slice(&mut dest_info.data.borrow_mut())?;
+    store_token_account(source_info, &source)?;
+    store_token_account(dest_info, &dest)?;
+
+    #[cfg(feature = "events")]
+    events::emit_transfer(source_info.key, dest_info.key, amount);
+
+    // Run the mint's transfer hook on the post-transfer state
+    invoke_transfer_hook(
+        &mint,
+        source_info,
+        dest_info,
+        mint_info,
+        authority_info,
+        &signer_accounts,
+        amount,
+    )?;
 
-    Ok(())
+    Ok(source.amount)
 }
\ No newline at end of file
@@ -0,0 +1,163 @@
+//! TransferBatch Instruction Processor
+//!
+//! Transfers tokens from one source account to many destinations.
+
+use crate::error::TokenError;
+use crate::state::{Account, COption, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+/// Process TransferBatch instruction
+///
+/// Accounts expected:
+/// 0. `[writable]` Source token account
+/// 1. `[signer]` Owner or delegate
+/// 2. `[]` The source's mint
+/// 3. ..3+N `[writable]` Destination token accounts (one per amount)
+/// 4. 3+N.. `[signer]` Multisig signers (if applicable)
+///
+/// Each leg gets the same mint and destination checks as Transfer: a
+/// non-transferable mint fails with `NonTransferable`, a destination
+/// requiring memos needs the instructions sysvar after the destinations,
+/// and a hooked mint needs its hook program there too, which runs once
+/// per leg after all balances are updated.
+///
+/// Between wrapped SOL accounts, each leg also moves its `amount` in
+/// lamports, as Transfer does.
+///
+/// A delegate whose approval expires must also pass the Clock sysvar
+/// after the destinations; past the expiry this fails with `DelegateExpired`.
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amounts: &[u64],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Source
+    let source_info = next_account_info(account_info_iter)?;
+
+    // Account 1: Authority
+    let authority_info = next_account_info(account_info_iter)?;
+
+    // Account 2: Mint
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // Accounts 3..3+N: Destinations
+    let mut dest_infos = Vec::with_capacity(amounts.len());
+    for _ in amounts {
+        dest_infos.push(next_account_info(account_info_iter)?);
+    }
+
+    // Remaining: Multisig signers
+    let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    // Validate source
+    assert_owned_by(source_info, program_id)?;
+    assert_writable(source_info)?;
+    assert_account_data_length(source_info)?;
+
+    let mut source = Account::unpack_from_slice(&source_info.data.borrow())?;
+    if !source.is_initialized() {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+    if source.is_frozen() {
+        return Err(TokenError::AccountFrozen.into());
+    }
+
+    // Validate every destination before moving anything
+    let mut dests = Vec::with_capacity(dest_infos.len());
+    let mut total: u64 = 0;
+    for (dest_info, &amount) in dest_infos.iter().zip(amounts) {
+        assert_owned_by(dest_info, program_id)?;
+        assert_writable(dest_info)?;
+        assert_account_data_length(dest_info)?;
+
+        if is_self_transfer(source_info.key, dest_info.key) {
+            return Err(TokenError::SelfTransfer.into());
+        }
+
+        let dest = Account::unpack_from_slice(&dest_info.data.borrow())?;
+        if !dest.is_initialized() {
+            return Err(TokenError::UninitializedAccount.into());
+        }
+        if dest.is_frozen() {
+            return Err(TokenError::AccountFrozen.into());
+        }
+        assert_memo_if_required(&dest, &signer_accounts)?;
+        dests.push(dest);
+
+        total = checked_add(total, amount)?;
+    }
+    assert_same_mint(&dests, &source.mint)?;
+
+    // Validate mint allows transfers
+    let mint = load_transfer_mint(mint_info, program_id, &source.mint)?;
+
+    // Validate sufficient funds for the whole batch
+    if source.amount < total {
+        return Err(TokenError::InsufficientFunds.into());
+    }
+
+    // Validate authority
+    let delegate_pubkey: Option<&Pubkey> = source.delegate.as_ref();
+    let authority_kind = validate_owner_or_delegate_kind(
+        program_id,
+        &source.owner,
+        delegate_pubkey,
+        authority_info,
+        &signer_accounts,
+    )?;
+
+    // Handle delegate allowance (charged once, for the total)
+    match authority_kind {
+        AuthorityKind::Owner => {}
+        AuthorityKind::Delegate => {
+            assert_delegate_not_expired(&source, &signer_accounts)?;
+            if source.delegated_amount < total {
+                return Err(TokenError::InsufficientDelegatedAmount.into());
+            }
+            source.delegated_amount = checked_sub(source.delegated_amount, total)?;
+            if source.delegated_amount == 0 {
+                source.delegate = COption::none();
+                source.delegate_expiry = COption::none();
+            }
+        }
+    }
+
+    // Debit source
+    source.amount = checked_sub(source.amount, total)?;
+    source.pack_into_slice(&mut source_info.data.borrow_mut())?;
+
+    // Credit destinations. Each one is re-read so a destination
+    // listed twice receives both amounts.
+    for (dest_info, &amount) in dest_infos.iter().zip(amounts) {
+        let mut dest = Account::unpack_from_slice(&dest_info.data.borrow())?;
+        dest.amount = checked_add(dest.amount, amount)?;
+        dest.pack_into_slice(&mut dest_info.data.borrow_mut())?;
+
+        // Wrapped SOL: the lamports above the reserve move too
+        if source.is_native() {
+            move_native_lamports(source_info, dest_info, amount)?;
+        }
+    }
+
+    // Run the mint's transfer hook for each leg
+    for (dest_info, &amount) in dest_infos.iter().zip(amounts) {
+        invoke_transfer_hook(
+            &mint,
+            source_info,
+            dest_info,
+            mint_info,
+            authority_info,
+            &signer_accounts,
+            amount,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+//! TransferWithFee Instruction Processor
+//!
+//! Transfers tokens while skimming a basis-point fee to a fee account.
+
+use crate::error::TokenError;
+use crate::state::{Account, COption, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Basis points in 100%.
+const MAX_BASIS_POINTS: u64 = 10_000;
+
+/// Process TransferWithFee instruction
+///
+/// Accounts expected:
+/// 0. `[writable]` Source token account
+/// 1. `[writable]` Destination token account
+/// 2. `[writable]` Fee destination token account
+/// 3. `[signer]` Owner or delegate
+/// 4. `[]` The source's mint
+/// 5. ..5+M `[signer]` Multisig signers (if applicable)
+///
+/// Both the destination and the fee destination get Transfer's checks:
+/// a non-transferable mint fails with `NonTransferable`, a receiver
+/// requiring memos needs the instructions sysvar after the mint, and a
+/// hooked mint needs its hook program there too, which runs once for
+/// each receiver after the balances are updated.
+///
+/// A delegate whose approval expires must also pass the Clock sysvar
+/// after the authority; past the expiry this fails with `DelegateExpired`.
+///
+/// `amount` leaves the source; the destination receives
+/// `amount - fee` and the fee destination receives
+/// `fee = amount * fee_basis_points / 10_000` (rounded down). Between
+/// wrapped SOL accounts, the same split is moved in lamports, as
+/// Transfer does.
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    fee_basis_points: u16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Source
+    let source_info = next_account_info(account_info_iter)?;
+
+    // Account 1: Destination
+    let dest_info = next_account_info(account_info_iter)?;
+
+    // Account 2: Fee destination
+    let fee_info = next_account_info(account_info_iter)?;
+
+    // Account 3: Authority
+    let authority_info = next_account_info(account_info_iter)?;
+
+    // Account 4: Mint
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // Remaining: Multisig signers
+    let signer_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    // Validate fee rate
+    let fee_basis_points = fee_basis_points as u64;
+    if fee_basis_points > MAX_BASIS_POINTS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Validate source
+    assert_owned_by(source_info, program_id)?;
+    assert_writable(source_info)?;
+    assert_account_data_length(source_info)?;
+
+    let mut source = Account::unpack_from_slice(&source_info.data.borrow())?;
+    if !source.is_initialized() {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+    if source.is_frozen() {
+        return Err(TokenError::AccountFrozen.into());
+    }
+
+    // Validate destination and fee destination
+    for receiver_info in [dest_info, fee_info] {
+        assert_owned_by(receiver_info, program_id)?;
+        assert_writable(receiver_info)?;
+        assert_account_data_length(receiver_info)?;
+
+        if is_self_transfer(source_info.key, receiver_info.key) {
+            return Err(TokenError::SelfTransfer.into());
+        }
+
+        let receiver = Account::unpack_from_slice(&receiver_info.data.borrow())?;
+        if !receiver.is_initialized() {
+            return Err(TokenError::UninitializedAccount.into());
+        }
+        if receiver.is_frozen() {
+            return Err(TokenError::AccountFrozen.into());
+        }
+        assert_account_mint(&receiver, &source.mint)?;
+        assert_memo_if_required(&receiver, &signer_accounts)?;
+    }
+
+    // Validate mint allows transfers
+    let mint = load_transfer_mint(mint_info, program_id, &source.mint)?;
+
+    // Validate sufficient funds
+    if source.amount < amount {
+        return Err(TokenError::InsufficientFunds.into());
+    }
+
+    // Validate authority
+    let delegate_pubkey: Option<&Pubkey> = source.delegate.as_ref();
+    let authority_kind = validate_owner_or_delegate_kind(
+        program_id,
+        &source.owner,
+        delegate_pubkey,
+        authority_info,
+        &signer_accounts,
+    )?;
+
+    // Handle delegate allowance (charged for the full amount)
+    match authority_kind {
+        AuthorityKind::Owner => {}
+        AuthorityKind::Delegate => {
+            assert_delegate_not_expired(&source, &signer_accounts)?;
+            if source.delegated_amount < amount {
+                return Err(TokenError::InsufficientDelegatedAmount.into());
+            }
+            source.delegated_amount = checked_sub(source.delegated_amount, amount)?;
+            if source.delegated_amount == 0 {
+                source.delegate = COption::none();
+                source.delegate_expiry = COption::none();
+            }
+        }
+    }
+
+    // Split amount. `amount * fee_basis_points` is taken in u128, since
+    // it overflows u64 for any amount above u64::MAX / 10_000; the fee
+    // itself never exceeds `amount`, so it fits back into a u64.
+    let fee = u64::try_from(
+        amount as u128 * fee_basis_points as u128 / MAX_BASIS_POINTS as u128,
+    )
+    .map_err(|_| TokenError::Overflow)?;
+    let net_amount = checked_sub(amount, fee)?;
+
+    // Debit source
+    source.amount = checked_sub(source.amount, amount)?;
+    source.pack_into_slice(&mut source_info.data.borrow_mut())?;
+
+    // Credit receivers. Each one is re-read so a fee destination that
+    // is also the destination receives both parts.
+    for (receiver_info, credit) in [(dest_info, net_amount), (fee_info, fee)] {
+        let mut receiver = Account::unpack_from_slice(&receiver_info.data.borrow())?;
+        receiver.amount = checked_add(receiver.amount, credit)?;
+        receiver.pack_into_slice(&mut receiver_info.data.borrow_mut())?;
+
+        // Wrapped SOL: the lamports above the reserve move too
+        if source.is_native() {
+            move_native_lamports(source_info, receiver_info, credit)?;
+        }
+    }
+
+    // Run the mint's transfer hook for each receiver
+    for (receiver_info, credit) in [(dest_info, net_amount), (fee_info, fee)] {
+        invoke_transfer_hook(
+            &mint,
+            source_info,
+            receiver_info,
+            mint_info,
+            authority_info,
+            &signer_accounts,
+            credit,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! VerifyAccount Instruction Processor
+//!
+//! Checks a token account against an expected mint and owner and
+//! reports the outcome as a status code in return data.
+
+use crate::return_data::encode_verify_status;
+use crate::state::{Account, AccountState, Pack};
+use crate::utils::*;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    pubkey::Pubkey,
+};
+
+/// Account matches both the expected mint and owner
+pub const VERIFY_OK: u8 = 0;
+
+/// Account belongs to a different mint
+pub const VERIFY_MINT_MISMATCH: u8 = 1;
+
+/// Account belongs to a different owner
+pub const VERIFY_OWNER_MISMATCH: u8 = 2;
+
+/// Account is uninitialized or frozen
+pub const VERIFY_UNUSABLE: u8 = 3;
+
+/// Process VerifyAccount instruction
+///
+/// Accounts expected:
+/// 0. `[]` Token account
+///
+/// Return data: status (u8), one of the `VERIFY_*` codes. Checks run
+/// in code order from highest to lowest: an unusable account reports
+/// 3 even if its mint and owner are also wrong, and a mint mismatch
+/// reports 1 even if the owner is also wrong.
+///
+/// A mismatch is a result, not an error; the instruction only fails
+/// when the account isn't a token account at all.
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    expected_mint: Pubkey,
+    expected_owner: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    // Account 0: Token account
+    let account_info = next_account_info(account_info_iter)?;
+
+    // Validate token account
+    assert_owned_by(account_info, program_id)?;
+    assert_account_data_length(account_info)?;
+
+    // Load without rejecting uninitialized state; that's a status here
+    let account = Account::unpack_unchecked(&account_info.data.borrow())?;
+
+    let status = if account.state != AccountState::Initialized {
+        VERIFY_UNUSABLE
+    } else if account.mint != expected_mint {
+        VERIFY_MINT_MISMATCH
+    } else if account.owner != expected_owner {
+        VERIFY_OWNER_MISMATCH
+    } else {
+        VERIFY_OK
+    };
+
+    // Read-only: report status, change nothing
+    set_return_data(&encode_verify_status(status));
+
+    Ok(())
+}
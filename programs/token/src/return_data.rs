@@ -0,0 +1,335 @@
+//! Return Data Encoding
+//!
+//! Query instructions answer through `set_return_data` instead of
+//! writing to an account. The processors build that output with the
+//! encoders here; clients read it back with the decoders, which are
+//! only compiled with the `client` feature.
+//!
+//! # Formats
+//!
+//! | Value | Encoding |
+//! |-------|----------|
+//! | supply | u64, little-endian (8 bytes) |
+//! | account balance | u64, little-endian (8 bytes) |
+//! | supply reconciliation | supply then balance sum, u64 little-endian each (16 bytes) |
+//! | transfer balance | source's new balance, u64 little-endian (8 bytes) |
+//! | account verification | status code, u8 (1 byte) |
+//! | mint info | supply u64 little-endian, then decimals and three flag bytes (12 bytes) |
+//! | multisig info | m, n, then n signer pubkeys (2 + 32*n bytes) |
+//!
+//! # Example
+//!
+//! ```ignore
+//! let simulation = banks_client.simulate_transaction(tx).await?;
+//! let data = simulation.simulation_details.unwrap().return_data.unwrap().data;
+//! let supply = return_data::decode_supply(&data)?;
+//! ```
+
+#[cfg(feature = "client")]
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+/// Compact mint summary (as written by GetMintInfo).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MintInfo {
+    /// Current supply
+    pub supply: u64,
+
+    /// Decimal places
+    pub decimals: u8,
+
+    /// Whether the mint is initialized
+    pub is_initialized: bool,
+
+    /// Whether a mint authority is set
+    pub has_mint_authority: bool,
+
+    /// Whether a freeze authority is set
+    pub has_freeze_authority: bool,
+}
+
+/// Multisig configuration (as written by GetMultisigInfo).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MultisigInfo {
+    /// Signatures required
+    pub m: u8,
+
+    /// The valid signers; N is `signers.len()`
+    pub signers: Vec<Pubkey>,
+}
+
+// =============================================================================
+// ENCODERS
+// =============================================================================
+
+/// Encode a mint supply (as written by GetSupply).
+pub fn encode_supply(supply: u64) -> [u8; 8] {
+    supply.to_le_bytes()
+}
+
+/// Encode a token account balance (as written by GetAccountBalance).
+pub fn encode_account_balance(amount: u64) -> [u8; 8] {
+    amount.to_le_bytes()
+}
+
+/// Encode a supply reconciliation (as written by ReconcileSupply).
+pub fn encode_supply_reconciliation(supply: u64, balance_sum: u64) -> [u8; 16] {
+    let mut data = [0u8; 16];
+    data[..8].copy_from_slice(&supply.to_le_bytes());
+    data[8..].copy_from_slice(&balance_sum.to_le_bytes());
+    data
+}
+
+/// Encode the source's new balance (as written by TransferWithReturnData).
+pub fn encode_transfer_balance(new_source_amount: u64) -> [u8; 8] {
+    new_source_amount.to_le_bytes()
+}
+
+/// Encode an account verification status (as written by VerifyAccount).
+pub fn encode_verify_status(status: u8) -> [u8; 1] {
+    [status]
+}
+
+/// Encode a mint summary (as written by GetMintInfo).
+pub fn encode_mint_info(info: &MintInfo) -> [u8; 12] {
+    let mut data = [0u8; 12];
+    data[..8].copy_from_slice(&info.supply.to_le_bytes());
+    data[8] = info.decimals;
+    data[9] = info.is_initialized as u8;
+    data[10] = info.has_mint_authority as u8;
+    data[11] = info.has_freeze_authority as u8;
+    data
+}
+
+/// Encode a multisig configuration (as written by GetMultisigInfo).
+pub fn encode_multisig_info(info: &MultisigInfo) -> Vec<u8> {
+    let mut data = Vec::with_capacity(2 + 32 * info.signers.len());
+    data.push(info.m);
+    data.push(info.signers.len() as u8);
+    for signer in &info.signers {
+        data.extend_from_slice(signer.as_ref());
+    }
+    data
+}
+
+// =============================================================================
+// DECODERS (client feature)
+// =============================================================================
+
+/// Decode a mint supply.
+///
+/// # Errors
+///
+/// * `InvalidArgument` - Data is not exactly 8 bytes
+#[cfg(feature = "client")]
+pub fn decode_supply(data: &[u8]) -> Result<u64, ProgramError> {
+    decode_u64(data)
+}
+
+/// Decode a token account balance.
+///
+/// # Errors
+///
+/// * `InvalidArgument` - Data is not exactly 8 bytes
+#[cfg(feature = "client")]
+pub fn decode_account_balance(data: &[u8]) -> Result<u64, ProgramError> {
+    decode_u64(data)
+}
+
+/// Decode a supply reconciliation into `(supply, balance_sum)`.
+///
+/// # Errors
+///
+/// * `InvalidArgument` - Data is not exactly 16 bytes
+#[cfg(feature = "client")]
+pub fn decode_supply_reconciliation(data: &[u8]) -> Result<(u64, u64), ProgramError> {
+    if data.len() != 16 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok((decode_u64(&data[..8])?, decode_u64(&data[8..])?))
+}
+
+/// Decode the source's new balance after a transfer.
+///
+/// # Errors
+///
+/// * `InvalidArgument` - Data is not exactly 8 bytes
+#[cfg(feature = "client")]
+pub fn decode_transfer_balance(data: &[u8]) -> Result<u64, ProgramError> {
+    decode_u64(data)
+}
+
+/// Decode an account verification status.
+///
+/// # Errors
+///
+/// * `InvalidArgument` - Data is not exactly 1 byte
+#[cfg(feature = "client")]
+pub fn decode_verify_status(data: &[u8]) -> Result<u8, ProgramError> {
+    match data {
+        [status] => Ok(*status),
+        _ => Err(ProgramError::InvalidArgument),
+    }
+}
+
+/// Decode a mint summary.
+///
+/// # Errors
+///
+/// * `InvalidArgument` - Data is not exactly 12 bytes, or a flag byte
+///   is not 0 or 1
+#[cfg(feature = "client")]
+pub fn decode_mint_info(data: &[u8]) -> Result<MintInfo, ProgramError> {
+    if data.len() != 12 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(MintInfo {
+        supply: decode_u64(&data[..8])?,
+        decimals: data[8],
+        is_initialized: decode_flag(data[9])?,
+        has_mint_authority: decode_flag(data[10])?,
+        has_freeze_authority: decode_flag(data[11])?,
+    })
+}
+
+/// Decode a multisig configuration.
+///
+/// # Errors
+///
+/// * `InvalidArgument` - Data is shorter than 2 bytes, or its length
+///   doesn't match the signer count in byte 1
+#[cfg(feature = "client")]
+pub fn decode_multisig_info(data: &[u8]) -> Result<MultisigInfo, ProgramError> {
+    let (&m, rest) = data.split_first().ok_or(ProgramError::InvalidArgument)?;
+    let (&n, keys) = rest.split_first().ok_or(ProgramError::InvalidArgument)?;
+    if keys.len() != 32 * n as usize {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let signers = keys
+        .chunks_exact(32)
+        .map(|key| Pubkey::try_from(key).map_err(|_| ProgramError::InvalidArgument))
+        .collect::<Result<_, _>>()?;
+    Ok(MultisigInfo { m, signers })
+}
+
+/// Decode a 0/1 flag byte.
+#[cfg(feature = "client")]
+fn decode_flag(byte: u8) -> Result<bool, ProgramError> {
+    match byte {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(ProgramError::InvalidArgument),
+    }
+}
+
+/// Decode a little-endian u64 that must fill the whole buffer.
+#[cfg(feature = "client")]
+fn decode_u64(data: &[u8]) -> Result<u64, ProgramError> {
+    let bytes: [u8; 8] = data.try_into().map_err(|_| ProgramError::InvalidArgument)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supply_roundtrip() {
+        for supply in [0, 1, 1_234_567, u64::MAX] {
+            assert_eq!(decode_supply(&encode_supply(supply)).unwrap(), supply);
+        }
+    }
+
+    #[test]
+    fn test_account_balance_roundtrip() {
+        for amount in [0, 1, 1_000_000_000, u64::MAX] {
+            assert_eq!(
+                decode_account_balance(&encode_account_balance(amount)).unwrap(),
+                amount
+            );
+        }
+    }
+
+    #[test]
+    fn test_supply_reconciliation_roundtrip() {
+        for (supply, sum) in [(0, 0), (100, 100), (100, 99), (u64::MAX, 1)] {
+            let encoded = encode_supply_reconciliation(supply, sum);
+            assert_eq!(decode_supply_reconciliation(&encoded).unwrap(), (supply, sum));
+        }
+        assert_eq!(
+            decode_supply_reconciliation(&[0u8; 8]).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_transfer_balance_roundtrip() {
+        for amount in [0, 1, 999_999, u64::MAX] {
+            assert_eq!(
+                decode_transfer_balance(&encode_transfer_balance(amount)).unwrap(),
+                amount
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_status_roundtrip() {
+        for status in 0..=3 {
+            assert_eq!(decode_verify_status(&encode_verify_status(status)).unwrap(), status);
+        }
+        assert_eq!(decode_verify_status(&[]).unwrap_err(), ProgramError::InvalidArgument);
+        assert_eq!(
+            decode_verify_status(&[0, 0]).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_mint_info_roundtrip() {
+        let info = MintInfo {
+            supply: 1_234_567,
+            decimals: 6,
+            is_initialized: true,
+            has_mint_authority: true,
+            has_freeze_authority: false,
+        };
+        assert_eq!(decode_mint_info(&encode_mint_info(&info)).unwrap(), info);
+
+        let mut encoded = encode_mint_info(&info);
+        encoded[11] = 2;
+        assert_eq!(decode_mint_info(&encoded).unwrap_err(), ProgramError::InvalidArgument);
+        assert_eq!(
+            decode_mint_info(&encoded[..11]).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_multisig_info_roundtrip() {
+        let info = MultisigInfo {
+            m: 2,
+            signers: vec![Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()],
+        };
+        let encoded = encode_multisig_info(&info);
+        assert_eq!(encoded.len(), 2 + 32 * 3);
+        assert_eq!(decode_multisig_info(&encoded).unwrap(), info);
+
+        // n says 3 signers, but only 2 follow
+        assert_eq!(
+            decode_multisig_info(&encoded[..2 + 32 * 2]).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+        assert_eq!(decode_multisig_info(&[1]).unwrap_err(), ProgramError::InvalidArgument);
+    }
+
+    #[test]
+    fn test_decode_wrong_length() {
+        assert_eq!(decode_supply(&[0u8; 7]).unwrap_err(), ProgramError::InvalidArgument);
+        assert_eq!(decode_supply(&[0u8; 9]).unwrap_err(), ProgramError::InvalidArgument);
+        assert_eq!(decode_supply(&[]).unwrap_err(), ProgramError::InvalidArgument);
+    }
+}
@@ -18,11 +18,15 @@
 //! - Total: 2 Token Accounts
 //!
 //! # Size: 165 bytes (matches SPL Token exactly)
+//!
+//! Extended accounts are `Account::EXTENDED_LEN` bytes: the same 165-byte
+//! base layout followed by a layout version byte and extension fields
+//! (see "Extended Layout" on `Account`).
 
 use crate::error::TokenError;
-use crate::state::{COption, Pack};
+use crate::state::{pack_coption_u64, unpack_coption_u64, COption, Pack};
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
-use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
 
 // =============================================================================
 // ACCOUNT STATE ENUM
@@ -79,6 +83,40 @@ impl AccountState {
             AccountState::Frozen => 2,
         }
     }
+
+    /// Lowercase state name: "uninitialized", "initialized" or "frozen".
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AccountState::Uninitialized => "uninitialized",
+            AccountState::Initialized => "initialized",
+            AccountState::Frozen => "frozen",
+        }
+    }
+
+    /// Check if moving from this state to `next` is allowed.
+    ///
+    /// # Allowed Transitions
+    ///
+    /// - Uninitialized → Initialized (InitializeAccount)
+    /// - Initialized → Frozen (FreezeAccount)
+    /// - Frozen → Initialized (ThawAccount)
+    ///
+    /// Everything else, including staying in the same state, is rejected.
+    pub fn can_transition_to(self, next: AccountState) -> bool {
+        matches!(
+            (self, next),
+            (AccountState::Uninitialized, AccountState::Initialized)
+                | (AccountState::Initialized, AccountState::Frozen)
+                | (AccountState::Frozen, AccountState::Initialized)
+        )
+    }
+}
+
+impl std::fmt::Display for AccountState {
+    /// Lowercase state name, for logs and CLIs.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 // =============================================================================
@@ -108,6 +146,26 @@ impl AccountState {
 /// └────────────────────────────────────────────────────────────────┘
 /// ```
 ///
+/// # Extended Layout (179 bytes total)
+///
+/// ```text
+/// ┌─────────────────────────────────────────────────────────────────┐
+/// │ Offset │ Size │ Field            │ Type                        │
+/// ├────────┼──────┼──────────────────┼─────────────────────────────┤
+/// │ 0      │ 165  │ (base layout)    │ as above                    │
+/// │ 165    │ 1    │ layout version   │ u8 (0 = unset, 2 = current) │
+/// │ 166    │ 1    │ memo_required    │ bool (0 or 1)               │
+/// │ 167    │ 12   │ delegate_expiry  │ COption<i64>                │
+/// ├────────┼──────┼──────────────────┼─────────────────────────────┤
+/// │ Total  │ 179  │                  │                             │
+/// └─────────────────────────────────────────────────────────────────┘
+/// ```
+///
+/// Version 0 means the extension area hasn't been written yet (for
+/// example right after Reallocate), so every extension field takes its
+/// default. Any other version than the current one is rejected: version
+/// 1 had a shorter extension area, which `is_valid_len` doesn't accept.
+///
 /// # Example Usage
 ///
 /// ```ignore
@@ -279,6 +337,19 @@ pub struct Account {
     /// - Close authority must sign
     /// - Rent lamports go to specified destination
     pub close_authority: COption<Pubkey>,
+
+    /// Inbound transfers must come with a memo (extension field).
+    ///
+    /// Set with SetMemoRequired. Only stored by extended accounts;
+    /// always `false` for 165-byte accounts.
+    pub memo_required: bool,
+
+    /// Unix timestamp after which the delegate can't transfer (extension field).
+    ///
+    /// Set with ApproveWithExpiry and cleared whenever the delegate is
+    /// replaced or revoked. Only stored by extended accounts; always
+    /// `None` for 165-byte accounts.
+    pub delegate_expiry: COption<i64>,
 }
 
 // =============================================================================
@@ -300,6 +371,51 @@ impl Account {
     /// - Total: 32 + 32 + 8 + 36 + 1 + 12 + 8 + 36 = 165 bytes
     pub const LEN: usize = 165;
 
+    /// Extension layout version written by this program.
+    ///
+    /// Bumped whenever an extension field is added. `EXTENDED_LEN` grows
+    /// with it, so only an area at this version is ever read.
+    pub const LAYOUT_VERSION: u8 = 2;
+
+    /// Size of an extended account.
+    ///
+    /// Calculation:
+    /// - base layout: 165 bytes
+    /// - layout version: 1 byte (u8)
+    /// - memo_required: 1 byte (bool as u8)
+    /// - delegate_expiry: 12 bytes (4 tag + 8 i64)
+    /// - Total: 165 + 1 + 1 + 12 = 179 bytes
+    pub const EXTENDED_LEN: usize = Account::LEN + 1 + 1 + 12;
+
+    /// Create an initialized, empty account for `mint` owned by `owner`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let account = Account::new(mint, owner);
+    /// assert!(account.is_initialized());
+    /// ```
+    pub fn new(mint: Pubkey, owner: Pubkey) -> Self {
+        Account {
+            mint,
+            owner,
+            state: AccountState::Initialized,
+            ..Account::default()
+        }
+    }
+
+    /// Check if `len` is a valid account data length (base or extended).
+    pub fn is_valid_len(len: usize) -> bool {
+        len == Account::LEN || len == Account::EXTENDED_LEN
+    }
+
+    /// Check if any extension field differs from its default.
+    ///
+    /// Such an account can only be stored in an extended account.
+    pub fn has_extensions(&self) -> bool {
+        self.memo_required || self.delegate_expiry.is_some()
+    }
+
     /// Check if the account is frozen.
     ///
     /// Frozen accounts cannot transfer tokens out.
@@ -342,6 +458,60 @@ impl Account {
     pub fn is_native(&self) -> bool {
         self.is_native.is_some()
     }
+
+    /// Check if a native account's `amount` has drifted from its lamports.
+    ///
+    /// Lamports sent straight to a wrapped SOL account (a plain system
+    /// transfer) don't update `amount`. When that happens a SyncNative
+    /// is needed to bring the two back in line.
+    ///
+    /// # Returns
+    ///
+    /// * `true` - Native, and `amount != current_lamports - rent_reserve`
+    /// * `false` - In sync, or not a native account
+    pub fn needs_sync(&self, current_lamports: u64, rent_reserve: u64) -> bool {
+        self.is_native() && self.amount != current_lamports.saturating_sub(rent_reserve)
+    }
+
+    /// Human-readable account status (same text as `AccountState`'s Display).
+    pub fn status_str(&self) -> &'static str {
+        self.state.as_str()
+    }
+
+    /// Freeze the account.
+    ///
+    /// # Errors
+    ///
+    /// * `UninitializedAccount` - Account not initialized
+    /// * `InvalidState` - Account already frozen
+    pub fn freeze(&mut self) -> ProgramResult {
+        self.transition_to(AccountState::Frozen)
+    }
+
+    /// Thaw a frozen account.
+    ///
+    /// # Errors
+    ///
+    /// * `UninitializedAccount` - Account not initialized
+    /// * `InvalidState` - Account not frozen
+    pub fn thaw(&mut self) -> ProgramResult {
+        self.transition_to(AccountState::Initialized)
+    }
+
+    /// Move an initialized account to `next` if `can_transition_to` allows it.
+    ///
+    /// Uninitialized accounts are rejected up front, so thaw can't be
+    /// used as a back door to Uninitialized → Initialized.
+    fn transition_to(&mut self, next: AccountState) -> ProgramResult {
+        if !self.is_initialized() {
+            return Err(TokenError::UninitializedAccount.into());
+        }
+        if !self.state.can_transition_to(next) {
+            return Err(TokenError::InvalidState.into());
+        }
+        self.state = next;
+        Ok(())
+    }
 }
 
 // =============================================================================
@@ -380,6 +550,8 @@ impl Pack for Account {
             is_native: unpack_coption_u64(is_native)?,
             delegated_amount: u64::from_le_bytes(*delegated_amount),
             close_authority: unpack_coption_pubkey(close_authority)?,
+            memo_required: false,
+            delegate_expiry: COption::none(),
         })
     }
 
@@ -413,6 +585,82 @@ impl Pack for Account {
 
         Ok(())
     }
+
+    /// Unpack a base or extended account without requiring initialization.
+    ///
+    /// Extended accounts still get their extension area read, so
+    /// initialization sees the whole existing state.
+    fn unpack_unchecked(input: &[u8]) -> Result<Self, ProgramError> {
+        Self::unpack_from_slice(input)
+    }
+
+    /// Unpack a base or extended account.
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        match src.len() {
+            Account::LEN => Self::unpack(src),
+            Account::EXTENDED_LEN => {
+                let mut account = Self::unpack(src)?;
+                let extension = array_ref![src, Account::LEN, Account::EXTENDED_LEN - Account::LEN];
+                unpack_extension(extension, &mut account)?;
+                Ok(account)
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// Pack a base or extended account.
+    ///
+    /// Fails with `InvalidAccountDataLength` if an extension field is set
+    /// but `dst` is a base 165-byte account.
+    fn pack_into_slice(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        match dst.len() {
+            Account::LEN => {
+                if self.has_extensions() {
+                    return Err(TokenError::InvalidAccountDataLength.into());
+                }
+                self.pack(dst)
+            }
+            Account::EXTENDED_LEN => {
+                self.pack(dst)?;
+                let extension =
+                    array_mut_ref![dst, Account::LEN, Account::EXTENDED_LEN - Account::LEN];
+                pack_extension(self, extension);
+                Ok(())
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+// =============================================================================
+// HELPER FUNCTIONS FOR THE EXTENSION AREA
+// =============================================================================
+
+/// Read extension fields from the bytes after the base layout.
+///
+/// Version 0 leaves every field at its default.
+fn unpack_extension(src: &[u8; 14], account: &mut Account) -> Result<(), ProgramError> {
+    let (version, memo_required, delegate_expiry) = array_refs![src, 1, 1, 12];
+
+    match version[0] {
+        0 => Ok(()),
+        Account::LAYOUT_VERSION => {
+            account.memo_required = memo_required[0] != 0;
+            // i64 shares the COption<u64> layout; `as` keeps the bits
+            account.delegate_expiry = unpack_coption_u64(delegate_expiry)?.map(|ts| ts as i64);
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Write extension fields (always at the current layout version).
+fn pack_extension(account: &Account, dst: &mut [u8; 14]) {
+    let (version, memo_required, delegate_expiry) = mut_array_refs![dst, 1, 1, 12];
+
+    version[0] = Account::LAYOUT_VERSION;
+    memo_required[0] = account.memo_required as u8;
+    pack_coption_u64(&account.delegate_expiry.map(|ts| ts as u64), delegate_expiry);
 }
 
 // =============================================================================
@@ -449,37 +697,68 @@ fn pack_coption_pubkey(src: &COption<Pubkey>, dst: &mut [u8; 36]) {
 }
 
 // =============================================================================
-// HELPER FUNCTIONS FOR COPTION<U64>
+// LENIENT DECODING (INDEXERS ONLY)
 // =============================================================================
 
-/// Unpack COption<u64> from 12 bytes.
-///
-/// Layout: [tag: 4 bytes][value: 8 bytes]
-///
-/// Used for the is_native field (wrapped SOL tracking).
-fn unpack_coption_u64(src: &[u8; 12]) -> Result<COption<u64>, ProgramError> {
-    let (tag, body) = array_refs![src, 4, 8];
+impl Account {
+    /// Best-effort decode of a possibly corrupted account buffer.
+    ///
+    /// **Non-validating.** Never use the result for authorization or
+    /// anything on-chain; use `unpack_from_slice` for that. This is for
+    /// indexers reading historical data that `unpack` would reject:
+    ///
+    /// - Any nonzero COption tag is read as `Some`
+    /// - An unknown state byte is read as `Uninitialized`
+    /// - Short input is zero-padded, extra bytes are ignored
+    pub fn unpack_lenient(data: &[u8]) -> Account {
+        let mut buf = [0u8; Account::LEN];
+        let len = data.len().min(Account::LEN);
+        buf[..len].copy_from_slice(&data[..len]);
+
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (
+            mint,
+            owner,
+            amount,
+            delegate,
+            state,
+            is_native,
+            delegated_amount,
+            close_authority,
+        ) = array_refs![&buf, 32, 32, 8, 36, 1, 12, 8, 36];
+
+        Account {
+            mint: Pubkey::new_from_array(*mint),
+            owner: Pubkey::new_from_array(*owner),
+            amount: u64::from_le_bytes(*amount),
+            delegate: unpack_coption_pubkey_lenient(delegate),
+            state: AccountState::from_u8(state[0]).unwrap_or(AccountState::Uninitialized),
+            is_native: unpack_coption_u64_lenient(is_native),
+            delegated_amount: u64::from_le_bytes(*delegated_amount),
+            close_authority: unpack_coption_pubkey_lenient(close_authority),
+            memo_required: false,
+            delegate_expiry: COption::none(),
+        }
+    }
+}
+
+/// Unpack COption<Pubkey>, treating any nonzero tag as Some.
+fn unpack_coption_pubkey_lenient(src: &[u8; 36]) -> COption<Pubkey> {
+    let (tag, body) = array_refs![src, 4, 32];
 
     match u32::from_le_bytes(*tag) {
-        0 => Ok(COption::none()),
-        1 => Ok(COption::some(u64::from_le_bytes(*body))),
-        _ => Err(TokenError::InvalidInstruction.into()),
+        0 => COption::none(),
+        _ => COption::some(Pubkey::new_from_array(*body)),
     }
 }
 
-/// Pack COption<u64> into 12 bytes.
-fn pack_coption_u64(src: &COption<u64>, dst: &mut [u8; 12]) {
-    let (tag, body) = mut_array_refs![dst, 4, 8];
+/// Unpack COption<u64>, treating any nonzero tag as Some.
+fn unpack_coption_u64_lenient(src: &[u8; 12]) -> COption<u64> {
+    let (tag, body) = array_refs![src, 4, 8];
 
-    match src.as_ref() {
-        Some(value) => {
-            *tag = 1u32.to_le_bytes();
-            *body = value.to_le_bytes();
-        }
-        None => {
-            *tag = 0u32.to_le_bytes();
-            body.fill(0);
-        }
+    match u32::from_le_bytes(*tag) {
+        0 => COption::none(),
+        _ => COption::some(u64::from_le_bytes(*body)),
     }
 }
 
@@ -503,6 +782,8 @@ mod tests {
             is_native: COption::none(),
             delegated_amount: 500_000_000,
             close_authority: COption::some(Pubkey::new_unique()),
+            memo_required: false,
+            delegate_expiry: COption::none(),
         };
 
         let mut packed = [0u8; Account::LEN];
@@ -525,6 +806,8 @@ mod tests {
             is_native: COption::none(),
             delegated_amount: 0,
             close_authority: COption::none(),
+            memo_required: false,
+            delegate_expiry: COption::none(),
         };
 
         let mut packed = [0u8; Account::LEN];
@@ -539,8 +822,10 @@ mod tests {
     /// Test frozen account state.
     #[test]
     fn test_account_frozen() {
-        let mut account = Account::default();
-        account.state = AccountState::Frozen;
+        let account = Account {
+            state: AccountState::Frozen,
+            ..Account::default()
+        };
 
         assert!(account.is_frozen());
         assert!(account.is_initialized()); // Frozen is still initialized
@@ -585,6 +870,347 @@ mod tests {
         assert_eq!(AccountState::Initialized.to_u8(), 1);
         assert_eq!(AccountState::Frozen.to_u8(), 2);
     }
+
+    /// Test needs_sync for native and non-native accounts.
+    #[test]
+    fn test_account_needs_sync() {
+        let rent_reserve = 2_039_280;
+
+        // Synced native account
+        let native = Account {
+            state: AccountState::Initialized,
+            is_native: COption::some(rent_reserve),
+            amount: 1_000,
+            ..Account::default()
+        };
+        assert!(!native.needs_sync(rent_reserve + 1_000, rent_reserve));
+
+        // Drifted: lamports were added without updating amount
+        assert!(native.needs_sync(rent_reserve + 5_000, rent_reserve));
+
+        // Non-native accounts never need syncing
+        let regular = Account {
+            state: AccountState::Initialized,
+            amount: 1_000,
+            ..Account::default()
+        };
+        assert!(!regular.needs_sync(rent_reserve + 1_000, rent_reserve));
+        assert!(!regular.needs_sync(rent_reserve + 5_000, rent_reserve));
+    }
+
+    /// Test AccountState Display output.
+    #[test]
+    fn test_account_state_display() {
+        assert_eq!(AccountState::Uninitialized.to_string(), "uninitialized");
+        assert_eq!(AccountState::Initialized.to_string(), "initialized");
+        assert_eq!(AccountState::Frozen.to_string(), "frozen");
+    }
+
+    /// Test status_str matches Display for every state.
+    #[test]
+    fn test_account_status_str() {
+        for state in [
+            AccountState::Uninitialized,
+            AccountState::Initialized,
+            AccountState::Frozen,
+        ] {
+            let account = Account {
+                state,
+                ..Account::default()
+            };
+            assert_eq!(account.status_str(), state.to_string());
+        }
+    }
+
+    /// Test freezing an initialized account.
+    #[test]
+    fn test_account_freeze_initialized() {
+        let mut account = Account {
+            state: AccountState::Initialized,
+            ..Account::default()
+        };
+
+        assert!(account.freeze().is_ok());
+        assert!(account.is_frozen());
+
+        // Thaw brings it back
+        assert!(account.thaw().is_ok());
+        assert_eq!(account.state, AccountState::Initialized);
+    }
+
+    /// Test freezing an already-frozen account fails.
+    #[test]
+    fn test_account_freeze_already_frozen() {
+        let mut account = Account {
+            state: AccountState::Frozen,
+            ..Account::default()
+        };
+
+        assert_eq!(account.freeze().unwrap_err(), TokenError::InvalidState.into());
+        assert!(account.is_frozen());
+    }
+
+    /// Test thawing an account that isn't frozen fails.
+    #[test]
+    fn test_account_thaw_not_frozen() {
+        let mut account = Account {
+            state: AccountState::Initialized,
+            ..Account::default()
+        };
+        assert_eq!(account.thaw().unwrap_err(), TokenError::InvalidState.into());
+
+        let mut account = Account::default();
+        assert_eq!(
+            account.thaw().unwrap_err(),
+            TokenError::UninitializedAccount.into()
+        );
+        assert_eq!(
+            account.freeze().unwrap_err(),
+            TokenError::UninitializedAccount.into()
+        );
+    }
+
+    /// Test lenient decoding of malformed bytes.
+    #[test]
+    fn test_account_unpack_lenient_malformed() {
+        let original = Account {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            amount: 42,
+            delegate: COption::some(Pubkey::new_unique()),
+            state: AccountState::Initialized,
+            is_native: COption::some(7),
+            delegated_amount: 5,
+            close_authority: COption::none(),
+            memo_required: false,
+            delegate_expiry: COption::none(),
+        };
+        let mut packed = [0u8; Account::LEN];
+        original.pack(&mut packed).unwrap();
+
+        // Non-canonical tags and an unknown state byte
+        packed[72..76].copy_from_slice(&7u32.to_le_bytes()); // delegate tag
+        packed[108] = 0xff; // state
+        packed[109..113].copy_from_slice(&2u32.to_le_bytes()); // is_native tag
+        assert!(Account::unpack(&packed).is_err());
+
+        let lenient = Account::unpack_lenient(&packed);
+        assert_eq!(lenient.mint, original.mint);
+        assert_eq!(lenient.amount, 42);
+        assert_eq!(lenient.delegate, original.delegate);
+        assert_eq!(lenient.state, AccountState::Uninitialized);
+        assert_eq!(lenient.is_native, COption::some(7));
+        assert!(lenient.close_authority.is_none());
+
+        // Truncated input is zero-padded
+        let truncated = Account::unpack_lenient(&packed[..40]);
+        assert_eq!(truncated.mint, original.mint);
+        assert_eq!(truncated.amount, 0);
+    }
+
+    /// Test every COption tag rejects out-of-range values.
+    #[test]
+    fn test_account_invalid_coption_tags() {
+        let original = Account {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            amount: 42,
+            state: AccountState::Initialized,
+            ..Account::default()
+        };
+        let mut valid = [0u8; Account::LEN];
+        original.pack(&mut valid).unwrap();
+
+        // delegate, is_native, close_authority
+        for offset in [72, 109, 129] {
+            for tag in [2u32, u32::MAX] {
+                let mut packed = valid;
+                packed[offset..offset + 4].copy_from_slice(&tag.to_le_bytes());
+                assert!(
+                    Account::unpack(&packed).is_err(),
+                    "tag {} at offset {} was accepted",
+                    tag,
+                    offset
+                );
+            }
+        }
+    }
+
+    /// Test tag 1 with an all-zero body reads as Some(default).
+    #[test]
+    fn test_account_coption_tag_one_zero_body() {
+        let mut packed = [0u8; Account::LEN];
+        packed[108] = AccountState::Initialized.to_u8();
+        packed[72] = 1; // delegate tag
+        packed[109] = 1; // is_native tag
+        packed[129] = 1; // close_authority tag
+
+        let account = Account::unpack(&packed).unwrap();
+        assert_eq!(account.delegate, COption::some(Pubkey::default()));
+        assert_eq!(account.is_native, COption::some(0));
+        assert_eq!(account.close_authority, COption::some(Pubkey::default()));
+
+        // Same bytes, same result
+        assert_eq!(Account::unpack(&packed).unwrap(), account);
+    }
+
+    /// Test Account::new builds an initialized, empty account.
+    #[test]
+    fn test_account_new() {
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let account = Account::new(mint, owner);
+
+        assert_eq!(account.state, AccountState::Initialized);
+        assert!(account.is_initialized());
+        assert!(!account.is_frozen());
+        assert!(!account.is_native());
+        assert_eq!(account.mint, mint);
+        assert_eq!(account.owner, owner);
+        assert_eq!(account.amount, 0);
+        assert!(account.delegate.is_none());
+        assert!(account.close_authority.is_none());
+
+        let mut packed = [0u8; Account::LEN];
+        account.pack_into_slice(&mut packed).unwrap();
+        assert_eq!(Account::unpack(&packed).unwrap(), account);
+    }
+
+    /// Test extended account roundtrip keeps the extension fields.
+    #[test]
+    fn test_account_extended_roundtrip() {
+        let original = Account {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            amount: 7,
+            state: AccountState::Initialized,
+            memo_required: true,
+            delegate_expiry: COption::some(-1),
+            ..Account::default()
+        };
+
+        let mut packed = [0u8; Account::EXTENDED_LEN];
+        original.pack_into_slice(&mut packed).unwrap();
+        assert_eq!(packed[Account::LEN], Account::LAYOUT_VERSION);
+
+        let unpacked = Account::unpack_from_slice(&packed).unwrap();
+        assert_eq!(original, unpacked);
+    }
+
+    /// Test version 0 reads as defaults and any other old or unknown
+    /// version fails.
+    #[test]
+    fn test_account_extended_versions() {
+        let mut packed = [0u8; Account::EXTENDED_LEN];
+        packed[Account::LEN + 1] = 1; // ignored without a version
+        assert!(!Account::unpack_from_slice(&packed).unwrap().memo_required);
+
+        for version in [1, Account::LAYOUT_VERSION + 1] {
+            packed[Account::LEN] = version;
+            assert_eq!(
+                Account::unpack_from_slice(&packed).unwrap_err(),
+                ProgramError::InvalidAccountData
+            );
+        }
+    }
+
+    /// Test extension fields can't be packed into a base account.
+    #[test]
+    fn test_account_extension_needs_extended_len() {
+        let account = Account {
+            state: AccountState::Initialized,
+            memo_required: true,
+            ..Account::default()
+        };
+
+        let mut packed = [0u8; Account::LEN];
+        assert_eq!(
+            account.pack_into_slice(&mut packed).unwrap_err(),
+            TokenError::InvalidAccountDataLength.into()
+        );
+    }
+
+    // =========================================================================
+    // PROPERTY TESTS
+    // =========================================================================
+
+    use proptest::prelude::*;
+
+    fn arb_pubkey() -> impl Strategy<Value = Pubkey> {
+        any::<[u8; 32]>().prop_map(Pubkey::new_from_array)
+    }
+
+    fn arb_state() -> impl Strategy<Value = AccountState> {
+        prop_oneof![
+            Just(AccountState::Uninitialized),
+            Just(AccountState::Initialized),
+            Just(AccountState::Frozen),
+        ]
+    }
+
+    /// Any base account: random keys, balances, state and options.
+    fn arb_base_account() -> impl Strategy<Value = Account> {
+        (
+            arb_pubkey(),
+            arb_pubkey(),
+            any::<u64>(),
+            proptest::option::of(arb_pubkey()).prop_map(COption::from),
+            arb_state(),
+            proptest::option::of(any::<u64>()).prop_map(COption::from),
+            any::<u64>(),
+            proptest::option::of(arb_pubkey()).prop_map(COption::from),
+        )
+            .prop_map(
+                |(
+                    mint,
+                    owner,
+                    amount,
+                    delegate,
+                    state,
+                    is_native,
+                    delegated_amount,
+                    close_authority,
+                )| Account {
+                    mint,
+                    owner,
+                    amount,
+                    delegate,
+                    state,
+                    is_native,
+                    delegated_amount,
+                    close_authority,
+                    memo_required: false,
+                    delegate_expiry: COption::none(),
+                },
+            )
+    }
+
+    proptest! {
+        /// Base accounts survive pack then unpack.
+        #[test]
+        fn prop_account_base_roundtrip(account in arb_base_account()) {
+            let mut packed = [0u8; Account::LEN];
+            account.pack_into_slice(&mut packed).unwrap();
+            prop_assert_eq!(Account::unpack_from_slice(&packed).unwrap(), account);
+        }
+
+        /// Extended accounts survive pack then unpack, extension included.
+        #[test]
+        fn prop_account_extended_roundtrip(
+            base in arb_base_account(),
+            memo_required in any::<bool>(),
+            delegate_expiry in proptest::option::of(any::<i64>()).prop_map(COption::from),
+        ) {
+            let account = Account {
+                memo_required,
+                delegate_expiry,
+                ..base
+            };
+            let mut packed = [0u8; Account::EXTENDED_LEN];
+            account.pack_into_slice(&mut packed).unwrap();
+            prop_assert_eq!(Account::unpack_from_slice(&packed).unwrap(), account);
+        }
+    }
 }
 
 /*
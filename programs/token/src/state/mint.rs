@@ -17,12 +17,24 @@
 //! 4. Who can freeze accounts (freeze_authority)
 //!
 //! # Size: 82 bytes (matches SPL Token exactly)
+//!
+//! Extended mints are `Mint::EXTENDED_LEN` bytes: the same 82-byte base
+//! layout followed by a layout version byte and extension fields (see
+//! "Extended Layout" on `Mint`).
 
 use crate::error::TokenError;
-use crate::state::{COption, Pack};
+use crate::state::{pack_coption_u64, unpack_coption_u64, COption, Pack};
+use crate::utils::ui_amount;
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 
+/// Maximum decimals InitializeMint accepts.
+///
+/// 18 is the ceiling most token registries apply. It also keeps
+/// display math (`10^decimals`) clear of u64 overflow, which starts
+/// at 20. Zero decimals (whole units only) stays valid.
+pub const MAX_DECIMALS: u8 = 18;
+
 // =============================================================================
 // MINT STRUCTURE
 // =============================================================================
@@ -59,6 +71,33 @@ use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 /// Tag = 1: Some(Pubkey)
 /// ```
 ///
+/// # Extended Layout (169 bytes total)
+///
+/// Mints created with `Mint::EXTENDED_LEN` bytes append extension fields
+/// after the base layout. The base 82 bytes are unchanged, so anything
+/// that only reads the base fields keeps working.
+///
+/// ```text
+/// ┌─────────────────────────────────────────────────────────────────┐
+/// │ Offset │ Size │ Field            │ Type                        │
+/// ├────────┼──────┼──────────────────┼─────────────────────────────┤
+/// │ 0      │ 82   │ (base layout)    │ as above                    │
+/// │ 82     │ 1    │ layout version   │ u8 (0 = unset, 5 = current) │
+/// │ 83     │ 1    │ non_transferable │ bool (0 or 1)               │
+/// │ 84     │ 12   │ supply_cap       │ COption<u64>                │
+/// │ 96     │ 1    │ mint_paused      │ bool (0 or 1)               │
+/// │ 97     │ 36   │ close_authority  │ COption<Pubkey>             │
+/// │ 133    │ 36   │ transfer_hook    │ COption<Pubkey>             │
+/// ├────────┼──────┼──────────────────┼─────────────────────────────┤
+/// │ Total  │ 169  │                  │                             │
+/// └─────────────────────────────────────────────────────────────────┘
+/// ```
+///
+/// Version 0 means the extension area hasn't been written yet (a freshly
+/// created account), so every extension field takes its default. Any
+/// other version than the current one is rejected: each earlier version
+/// had a shorter extension area, which `is_valid_len` doesn't accept.
+///
 /// # Example Usage
 ///
 /// ```ignore
@@ -247,6 +286,44 @@ pub struct Mint {
     /// You cannot add freeze authority later.
     /// Most DeFi tokens set this to None for decentralization.
     pub freeze_authority: COption<Pubkey>,
+
+    /// Tokens of this mint can't be transferred (extension field).
+    ///
+    /// Set once with SetNonTransferable and never cleared. Minting and
+    /// burning still work. Only stored by extended mints; always `false`
+    /// for 82-byte mints.
+    pub non_transferable: bool,
+
+    /// Maximum supply (extension field).
+    ///
+    /// Set or cleared by the mint authority with SetSupplyCap. MintTo
+    /// fails with `SupplyCapExceeded` if it would push supply past the
+    /// cap. Only stored by extended mints; always None for 82-byte mints.
+    pub supply_cap: COption<u64>,
+
+    /// Minting is temporarily halted (extension field).
+    ///
+    /// Toggled by the mint authority with SetMintPaused. While set,
+    /// MintTo fails with `MintPaused`; burns and transfers are
+    /// unaffected. Only stored by extended mints; always `false` for
+    /// 82-byte mints.
+    pub mint_paused: bool,
+
+    /// The authority that can close this mint (extension field).
+    ///
+    /// Set with SetAuthority(CloseAccount); while unset, the mint
+    /// authority may set it. CloseAccount with this authority closes
+    /// the mint once its supply is zero. Only stored by extended mints;
+    /// always None for 82-byte mints.
+    pub close_authority: COption<Pubkey>,
+
+    /// Program called on every transfer of this token (extension field).
+    ///
+    /// Set or cleared by the mint authority with SetTransferHook. While
+    /// set, Transfer invokes it after moving the tokens and fails with
+    /// `TransferHookFailed` if it errors. Only stored by extended mints;
+    /// always None for 82-byte mints.
+    pub transfer_hook: COption<Pubkey>,
 }
 
 // =============================================================================
@@ -266,6 +343,130 @@ impl Mint {
     ///
     /// This matches SPL Token exactly for compatibility.
     pub const LEN: usize = 82;
+
+    /// Extension layout version written by this program.
+    ///
+    /// Bumped whenever an extension field is added. `EXTENDED_LEN` grows
+    /// with it, so only an area at this version is ever read.
+    pub const LAYOUT_VERSION: u8 = 5;
+
+    /// Size of an extended mint.
+    ///
+    /// Calculation:
+    /// - base layout: 82 bytes
+    /// - layout version: 1 byte (u8)
+    /// - non_transferable: 1 byte (bool as u8)
+    /// - supply_cap: 12 bytes (4 tag + 8 u64)
+    /// - mint_paused: 1 byte (bool as u8)
+    /// - close_authority: 36 bytes (4 tag + 32 pubkey)
+    /// - transfer_hook: 36 bytes (4 tag + 32 pubkey)
+    /// - Total: 82 + 1 + 1 + 12 + 1 + 36 + 36 = 169 bytes
+    pub const EXTENDED_LEN: usize = Mint::LEN + 1 + 1 + 12 + 1 + 36 + 36;
+
+    /// Create an initialized mint with zero supply and no extensions.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mint = Mint::new(6, Some(authority), None);
+    /// assert!(mint.is_initialized);
+    /// ```
+    pub fn new(
+        decimals: u8,
+        mint_authority: Option<Pubkey>,
+        freeze_authority: Option<Pubkey>,
+    ) -> Self {
+        Mint {
+            mint_authority: mint_authority.into(),
+            decimals,
+            is_initialized: true,
+            freeze_authority: freeze_authority.into(),
+            ..Mint::default()
+        }
+    }
+
+    /// Check if `len` is a valid mint data length (base or extended).
+    pub fn is_valid_len(len: usize) -> bool {
+        len == Mint::LEN || len == Mint::EXTENDED_LEN
+    }
+
+    /// Check if any extension field differs from its default.
+    ///
+    /// Such a mint can only be stored in an extended account.
+    pub fn has_extensions(&self) -> bool {
+        self.non_transferable
+            || self.supply_cap.is_some()
+            || self.mint_paused
+            || self.close_authority.is_some()
+            || self.transfer_hook.is_some()
+    }
+
+    /// Check that minting `amount` more tokens stays within the cap.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - No cap, or `supply + amount <= cap`
+    /// * `Err(SupplyCapExceeded)` - The cap would be exceeded
+    /// * `Err(Overflow)` - `supply + amount` overflows u64
+    pub fn check_supply_cap(&self, amount: u64) -> Result<(), ProgramError> {
+        let new_supply = self
+            .supply
+            .checked_add(amount)
+            .ok_or(TokenError::Overflow)?;
+
+        match self.supply_cap.as_ref() {
+            Some(cap) if new_supply > *cap => Err(TokenError::SupplyCapExceeded.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Format a raw amount of this token for display.
+    ///
+    /// Uses this mint's decimals and trims trailing zeros.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // decimals = 6
+    /// assert_eq!(mint.amount_to_ui_string(1_500_000), "1.5");
+    /// ```
+    pub fn amount_to_ui_string(&self, amount: u64) -> String {
+        ui_amount::amount_to_ui_string(amount, self.decimals)
+    }
+
+    /// Parse a display string into a raw amount of this token.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidArgument` - Malformed
+    /// * `ExcessPrecision` - More fractional digits than decimals
+    /// * `Overflow` - Doesn't fit in a u64
+    pub fn ui_string_to_amount(&self, ui_amount: &str) -> Result<u64, ProgramError> {
+        ui_amount::ui_string_to_amount(ui_amount, self.decimals)
+    }
+
+    /// One-line summary for logs and CLIs.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// supply=1.5 (1500000) decimals=6 mint_authority=Abc... freeze_authority=none
+    /// ```
+    pub fn summary(&self) -> String {
+        let authority = |a: &COption<Pubkey>| match a.as_ref() {
+            Some(key) => key.to_string(),
+            None => "none".to_string(),
+        };
+        format!(
+            "supply={} ({}) decimals={} mint_authority={} freeze_authority={}{}",
+            self.amount_to_ui_string(self.supply),
+            self.supply,
+            self.decimals,
+            authority(&self.mint_authority),
+            authority(&self.freeze_authority),
+            if self.is_initialized { "" } else { " (uninitialized)" },
+        )
+    }
 }
 
 // =============================================================================
@@ -360,6 +561,11 @@ impl Pack for Mint {
             decimals,
             is_initialized,
             freeze_authority,
+            non_transferable: false,
+            supply_cap: COption::none(),
+            mint_paused: false,
+            close_authority: COption::none(),
+            transfer_hook: COption::none(),
         })
     }
 
@@ -421,6 +627,91 @@ impl Pack for Mint {
 
         Ok(())
     }
+
+    /// Unpack a base or extended mint without requiring initialization.
+    ///
+    /// Extended accounts still get their extension area read, so
+    /// initialization sees the whole existing state.
+    fn unpack_unchecked(input: &[u8]) -> Result<Self, ProgramError> {
+        Self::unpack_from_slice(input)
+    }
+
+    /// Unpack a base or extended mint.
+    ///
+    /// `unpack` only reads the base 82 bytes; extension fields are
+    /// read here when `src` is `Mint::EXTENDED_LEN` bytes.
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        match src.len() {
+            Mint::LEN => Self::unpack(src),
+            Mint::EXTENDED_LEN => {
+                let mut mint = Self::unpack(src)?;
+                let extension = array_ref![src, Mint::LEN, Mint::EXTENDED_LEN - Mint::LEN];
+                unpack_extension(extension, &mut mint)?;
+                Ok(mint)
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// Pack a base or extended mint.
+    ///
+    /// Fails with `InvalidAccountDataLength` if an extension field is set
+    /// but `dst` is a base 82-byte mint, rather than silently dropping it.
+    fn pack_into_slice(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        match dst.len() {
+            Mint::LEN => {
+                if self.has_extensions() {
+                    return Err(TokenError::InvalidAccountDataLength.into());
+                }
+                self.pack(dst)
+            }
+            Mint::EXTENDED_LEN => {
+                self.pack(dst)?;
+                let extension = array_mut_ref![dst, Mint::LEN, Mint::EXTENDED_LEN - Mint::LEN];
+                pack_extension(self, extension);
+                Ok(())
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+// =============================================================================
+// HELPER FUNCTIONS FOR THE EXTENSION AREA
+// =============================================================================
+
+/// Read extension fields from the bytes after the base layout.
+///
+/// Version 0 leaves every field at its default.
+fn unpack_extension(src: &[u8; 87], mint: &mut Mint) -> Result<(), ProgramError> {
+    let (version, non_transferable, supply_cap, mint_paused, close_authority, transfer_hook) =
+        array_refs![src, 1, 1, 12, 1, 36, 36];
+
+    match version[0] {
+        0 => Ok(()),
+        Mint::LAYOUT_VERSION => {
+            mint.non_transferable = non_transferable[0] != 0;
+            mint.supply_cap = unpack_coption_u64(supply_cap)?;
+            mint.mint_paused = mint_paused[0] != 0;
+            mint.close_authority = unpack_coption_pubkey(close_authority)?;
+            mint.transfer_hook = unpack_coption_pubkey(transfer_hook)?;
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Write extension fields (always at the current layout version).
+fn pack_extension(mint: &Mint, dst: &mut [u8; 87]) {
+    let (version, non_transferable, supply_cap, mint_paused, close_authority, transfer_hook) =
+        mut_array_refs![dst, 1, 1, 12, 1, 36, 36];
+
+    version[0] = Mint::LAYOUT_VERSION;
+    non_transferable[0] = mint.non_transferable as u8;
+    pack_coption_u64(&mint.supply_cap, supply_cap);
+    mint_paused[0] = mint.mint_paused as u8;
+    pack_coption_pubkey(&mint.close_authority, close_authority);
+    pack_coption_pubkey(&mint.transfer_hook, transfer_hook);
 }
 
 // =============================================================================
@@ -545,6 +836,11 @@ mod tests {
             decimals: 9,
             is_initialized: true,
             freeze_authority: COption::some(Pubkey::new_unique()),
+            non_transferable: false,
+            supply_cap: COption::none(),
+            mint_paused: false,
+            close_authority: COption::none(),
+            transfer_hook: COption::none(),
         };
 
         // Pack it
@@ -567,6 +863,11 @@ mod tests {
             decimals: 9,
             is_initialized: true,
             freeze_authority: COption::none(),
+            non_transferable: false,
+            supply_cap: COption::none(),
+            mint_paused: false,
+            close_authority: COption::none(),
+            transfer_hook: COption::none(),
         };
 
         let mut packed = [0u8; Mint::LEN];
@@ -623,6 +924,262 @@ mod tests {
         let result = Mint::unpack(&packed);
         assert!(result.is_err());
     }
+
+    /// Test the freeze_authority tag rejects out-of-range values too.
+    #[test]
+    fn test_mint_invalid_freeze_authority_tag() {
+        for tag in [2u32, u32::MAX] {
+            let mut packed = [0u8; Mint::LEN];
+            packed[45] = 1; // is_initialized
+            packed[46..50].copy_from_slice(&tag.to_le_bytes());
+
+            assert!(Mint::unpack(&packed).is_err());
+        }
+    }
+
+    /// Test Mint::new builds an initialized, empty mint.
+    #[test]
+    fn test_mint_new() {
+        let authority = Pubkey::new_unique();
+        let mint = Mint::new(6, Some(authority), None);
+
+        assert!(mint.is_initialized);
+        assert_eq!(mint.supply, 0);
+        assert_eq!(mint.decimals, 6);
+        assert_eq!(mint.mint_authority, COption::some(authority));
+        assert!(mint.freeze_authority.is_none());
+        assert!(!mint.has_extensions());
+
+        // Fits the base layout and survives a roundtrip
+        let mut packed = [0u8; Mint::LEN];
+        mint.pack_into_slice(&mut packed).unwrap();
+        assert_eq!(Mint::unpack(&packed).unwrap(), mint);
+    }
+
+    /// Test summary output.
+    #[test]
+    fn test_mint_summary() {
+        let authority = Pubkey::new_unique();
+        let mint = Mint {
+            mint_authority: COption::some(authority),
+            supply: 1_500_000,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::none(),
+            non_transferable: false,
+            supply_cap: COption::none(),
+            mint_paused: false,
+            close_authority: COption::none(),
+            transfer_hook: COption::none(),
+        };
+
+        assert_eq!(
+            mint.summary(),
+            format!(
+                "supply=1.5 (1500000) decimals=6 mint_authority={} freeze_authority=none",
+                authority
+            )
+        );
+        assert!(Mint::default().summary().ends_with(" (uninitialized)"));
+    }
+
+    /// Test UI string helpers at 0, 6 and 9 decimals.
+    #[test]
+    fn test_mint_ui_string_helpers() {
+        let mint_with = |decimals| Mint {
+            decimals,
+            is_initialized: true,
+            ..Mint::default()
+        };
+
+        // 0 decimals: no fractional part at all
+        let mint = mint_with(0);
+        assert_eq!(mint.amount_to_ui_string(0), "0");
+        assert_eq!(mint.amount_to_ui_string(1_000), "1000");
+        assert_eq!(mint.ui_string_to_amount("1000").unwrap(), 1_000);
+        assert!(mint.ui_string_to_amount("1.5").is_err());
+
+        // 6 decimals: trailing zeros trimmed
+        let mint = mint_with(6);
+        assert_eq!(mint.amount_to_ui_string(0), "0");
+        assert_eq!(mint.amount_to_ui_string(1_500_000), "1.5");
+        assert_eq!(mint.amount_to_ui_string(2_000_000), "2");
+        assert_eq!(mint.amount_to_ui_string(10), "0.00001");
+        assert_eq!(mint.ui_string_to_amount("1.5").unwrap(), 1_500_000);
+        assert_eq!(mint.ui_string_to_amount("1.500000").unwrap(), 1_500_000);
+        assert!(mint.ui_string_to_amount("1.0000001").is_err());
+
+        // 9 decimals
+        let mint = mint_with(9);
+        assert_eq!(mint.amount_to_ui_string(0), "0");
+        assert_eq!(mint.amount_to_ui_string(1), "0.000000001");
+        assert_eq!(mint.amount_to_ui_string(123_450_000_000), "123.45");
+        assert_eq!(mint.ui_string_to_amount("123.45").unwrap(), 123_450_000_000);
+        assert_eq!(mint.ui_string_to_amount("0.000000001").unwrap(), 1);
+    }
+
+    /// Test extended mint roundtrip keeps the extension fields.
+    #[test]
+    fn test_mint_extended_roundtrip() {
+        let original = Mint {
+            mint_authority: COption::some(Pubkey::new_unique()),
+            supply: 42,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: COption::none(),
+            non_transferable: true,
+            supply_cap: COption::some(1_000),
+            mint_paused: true,
+            close_authority: COption::some(Pubkey::new_unique()),
+            transfer_hook: COption::some(Pubkey::new_unique()),
+        };
+
+        let mut packed = [0u8; Mint::EXTENDED_LEN];
+        original.pack_into_slice(&mut packed).unwrap();
+        assert_eq!(packed[Mint::LEN], Mint::LAYOUT_VERSION);
+
+        let unpacked = Mint::unpack_from_slice(&packed).unwrap();
+        assert_eq!(original, unpacked);
+
+        // The base 82 bytes still read as a plain mint
+        let base = Mint::unpack(&packed[..Mint::LEN]).unwrap();
+        assert_eq!(base.supply, 42);
+        assert!(!base.non_transferable);
+    }
+
+    /// Test version 0 (unwritten extension area) reads as defaults.
+    #[test]
+    fn test_mint_extended_version_zero() {
+        let mut packed = [0u8; Mint::EXTENDED_LEN];
+        packed[Mint::LEN + 1] = 1; // ignored without a version
+
+        let mint = Mint::unpack_from_slice(&packed).unwrap();
+        assert!(!mint.non_transferable);
+    }
+
+    /// Test check_supply_cap with and without a cap.
+    #[test]
+    fn test_mint_check_supply_cap() {
+        let mut mint = Mint {
+            supply: 900,
+            ..Mint::default()
+        };
+        assert!(mint.check_supply_cap(u64::MAX - 900).is_ok());
+
+        mint.supply_cap = COption::some(1_000);
+        assert!(mint.check_supply_cap(100).is_ok());
+        assert_eq!(
+            mint.check_supply_cap(101).unwrap_err(),
+            TokenError::SupplyCapExceeded.into()
+        );
+        assert_eq!(
+            mint.check_supply_cap(u64::MAX).unwrap_err(),
+            TokenError::Overflow.into()
+        );
+    }
+
+    /// Test any version but 0 and the current one is rejected.
+    #[test]
+    fn test_mint_extended_unknown_version() {
+        for version in (1..Mint::LAYOUT_VERSION).chain([Mint::LAYOUT_VERSION + 1]) {
+            let mut packed = [0u8; Mint::EXTENDED_LEN];
+            packed[Mint::LEN] = version;
+
+            assert_eq!(
+                Mint::unpack_from_slice(&packed).unwrap_err(),
+                ProgramError::InvalidAccountData
+            );
+        }
+    }
+
+    /// Test extension fields can't be packed into a base mint.
+    #[test]
+    fn test_mint_extension_needs_extended_len() {
+        let mint = Mint {
+            is_initialized: true,
+            non_transferable: true,
+            ..Mint::default()
+        };
+
+        let mut packed = [0u8; Mint::LEN];
+        assert_eq!(
+            mint.pack_into_slice(&mut packed).unwrap_err(),
+            TokenError::InvalidAccountDataLength.into()
+        );
+    }
+
+    // =========================================================================
+    // PROPERTY TESTS
+    // =========================================================================
+
+    use proptest::prelude::*;
+
+    fn arb_pubkey() -> impl Strategy<Value = Pubkey> {
+        any::<[u8; 32]>().prop_map(Pubkey::new_from_array)
+    }
+
+    fn arb_coption_pubkey() -> impl Strategy<Value = COption<Pubkey>> {
+        proptest::option::of(arb_pubkey()).prop_map(COption::from)
+    }
+
+    /// Any base mint: random authorities, supply, decimals and flag.
+    fn arb_base_mint() -> impl Strategy<Value = Mint> {
+        (
+            arb_coption_pubkey(),
+            any::<u64>(),
+            any::<u8>(),
+            any::<bool>(),
+            arb_coption_pubkey(),
+        )
+            .prop_map(
+                |(mint_authority, supply, decimals, is_initialized, freeze_authority)| Mint {
+                    mint_authority,
+                    supply,
+                    decimals,
+                    is_initialized,
+                    freeze_authority,
+                    ..Mint::default()
+                },
+            )
+    }
+
+    /// Any extended mint: a base mint plus random extension fields.
+    fn arb_extended_mint() -> impl Strategy<Value = Mint> {
+        (
+            arb_base_mint(),
+            any::<bool>(),
+            proptest::option::of(any::<u64>()).prop_map(COption::from),
+            any::<bool>(),
+            arb_coption_pubkey(),
+            arb_coption_pubkey(),
+        )
+            .prop_map(|(base, non_transferable, supply_cap, paused, close, hook)| Mint {
+                non_transferable,
+                supply_cap,
+                mint_paused: paused,
+                close_authority: close,
+                transfer_hook: hook,
+                ..base
+            })
+    }
+
+    proptest! {
+        /// Base mints survive pack then unpack.
+        #[test]
+        fn prop_mint_base_roundtrip(mint in arb_base_mint()) {
+            let mut packed = [0u8; Mint::LEN];
+            mint.pack_into_slice(&mut packed).unwrap();
+            prop_assert_eq!(Mint::unpack_from_slice(&packed).unwrap(), mint);
+        }
+
+        /// Extended mints survive pack then unpack, extension included.
+        #[test]
+        fn prop_mint_extended_roundtrip(mint in arb_extended_mint()) {
+            let mut packed = [0u8; Mint::EXTENDED_LEN];
+            mint.pack_into_slice(&mut packed).unwrap();
+            prop_assert_eq!(Mint::unpack_from_slice(&packed).unwrap(), mint);
+        }
+    }
 }
 
 /*
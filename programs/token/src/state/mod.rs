@@ -39,10 +39,12 @@ pub mod multisig;
 // =============================================================================
 
 pub use account::{Account, AccountState};
-pub use mint::Mint;
+pub use mint::{Mint, MAX_DECIMALS};
 pub use multisig::{Multisig, MAX_SIGNERS};
 
-use solana_program::program_error::ProgramError;
+use crate::error::TokenError;
+use arrayref::{array_refs, mut_array_refs};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 
 // =============================================================================
 // PACK TRAIT
@@ -89,6 +91,14 @@ pub trait Pack: Sized {
     /// - Calculate rent exemption
     const LEN: usize;
 
+    /// The fixed size in bytes, as a method.
+    ///
+    /// Same as `Self::LEN`, but callable where a const is awkward
+    /// (e.g. generic code working over `T: Pack`).
+    fn get_packed_len() -> usize {
+        Self::LEN
+    }
+
     /// Deserialize from a byte slice.
     ///
     /// # Arguments
@@ -112,6 +122,25 @@ pub trait Pack: Sized {
     /// * `Err(...)` - Output is wrong size
     fn pack(&self, output: &mut [u8]) -> Result<(), ProgramError>;
 
+    /// Deserialize state that may not be initialized yet.
+    ///
+    /// Matches SPL Token's `unpack_unchecked`: used by the Initialize*
+    /// processors to load the existing (usually zeroed) state and check
+    /// `!is_initialized` themselves. `unpack_initialized` is the checked
+    /// counterpart. Callers validate the data length first.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mint = Mint::unpack_unchecked(&mint_info.data.borrow())?;
+    /// if mint.is_initialized {
+    ///     return Err(TokenError::AlreadyInitialized.into());
+    /// }
+    /// ```
+    fn unpack_unchecked(input: &[u8]) -> Result<Self, ProgramError> {
+        Self::unpack(input)
+    }
+
     /// Unpack with length validation.
     ///
     /// Checks that `src.len() == Self::LEN` before unpacking.
@@ -149,6 +178,116 @@ pub trait Pack: Sized {
     }
 }
 
+// =============================================================================
+// NATIVE MINT
+// =============================================================================
+
+/// The native mint: wrapped SOL.
+///
+/// Token accounts for this mint hold real lamports. Their token
+/// `amount` mirrors the lamports above the rent-exempt reserve.
+pub mod native_mint {
+    solana_program::declare_id!("So11111111111111111111111111111111111111112");
+
+    /// Wrapped SOL uses the same precision as lamports
+    pub const DECIMALS: u8 = 9;
+}
+
+/// Check if a mint address is the native (wrapped SOL) mint.
+pub fn is_native_mint(key: &Pubkey) -> bool {
+    key == &native_mint::id()
+}
+
+// =============================================================================
+// LAYOUT VERSIONING
+// =============================================================================
+
+/// Extension layout version of token accounts written by this program.
+///
+/// Mints are versioned separately by `Mint::LAYOUT_VERSION`.
+pub const CURRENT_VERSION: u8 = Account::LAYOUT_VERSION;
+
+/// Read the layout version of a state buffer.
+///
+/// A buffer of exactly `T::LEN` bytes predates versioning and reads as
+/// version 0. Longer buffers carry the version in the first byte after
+/// the base layout, so `unpack` implementations can branch on it.
+///
+/// # Errors
+///
+/// * `InvalidAccountData` - Buffer is shorter than `T::LEN`
+///
+/// # Example
+///
+/// ```ignore
+/// match read_version::<Account>(&data)? {
+///     0 => { /* legacy 165-byte account */ }
+///     CURRENT_VERSION => { /* read extension fields */ }
+///     _ => return Err(ProgramError::InvalidAccountData),
+/// }
+/// ```
+pub fn read_version<T: Pack>(data: &[u8]) -> Result<u8, ProgramError> {
+    match data.len().cmp(&T::LEN) {
+        std::cmp::Ordering::Less => Err(ProgramError::InvalidAccountData),
+        std::cmp::Ordering::Equal => Ok(0),
+        std::cmp::Ordering::Greater => Ok(data[T::LEN]),
+    }
+}
+
+// =============================================================================
+// ISINITIALIZED TRAIT
+// =============================================================================
+
+/// Trait for state types that track whether they have been initialized.
+///
+/// Mint and Multisig store an `is_initialized` flag directly.
+/// Account derives it from its `state` field.
+pub trait IsInitialized {
+    /// Check if this state has been initialized.
+    fn is_initialized(&self) -> bool;
+}
+
+impl IsInitialized for Mint {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for Account {
+    fn is_initialized(&self) -> bool {
+        self.state != AccountState::Uninitialized
+    }
+}
+
+impl IsInitialized for Multisig {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+/// Unpack state and require it to be initialized.
+///
+/// Combines `unpack_from_slice` with the initialization check
+/// every processor performs after loading an account.
+///
+/// # Errors
+///
+/// * `InvalidAccountData` - Wrong data length
+/// * `UninitializedAccount` - Data unpacked, but not initialized
+///
+/// # Example
+///
+/// ```ignore
+/// let mint: Mint = unpack_initialized(&mint_info.data.borrow())?;
+/// ```
+pub fn unpack_initialized<T: Pack + IsInitialized>(data: &[u8]) -> Result<T, ProgramError> {
+    let value = T::unpack_from_slice(data)?;
+    if !value.is_initialized() {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+    Ok(value)
+}
+
 // =============================================================================
 // COPTION - COMPACT OPTIONAL TYPE
 // =============================================================================
@@ -271,6 +410,248 @@ impl<T> From<COption<T>> for Option<T> {
     }
 }
 
+// =============================================================================
+// COPTION<U64> ENCODING
+// =============================================================================
+
+/// Unpack COption<u64> from 12 bytes.
+///
+/// Layout: [tag: 4 bytes][value: 8 bytes]
+///
+/// Shared by Account (is_native, delegate_expiry) and Mint (supply_cap).
+///
+/// # Errors
+///
+/// * `InvalidAccountData` - Tag is neither 0 nor 1
+pub(crate) fn unpack_coption_u64(src: &[u8; 12]) -> Result<COption<u64>, ProgramError> {
+    let (tag, body) = array_refs![src, 4, 8];
+
+    match u32::from_le_bytes(*tag) {
+        0 => Ok(COption::none()),
+        1 => Ok(COption::some(u64::from_le_bytes(*body))),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Pack COption<u64> into 12 bytes.
+pub(crate) fn pack_coption_u64(src: &COption<u64>, dst: &mut [u8; 12]) {
+    let (tag, body) = mut_array_refs![dst, 4, 8];
+
+    match src.as_ref() {
+        Some(value) => {
+            *tag = 1u32.to_le_bytes();
+            *body = value.to_le_bytes();
+        }
+        None => {
+            *tag = 0u32.to_le_bytes();
+            body.fill(0);
+        }
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_native_mint() {
+        let canonical: Pubkey = "So11111111111111111111111111111111111111112".parse().unwrap();
+        assert!(is_native_mint(&canonical));
+        assert!(is_native_mint(&native_mint::id()));
+        assert!(!is_native_mint(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_read_version_legacy_account() {
+        let account = Account {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            amount: 7,
+            state: AccountState::Initialized,
+            ..Account::default()
+        };
+        let mut packed = [0u8; Account::LEN];
+        account.pack_into_slice(&mut packed).unwrap();
+
+        // 165 bytes: no version byte
+        assert_eq!(read_version::<Account>(&packed).unwrap(), 0);
+        assert_eq!(Account::unpack_from_slice(&packed).unwrap(), account);
+    }
+
+    #[test]
+    fn test_read_version_versioned_account() {
+        let account = Account {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            state: AccountState::Initialized,
+            memo_required: true,
+            ..Account::default()
+        };
+        let mut packed = [0u8; Account::EXTENDED_LEN];
+        account.pack_into_slice(&mut packed).unwrap();
+
+        assert_eq!(read_version::<Account>(&packed).unwrap(), CURRENT_VERSION);
+        assert_eq!(Account::unpack_from_slice(&packed).unwrap(), account);
+    }
+
+    #[test]
+    fn test_read_version_short_buffer() {
+        assert_eq!(
+            read_version::<Account>(&[0u8; Account::LEN - 1]).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[test]
+    fn test_coption_u64_roundtrip() {
+        for value in [COption::none(), COption::some(0), COption::some(u64::MAX)] {
+            let mut buf = [0xffu8; 12];
+            pack_coption_u64(&value, &mut buf);
+            assert_eq!(unpack_coption_u64(&buf).unwrap(), value);
+        }
+
+        // A corrupt tag is bad account data, not a bad instruction
+        let mut buf = [0u8; 12];
+        buf[0] = 2;
+        assert_eq!(
+            unpack_coption_u64(&buf).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[test]
+    fn test_get_packed_len() {
+        assert_eq!(Mint::get_packed_len(), Mint::LEN);
+        assert_eq!(Account::get_packed_len(), Account::LEN);
+        assert_eq!(Multisig::get_packed_len(), Multisig::LEN);
+    }
+
+    #[test]
+    fn test_unpack_initialized_mint() {
+        let mint = Mint {
+            mint_authority: COption::some(Pubkey::new_unique()),
+            supply: 100,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::none(),
+            non_transferable: false,
+            supply_cap: COption::none(),
+            mint_paused: false,
+            close_authority: COption::none(),
+            transfer_hook: COption::none(),
+        };
+        let mut packed = [0u8; Mint::LEN];
+        mint.pack(&mut packed).unwrap();
+
+        let unpacked: Mint = unpack_initialized(&packed).unwrap();
+        assert_eq!(unpacked, mint);
+
+        // All zeros = uninitialized
+        let zeroed = [0u8; Mint::LEN];
+        assert_eq!(
+            unpack_initialized::<Mint>(&zeroed).unwrap_err(),
+            TokenError::UninitializedAccount.into()
+        );
+    }
+
+    #[test]
+    fn test_unpack_initialized_account() {
+        let account = Account {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            state: AccountState::Initialized,
+            ..Account::default()
+        };
+        let mut packed = [0u8; Account::LEN];
+        account.pack(&mut packed).unwrap();
+
+        let unpacked: Account = unpack_initialized(&packed).unwrap();
+        assert_eq!(unpacked, account);
+
+        let zeroed = [0u8; Account::LEN];
+        assert_eq!(
+            unpack_initialized::<Account>(&zeroed).unwrap_err(),
+            TokenError::UninitializedAccount.into()
+        );
+    }
+
+    #[test]
+    fn test_unpack_initialized_multisig() {
+        let mut signers = [Pubkey::default(); MAX_SIGNERS];
+        signers[0] = Pubkey::new_unique();
+        let multisig = Multisig {
+            m: 1,
+            n: 1,
+            is_initialized: true,
+            signers,
+        };
+        let mut packed = [0u8; Multisig::LEN];
+        multisig.pack(&mut packed).unwrap();
+
+        let unpacked: Multisig = unpack_initialized(&packed).unwrap();
+        assert_eq!(unpacked, multisig);
+
+        let zeroed = [0u8; Multisig::LEN];
+        assert_eq!(
+            unpack_initialized::<Multisig>(&zeroed).unwrap_err(),
+            TokenError::UninitializedAccount.into()
+        );
+    }
+
+    /// A zeroed buffer reads via unpack_unchecked but not unpack_initialized.
+    #[test]
+    fn test_unpack_unchecked_zeroed() {
+        let zeroed = [0u8; Mint::LEN];
+        let mint = Mint::unpack_unchecked(&zeroed).unwrap();
+        assert!(!mint.is_initialized);
+        assert!(unpack_initialized::<Mint>(&zeroed).is_err());
+
+        let zeroed = [0u8; Mint::EXTENDED_LEN];
+        let mint = Mint::unpack_unchecked(&zeroed).unwrap();
+        assert!(!mint.is_initialized);
+        assert!(!mint.has_extensions());
+
+        let zeroed = [0u8; Account::EXTENDED_LEN];
+        let account = Account::unpack_unchecked(&zeroed).unwrap();
+        assert_eq!(account.state, AccountState::Uninitialized);
+        assert!(unpack_initialized::<Account>(&zeroed).is_err());
+
+        let zeroed = [0u8; Multisig::LEN];
+        let multisig = Multisig::unpack_unchecked(&zeroed).unwrap();
+        assert!(!multisig.is_initialized);
+        assert!(unpack_initialized::<Multisig>(&zeroed).is_err());
+    }
+
+    /// Extended layouts must never share a length with another account
+    /// type, since account types are told apart by data length.
+    #[test]
+    fn test_extended_lens_are_distinct() {
+        let base = [Mint::LEN, Account::LEN, Multisig::LEN];
+        assert!(!base.contains(&Mint::EXTENDED_LEN));
+        assert!(!base.contains(&Account::EXTENDED_LEN));
+        assert_ne!(Mint::EXTENDED_LEN, Account::EXTENDED_LEN);
+        assert!(Mint::is_valid_len(Mint::LEN));
+        assert!(Mint::is_valid_len(Mint::EXTENDED_LEN));
+        assert!(!Mint::is_valid_len(Account::LEN));
+        assert!(Account::is_valid_len(Account::LEN));
+        assert!(Account::is_valid_len(Account::EXTENDED_LEN));
+        assert!(!Account::is_valid_len(Multisig::LEN));
+    }
+
+    #[test]
+    fn test_unpack_initialized_wrong_length() {
+        let too_short = [0u8; Mint::LEN - 1];
+        assert_eq!(
+            unpack_initialized::<Mint>(&too_short).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+}
+
 /*
 =============================================================================
 DETAILED EXPLANATION
@@ -351,6 +732,25 @@ Safe wrappers (provided):
 Always use the _slice variants in processors!
 They catch size mismatches early.
 
+get_packed_len() is the method form of LEN, for generic code.
+
+NATIVE MINT
+===========
+
+So11111111111111111111111111111111111111112 is the wrapped SOL mint.
+Token accounts for it set is_native = Some(rent_exempt_reserve) and
+track amount = lamports - reserve. See initialize_account.
+
+ISINITIALIZED TRAIT
+===================
+
+Every state type has some notion of "initialized":
+- Mint, Multisig: is_initialized flag
+- Account: state != Uninitialized
+
+IsInitialized gives them one shared method, so generic helpers
+like unpack_initialized::<T>() work for all three.
+
 THE FROM TRAIT
 ==============
 
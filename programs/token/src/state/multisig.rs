@@ -176,6 +176,70 @@ impl Multisig {
     /// - signers: 11 * 32 = 352 bytes
     /// - Total: 1 + 1 + 1 + 352 = 355 bytes
     pub const LEN: usize = 355;
+
+    /// The valid signers: the first `n` entries of `signers`.
+    ///
+    /// Slots past `n` are ignored, whatever they contain.
+    pub fn valid_signers(&self) -> &[Pubkey] {
+        let n = (self.n as usize).min(MAX_SIGNERS);
+        &self.signers[..n]
+    }
+
+    /// Check if `key` is one of the valid signers.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if multisig.contains_signer(signer_account.key) {
+    ///     valid_signer_count += 1;
+    /// }
+    /// ```
+    pub fn contains_signer(&self, key: &Pubkey) -> bool {
+        self.valid_signers().contains(key)
+    }
+
+    /// How many more valid signatures are needed to reach `m`.
+    ///
+    /// Counts each valid signer in `current_signers` once; unknown
+    /// keys and repeats are ignored, matching on-chain validation.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let remaining = multisig.signatures_remaining(&collected);
+    /// if remaining > 0 {
+    ///     println!("{} more signature(s) needed", remaining);
+    /// }
+    /// ```
+    pub fn signatures_remaining(&self, current_signers: &[Pubkey]) -> u8 {
+        let valid_count = current_signers
+            .iter()
+            .enumerate()
+            .filter(|(index, key)| {
+                self.contains_signer(key) && !current_signers[..*index].contains(key)
+            })
+            .count();
+        let valid_count = u8::try_from(valid_count).unwrap_or(u8::MAX);
+        self.m.saturating_sub(valid_count)
+    }
+
+    /// One-line summary for logs and CLIs.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// 2-of-3 signers=[Abc..., Def..., Ghi...]
+    /// ```
+    pub fn summary(&self) -> String {
+        let signers: Vec<String> = self.valid_signers().iter().map(|k| k.to_string()).collect();
+        format!(
+            "{}-of-{} signers=[{}]{}",
+            self.m,
+            self.n,
+            signers.join(", "),
+            if self.is_initialized { "" } else { " (uninitialized)" },
+        )
+    }
 }
 
 // =============================================================================
@@ -222,7 +286,7 @@ impl Pack for Multisig {
         // We always store 11, but only first `n` are valid
         let mut signers = [Pubkey::default(); MAX_SIGNERS];
 
-        for i in 0..MAX_SIGNERS {
+        for (i, signer) in signers.iter_mut().enumerate() {
             // Calculate byte offset for this signer
             // Offset = 3 (header) + i * 32 (pubkey size)
             let start = 3 + i * 32;
@@ -234,7 +298,7 @@ impl Pack for Multisig {
                 .map_err(|_| ProgramError::InvalidAccountData)?;
 
             // Convert to Pubkey
-            signers[i] = Pubkey::new_from_array(pubkey_bytes);
+            *signer = Pubkey::new_from_array(pubkey_bytes);
         }
 
         Ok(Multisig {
@@ -323,8 +387,8 @@ mod tests {
     #[test]
     fn test_multisig_max_signers() {
         let mut signers = [Pubkey::default(); MAX_SIGNERS];
-        for i in 0..MAX_SIGNERS {
-            signers[i] = Pubkey::new_unique();
+        for signer in signers.iter_mut() {
+            *signer = Pubkey::new_unique();
         }
 
         let multisig = Multisig {
@@ -343,6 +407,51 @@ mod tests {
         assert_eq!(multisig.n, unpacked.n);
     }
 
+    /// 2-of-3 multisig used by the signatures_remaining tests.
+    fn two_of_three() -> Multisig {
+        let mut signers = [Pubkey::default(); MAX_SIGNERS];
+        signers[0] = Pubkey::new_unique();
+        signers[1] = Pubkey::new_unique();
+        signers[2] = Pubkey::new_unique();
+
+        Multisig {
+            m: 2,
+            n: 3,
+            is_initialized: true,
+            signers,
+        }
+    }
+
+    /// Test signatures_remaining with no signatures collected.
+    #[test]
+    fn test_signatures_remaining_none_collected() {
+        let multisig = two_of_three();
+        assert_eq!(multisig.signatures_remaining(&[]), 2);
+
+        // Keys outside the multisig don't count
+        assert_eq!(multisig.signatures_remaining(&[Pubkey::new_unique()]), 2);
+    }
+
+    /// Test signatures_remaining with a partial collection.
+    #[test]
+    fn test_signatures_remaining_partial() {
+        let multisig = two_of_three();
+        let first = multisig.signers[0];
+        assert_eq!(multisig.signatures_remaining(&[first]), 1);
+
+        // A repeated signer counts once
+        assert_eq!(multisig.signatures_remaining(&[first, first]), 1);
+    }
+
+    /// Test signatures_remaining once the threshold is met.
+    #[test]
+    fn test_signatures_remaining_enough_collected() {
+        let multisig = two_of_three();
+        let signers = multisig.valid_signers().to_vec();
+        assert_eq!(multisig.signatures_remaining(&signers[..2]), 0);
+        assert_eq!(multisig.signatures_remaining(&signers), 0);
+    }
+
     /// Test invalid: m > n.
     #[test]
     fn test_multisig_invalid_m_greater_than_n() {
@@ -397,6 +506,141 @@ mod tests {
         assert_eq!(multisig.n, 0);
         assert!(!multisig.is_initialized);
     }
+
+    /// Test m/n/is_initialized boundaries.
+    ///
+    /// m = 0 is only rejected once the multisig is initialized,
+    /// while m > n is rejected regardless.
+    #[test]
+    fn test_multisig_m_n_initialized_boundaries() {
+        // Uninitialized, m = 0, n = 0: all zeros, valid
+        let packed = [0u8; Multisig::LEN];
+        assert!(Multisig::unpack(&packed).is_ok());
+
+        // Uninitialized, m = 0, n = 3: the m >= 1 rule doesn't apply yet
+        let mut packed = [0u8; Multisig::LEN];
+        packed[1] = 3;
+        let multisig = Multisig::unpack(&packed).unwrap();
+        assert_eq!(multisig.m, 0);
+        assert_eq!(multisig.n, 3);
+        assert!(!multisig.is_initialized);
+
+        // Initialized, m = 0, n = 3: rejected
+        let mut packed = [0u8; Multisig::LEN];
+        packed[0] = 0;
+        packed[1] = 3;
+        packed[2] = 1;
+        assert_eq!(
+            Multisig::unpack(&packed).unwrap_err(),
+            TokenError::InvalidMultisigConfig.into()
+        );
+
+        // Initialized, m = 2, n = 0: rejected (m > n)
+        let mut packed = [0u8; Multisig::LEN];
+        packed[0] = 2;
+        packed[1] = 0;
+        packed[2] = 1;
+        assert_eq!(
+            Multisig::unpack(&packed).unwrap_err(),
+            TokenError::InvalidMultisigConfig.into()
+        );
+    }
+
+    /// Test summary output.
+    #[test]
+    fn test_multisig_summary() {
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let mut signers = [Pubkey::default(); MAX_SIGNERS];
+        signers[0] = alice;
+        signers[1] = bob;
+
+        let multisig = Multisig {
+            m: 2,
+            n: 2,
+            is_initialized: true,
+            signers,
+        };
+
+        assert_eq!(multisig.summary(), format!("2-of-2 signers=[{}, {}]", alice, bob));
+        assert_eq!(Multisig::default().summary(), "0-of-0 signers=[] (uninitialized)");
+    }
+
+    /// Test contains_signer / valid_signers.
+    #[test]
+    fn test_multisig_contains_signer() {
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let stale = Pubkey::new_unique();
+        let outsider = Pubkey::new_unique();
+
+        let mut signers = [Pubkey::default(); MAX_SIGNERS];
+        signers[0] = alice;
+        signers[1] = bob;
+        signers[2] = stale; // Beyond n: garbage slot
+
+        let multisig = Multisig {
+            m: 1,
+            n: 2,
+            is_initialized: true,
+            signers,
+        };
+
+        assert_eq!(multisig.valid_signers(), &[alice, bob]);
+
+        // Present
+        assert!(multisig.contains_signer(&alice));
+        assert!(multisig.contains_signer(&bob));
+
+        // Absent
+        assert!(!multisig.contains_signer(&outsider));
+
+        // Beyond the n boundary: must not match
+        assert!(!multisig.contains_signer(&stale));
+        assert!(!multisig.contains_signer(&Pubkey::default()));
+    }
+
+    // =========================================================================
+    // PROPERTY TESTS
+    // =========================================================================
+
+    use proptest::prelude::*;
+
+    /// Any valid multisig: 1 <= m <= n <= 11, with the first n signers
+    /// random and the unused slots left at the default key.
+    fn arb_multisig() -> impl Strategy<Value = Multisig> {
+        (1..=MAX_SIGNERS as u8)
+            .prop_flat_map(|n| {
+                (
+                    1..=n,
+                    Just(n),
+                    any::<bool>(),
+                    proptest::collection::vec(any::<[u8; 32]>(), n as usize),
+                )
+            })
+            .prop_map(|(m, n, is_initialized, keys)| {
+                let mut signers = [Pubkey::default(); MAX_SIGNERS];
+                for (signer, key) in signers.iter_mut().zip(keys) {
+                    *signer = Pubkey::new_from_array(key);
+                }
+                Multisig {
+                    m,
+                    n,
+                    is_initialized,
+                    signers,
+                }
+            })
+    }
+
+    proptest! {
+        /// Valid multisigs survive pack then unpack.
+        #[test]
+        fn prop_multisig_roundtrip(multisig in arb_multisig()) {
+            let mut packed = [0u8; Multisig::LEN];
+            multisig.pack(&mut packed).unwrap();
+            prop_assert_eq!(Multisig::unpack(&packed).unwrap(), multisig);
+        }
+    }
 }
 
 /*
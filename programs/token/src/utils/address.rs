@@ -0,0 +1,89 @@
+//! Token Account Addresses
+//!
+//! Derives associated-token-account-style addresses: one canonical
+//! token account per (wallet, mint), found with `find_program_address`.
+//!
+//! This crate isn't the Associated Token Account program, so the
+//! addresses differ from real ATAs. The derivation follows the same
+//! convention, with seeds `[wallet, token_program_id, mint]`, where
+//! `program_id` is both the token program in the seeds and the
+//! program the address is derived under.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let (address, bump) = get_account_address(&wallet, &mint, &spl_token_from_scratch::id());
+//! ```
+
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
+
+/// Derive the canonical token account address for `wallet` and `mint`.
+///
+/// Returns the address and its bump seed.
+pub fn get_account_address(wallet: &Pubkey, mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), program_id.as_ref(), mint.as_ref()],
+        program_id,
+    )
+}
+
+/// Assert that `expected` is the canonical address for `wallet` and `mint`.
+///
+/// # Errors
+///
+/// * `InvalidSeeds` - `expected` isn't the derived address
+pub fn assert_account_address(
+    expected: &Pubkey,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let (address, _bump) = get_account_address(wallet, mint, program_id);
+    if address != *expected {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(())
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_account_address_deterministic() {
+        let wallet = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let program_id = crate::id();
+
+        let first = get_account_address(&wallet, &mint, &program_id);
+        let second = get_account_address(&wallet, &mint, &program_id);
+        assert_eq!(first, second);
+
+        // Off-curve, so no private key can sign for it
+        assert!(!first.0.is_on_curve());
+
+        // Any input change gives a different address
+        let other_wallet = get_account_address(&Pubkey::new_unique(), &mint, &program_id);
+        let other_mint = get_account_address(&wallet, &Pubkey::new_unique(), &program_id);
+        assert_ne!(first.0, other_wallet.0);
+        assert_ne!(first.0, other_mint.0);
+    }
+
+    #[test]
+    fn test_assert_account_address() {
+        let wallet = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let program_id = crate::id();
+        let (address, _) = get_account_address(&wallet, &mint, &program_id);
+
+        assert!(assert_account_address(&address, &wallet, &mint, &program_id).is_ok());
+        assert_eq!(
+            assert_account_address(&address, &mint, &wallet, &program_id).unwrap_err(),
+            ProgramError::InvalidSeeds
+        );
+    }
+}
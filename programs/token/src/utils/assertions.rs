@@ -18,12 +18,16 @@
 //! ```
 
 use crate::error::TokenError;
+use crate::state::{Account, AccountState, Mint, Multisig};
 use solana_program::{
     account_info::AccountInfo,
+    clock::Clock,
     entrypoint::ProgramResult,
+    msg,
     program_error::ProgramError,
     pubkey::Pubkey,
     rent::Rent,
+    sysvar::{self, Sysvar},
 };
 
 // =============================================================================
@@ -162,6 +166,34 @@ pub fn assert_data_length(account: &AccountInfo, expected: usize) -> ProgramResu
     }
 }
 
+/// Assert that an account is a valid mint size (base or extended).
+///
+/// # Errors
+///
+/// Returns `InvalidAccountDataLength` unless the length is
+/// `Mint::LEN` or `Mint::EXTENDED_LEN`.
+pub fn assert_mint_data_length(account: &AccountInfo) -> ProgramResult {
+    if !Mint::is_valid_len(account.data_len()) {
+        Err(TokenError::InvalidAccountDataLength.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Assert that an account is a valid token account size (base or extended).
+///
+/// # Errors
+///
+/// Returns `InvalidAccountDataLength` unless the length is
+/// `Account::LEN` or `Account::EXTENDED_LEN`.
+pub fn assert_account_data_length(account: &AccountInfo) -> ProgramResult {
+    if !Account::is_valid_len(account.data_len()) {
+        Err(TokenError::InvalidAccountDataLength.into())
+    } else {
+        Ok(())
+    }
+}
+
 // =============================================================================
 // RENT CHECKS
 // =============================================================================
@@ -196,6 +228,327 @@ pub fn assert_rent_exempt(rent: &Rent, account: &AccountInfo) -> ProgramResult {
     }
 }
 
+// =============================================================================
+// FRESH ACCOUNT CHECKS (fresh-account-check feature)
+// =============================================================================
+
+/// Assert that `account_key` was created earlier in this transaction.
+///
+/// # Why This Matters
+///
+/// If account creation and initialization happen in separate
+/// transactions, an attacker who sees the new (uninitialized)
+/// account can front-run the init and claim it with their own owner.
+///
+/// # How
+///
+/// Scans the instructions sysvar for a System Program CreateAccount
+/// or CreateAccountWithSeed that runs before the current instruction
+/// and targets `account_key`.
+///
+/// # Errors
+///
+/// * `AccountNotFreshlyCreated` - No such create instruction found
+/// * `InvalidArgument` - `instructions_info` isn't the instructions sysvar
+#[cfg(feature = "fresh-account-check")]
+pub fn assert_freshly_created(account_key: &Pubkey, instructions_info: &AccountInfo) -> ProgramResult {
+    use solana_program::{
+        system_program,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    };
+
+    // SystemInstruction tags (u32 little-endian) whose account 1 is the new account
+    const CREATE_ACCOUNT: u32 = 0;
+    const CREATE_ACCOUNT_WITH_SEED: u32 = 3;
+
+    let current_index = load_current_index_checked(instructions_info)?;
+
+    for index in 0..current_index {
+        let instruction = load_instruction_at_checked(index as usize, instructions_info)?;
+
+        if instruction.program_id != system_program::id() || instruction.data.len() < 4 {
+            continue;
+        }
+
+        let tag = u32::from_le_bytes([
+            instruction.data[0],
+            instruction.data[1],
+            instruction.data[2],
+            instruction.data[3],
+        ]);
+        if tag != CREATE_ACCOUNT && tag != CREATE_ACCOUNT_WITH_SEED {
+            continue;
+        }
+
+        if instruction.accounts.get(1).map(|meta| &meta.pubkey) == Some(account_key) {
+            return Ok(());
+        }
+    }
+
+    Err(TokenError::AccountNotFreshlyCreated.into())
+}
+
+// =============================================================================
+// CPI GUARD CHECKS (cpi-guard feature)
+// =============================================================================
+
+/// Assert that the running instruction is a top-level call.
+///
+/// # Why This Matters
+///
+/// A signer who approves a transaction for some other program also
+/// lends it their signature for any CPI it makes. Requiring sensitive
+/// instructions to be top-level means they only run when the signer
+/// addressed this program directly.
+///
+/// # How
+///
+/// The runtime tracks the invoke stack height: top-level instructions run
+/// at `TRANSACTION_LEVEL_STACK_HEIGHT` and every CPI adds one. Checking
+/// the height catches any depth of CPI, including a call back into this
+/// program from a program it invoked.
+///
+/// # Errors
+///
+/// * `CpiGuardViolation` - The instruction was reached through CPI
+#[cfg(feature = "cpi-guard")]
+pub fn assert_top_level_call() -> ProgramResult {
+    use solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+
+    if get_stack_height() != TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Err(TokenError::CpiGuardViolation.into());
+    }
+    Ok(())
+}
+
+// =============================================================================
+// MEMO CHECKS
+// =============================================================================
+
+/// SPL Memo program ids (current and legacy v1).
+pub mod spl_memo {
+    solana_program::declare_id!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+    /// Legacy Memo v1 program id.
+    pub mod v1 {
+        solana_program::declare_id!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+    }
+}
+
+/// Assert that a Memo program instruction runs earlier in this transaction.
+///
+/// Used for destinations that require a memo on inbound transfers.
+/// Scans the instructions sysvar for any Memo (current or v1)
+/// instruction before the current one.
+///
+/// # Errors
+///
+/// * `MemoRequired` - No memo instruction before this one
+/// * `InvalidArgument` - `instructions_info` isn't the instructions sysvar
+pub fn assert_memo_precedes(instructions_info: &AccountInfo) -> ProgramResult {
+    use solana_program::sysvar::instructions::{
+        load_current_index_checked, load_instruction_at_checked,
+    };
+
+    let current_index = load_current_index_checked(instructions_info)?;
+
+    for index in 0..current_index {
+        let instruction = load_instruction_at_checked(index as usize, instructions_info)?;
+        if spl_memo::check_id(&instruction.program_id)
+            || spl_memo::v1::check_id(&instruction.program_id)
+        {
+            return Ok(());
+        }
+    }
+
+    Err(TokenError::MemoRequired.into())
+}
+
+// =============================================================================
+// INITIALIZATION CHECKS
+// =============================================================================
+
+/// Assert that a token account hasn't been initialized yet.
+///
+/// A frozen account counts as initialized.
+///
+/// # Errors
+///
+/// * `AlreadyInitialized` - The account is initialized or frozen
+///
+/// # Example
+///
+/// ```ignore
+/// let account = Account::unpack_unchecked(&account_info.data.borrow())?;
+/// assert_uninitialized_account(&account)?;
+/// ```
+pub fn assert_uninitialized_account(account: &Account) -> ProgramResult {
+    if account.state != AccountState::Uninitialized {
+        return Err(TokenError::AlreadyInitialized.into());
+    }
+    Ok(())
+}
+
+/// Assert that a mint hasn't been initialized yet.
+///
+/// # Errors
+///
+/// * `AlreadyInitialized` - The mint is initialized
+pub fn assert_uninitialized_mint(mint: &Mint) -> ProgramResult {
+    if mint.is_initialized {
+        return Err(TokenError::AlreadyInitialized.into());
+    }
+    Ok(())
+}
+
+/// Assert that a multisig hasn't been initialized yet.
+///
+/// # Errors
+///
+/// * `AlreadyInitialized` - The multisig is initialized
+pub fn assert_uninitialized_multisig(ms: &Multisig) -> ProgramResult {
+    if ms.is_initialized {
+        return Err(TokenError::AlreadyInitialized.into());
+    }
+    Ok(())
+}
+
+// =============================================================================
+// FREEZE CHECKS
+// =============================================================================
+
+/// Assert that a token account isn't frozen.
+///
+/// # Errors
+///
+/// * `AccountFrozen` - The account is frozen
+///
+/// # Example
+///
+/// ```ignore
+/// assert_not_frozen(&source)?;
+/// ```
+pub fn assert_not_frozen(account: &Account) -> ProgramResult {
+    if account.is_frozen() {
+        return Err(TokenError::AccountFrozen.into());
+    }
+    Ok(())
+}
+
+// =============================================================================
+// DELEGATE EXPIRY CHECKS
+// =============================================================================
+
+/// Assert that a token account's delegate approval hasn't expired.
+///
+/// Approvals without an expiry always pass and need no extra account.
+/// Otherwise the Clock sysvar must be among `accounts`, and the approval
+/// holds up to and including its expiry timestamp.
+///
+/// # Errors
+///
+/// * `NotEnoughAccountKeys` - The approval expires but no Clock sysvar was passed
+/// * `DelegateExpired` - The Clock's unix timestamp is past the expiry
+///
+/// # Example
+///
+/// ```ignore
+/// assert_delegate_not_expired(&source, &signer_accounts)?;
+/// ```
+pub fn assert_delegate_not_expired(account: &Account, accounts: &[AccountInfo]) -> ProgramResult {
+    let expiry = match account.delegate_expiry.as_ref() {
+        Some(expiry) => *expiry,
+        None => return Ok(()),
+    };
+
+    let clock_info = accounts
+        .iter()
+        .find(|info| sysvar::clock::check_id(info.key))
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let clock = Clock::from_account_info(clock_info)?;
+
+    if clock.unix_timestamp > expiry {
+        return Err(TokenError::DelegateExpired.into());
+    }
+    Ok(())
+}
+
+// =============================================================================
+// MINT CHECKS
+// =============================================================================
+
+/// Assert that a token account belongs to `mint`.
+///
+/// # Errors
+///
+/// * `MintMismatch` - `account.mint` differs from `mint`
+///
+/// # Example
+///
+/// ```ignore
+/// assert_account_mint(&account, mint_info.key)?;
+/// ```
+pub fn assert_account_mint(account: &Account, mint: &Pubkey) -> ProgramResult {
+    if account.mint != *mint {
+        return Err(TokenError::MintMismatch.into());
+    }
+    Ok(())
+}
+
+/// Assert that every account in a batch belongs to `expected_mint`.
+///
+/// Logs the index of the first offender so a failed batch is easy
+/// to debug.
+///
+/// # Errors
+///
+/// * `MintMismatch` - An account's mint differs from `expected_mint`
+///
+/// # Example
+///
+/// ```ignore
+/// assert_same_mint(&destinations, &source.mint)?;
+/// ```
+pub fn assert_same_mint(accounts: &[Account], expected_mint: &Pubkey) -> ProgramResult {
+    for (index, account) in accounts.iter().enumerate() {
+        if account.mint != *expected_mint {
+            msg!("Account {} has the wrong mint", index);
+            return Err(TokenError::MintMismatch.into());
+        }
+    }
+    Ok(())
+}
+
+// =============================================================================
+// SELF-TRANSFER CHECKS
+// =============================================================================
+
+/// Check whether a transfer's source and destination are the same account.
+///
+/// # Semantics Per Instruction
+///
+/// | Instruction | Same source and destination |
+/// |-------------|-----------------------------|
+/// | Transfer | Rejected with `SelfTransfer` |
+/// | TransferBatch | Rejected with `SelfTransfer` (any destination) |
+/// | TransferWithFee | Rejected with `SelfTransfer` (destination or fee account) |
+/// | MintTo, Burn | No self concept: one token account, no counterparty |
+///
+/// Comparing keys, not unpacked state, matters: two `Account` copies
+/// of the same account would otherwise be credited and debited
+/// independently, and the last write would win.
+///
+/// # Example
+///
+/// ```ignore
+/// if is_self_transfer(source_info.key, dest_info.key) {
+///     return Err(TokenError::SelfTransfer.into());
+/// }
+/// ```
+pub fn is_self_transfer(source: &Pubkey, dest: &Pubkey) -> bool {
+    source == dest
+}
+
 // =============================================================================
 // CHECKED ARITHMETIC
 // =============================================================================
@@ -259,6 +612,38 @@ pub fn checked_sub(a: u64, b: u64) -> Result<u64, ProgramError> {
         .ok_or_else(|| TokenError::InsufficientFunds.into())
 }
 
+/// Checked multiplication that returns a clear error on overflow.
+///
+/// # Returns
+///
+/// * `Ok(a * b)` - If no overflow
+/// * `Err(Overflow)` - If overflow would occur
+///
+/// # Example
+///
+/// ```ignore
+/// let raw = checked_mul(whole_tokens, 10u64.pow(decimals as u32))?;
+/// ```
+pub fn checked_mul(a: u64, b: u64) -> Result<u64, ProgramError> {
+    a.checked_mul(b).ok_or_else(|| TokenError::Overflow.into())
+}
+
+/// Checked division (rounds toward zero).
+///
+/// # Returns
+///
+/// * `Ok(a / b)` - If `b != 0`
+/// * `Err(Overflow)` - If `b == 0`
+///
+/// # Example
+///
+/// ```ignore
+/// let per_account = checked_div(total, account_count)?;
+/// ```
+pub fn checked_div(a: u64, b: u64) -> Result<u64, ProgramError> {
+    a.checked_div(b).ok_or_else(|| TokenError::Overflow.into())
+}
+
 // =============================================================================
 // UNIT TESTS
 // =============================================================================
@@ -266,6 +651,150 @@ pub fn checked_sub(a: u64, b: u64) -> Result<u64, ProgramError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::COption;
+
+    fn account_for(mint: Pubkey) -> Account {
+        Account {
+            mint,
+            ..Account::default()
+        }
+    }
+
+    #[test]
+    fn test_assert_not_frozen() {
+        let mut account = Account {
+            state: AccountState::Initialized,
+            ..Account::default()
+        };
+        assert!(assert_not_frozen(&account).is_ok());
+
+        account.state = AccountState::Frozen;
+        assert_eq!(
+            assert_not_frozen(&account).unwrap_err(),
+            TokenError::AccountFrozen.into()
+        );
+    }
+
+    #[test]
+    fn test_assert_delegate_not_expired() {
+        let mut account = Account::default();
+
+        // No expiry: passes without a clock
+        assert!(assert_delegate_not_expired(&account, &[]).is_ok());
+
+        // An expiry needs the Clock sysvar
+        account.delegate_expiry = COption::some(100);
+        assert_eq!(
+            assert_delegate_not_expired(&account, &[]).unwrap_err(),
+            ProgramError::NotEnoughAccountKeys
+        );
+
+        // Clock layout: slot, epoch_start_timestamp, epoch,
+        // leader_schedule_epoch, unix_timestamp (8 bytes each)
+        let clock_id = sysvar::clock::id();
+        let sysvar_owner = sysvar::id();
+        for (now, expired) in [(99i64, false), (100, false), (101, true)] {
+            let mut data = [0u8; 40];
+            data[32..].copy_from_slice(&now.to_le_bytes());
+            let mut lamports = 0u64;
+            let clock_info = AccountInfo::new(
+                &clock_id,
+                false,
+                false,
+                &mut lamports,
+                &mut data,
+                &sysvar_owner,
+                false,
+                0,
+            );
+
+            let result = assert_delegate_not_expired(&account, &[clock_info]);
+            if expired {
+                assert_eq!(result.unwrap_err(), TokenError::DelegateExpired.into());
+            } else {
+                assert!(result.is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_assert_uninitialized_account() {
+        let mut account = Account::default();
+        assert!(assert_uninitialized_account(&account).is_ok());
+
+        for state in [AccountState::Initialized, AccountState::Frozen] {
+            account.state = state;
+            assert_eq!(
+                assert_uninitialized_account(&account).unwrap_err(),
+                TokenError::AlreadyInitialized.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_assert_uninitialized_mint() {
+        let mut mint = Mint::default();
+        assert!(assert_uninitialized_mint(&mint).is_ok());
+
+        mint.is_initialized = true;
+        assert_eq!(
+            assert_uninitialized_mint(&mint).unwrap_err(),
+            TokenError::AlreadyInitialized.into()
+        );
+    }
+
+    #[test]
+    fn test_assert_uninitialized_multisig() {
+        let mut ms = Multisig::default();
+        assert!(assert_uninitialized_multisig(&ms).is_ok());
+
+        ms.is_initialized = true;
+        assert_eq!(
+            assert_uninitialized_multisig(&ms).unwrap_err(),
+            TokenError::AlreadyInitialized.into()
+        );
+    }
+
+    #[test]
+    fn test_assert_account_mint_matches() {
+        let mint = Pubkey::new_unique();
+
+        assert!(assert_account_mint(&account_for(mint), &mint).is_ok());
+    }
+
+    #[test]
+    fn test_assert_account_mint_mismatch() {
+        let mint = Pubkey::new_unique();
+
+        assert_eq!(
+            assert_account_mint(&account_for(Pubkey::new_unique()), &mint).unwrap_err(),
+            TokenError::MintMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_assert_same_mint_all_match() {
+        let mint = Pubkey::new_unique();
+        let accounts = [account_for(mint), account_for(mint), account_for(mint)];
+
+        assert!(assert_same_mint(&accounts, &mint).is_ok());
+        assert!(assert_same_mint(&[], &mint).is_ok());
+    }
+
+    #[test]
+    fn test_assert_same_mint_mismatch_in_middle() {
+        let mint = Pubkey::new_unique();
+        let accounts = [
+            account_for(mint),
+            account_for(Pubkey::new_unique()),
+            account_for(mint),
+        ];
+
+        assert_eq!(
+            assert_same_mint(&accounts, &mint).unwrap_err(),
+            TokenError::MintMismatch.into()
+        );
+    }
 
     #[test]
     fn test_checked_add_success() {
@@ -292,6 +821,29 @@ mod tests {
         assert!(checked_sub(0, 1).is_err());
         assert!(checked_sub(100, 101).is_err());
     }
+
+    #[test]
+    fn test_is_self_transfer() {
+        let source = Pubkey::new_unique();
+        let dest = Pubkey::new_unique();
+
+        assert!(is_self_transfer(&source, &source));
+        assert!(!is_self_transfer(&source, &dest));
+    }
+
+    #[test]
+    fn test_checked_mul() {
+        assert_eq!(checked_mul(10_000, 100).unwrap(), 1_000_000);
+        assert_eq!(checked_mul(u64::MAX, 0).unwrap(), 0);
+        assert_eq!(checked_mul(u64::MAX, 2).unwrap_err(), TokenError::Overflow.into());
+    }
+
+    #[test]
+    fn test_checked_div() {
+        assert_eq!(checked_div(1_000_000, 10_000).unwrap(), 100);
+        assert_eq!(checked_div(9_999, 10_000).unwrap(), 0);
+        assert_eq!(checked_div(1, 0).unwrap_err(), TokenError::Overflow.into());
+    }
 }
 
 /*
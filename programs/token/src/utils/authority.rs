@@ -28,7 +28,7 @@
 //! )?;
 //!
 //! // Validate owner OR delegate
-//! let used_delegate = validate_owner_or_delegate(
+//! let kind = validate_owner_or_delegate_kind(
 //!     program_id,
 //!     &account.owner,
 //!     account.delegate.as_ref(),
@@ -38,7 +38,7 @@
 //! ```
 
 use crate::error::TokenError;
-use crate::state::{Multisig, Pack};
+use crate::state::{Multisig, Pack, MAX_SIGNERS};
 use solana_program::{
     account_info::AccountInfo,
     entrypoint::ProgramResult,
@@ -199,6 +199,7 @@ fn validate_single_signer(
 /// A signer is counted if:
 /// - The account has `is_signer = true` (actually signed)
 /// - The account's pubkey is in `multisig.signers[0..n]`
+/// - The same pubkey hasn't already been counted
 ///
 /// # Arguments
 ///
@@ -249,21 +250,23 @@ fn validate_multisig(
     // =========================================================================
     let mut valid_signer_count: u8 = 0;
 
-    for signer_account in signer_accounts {
+    for (index, signer_account) in signer_accounts.iter().enumerate() {
         // Skip accounts that didn't actually sign
         if !signer_account.is_signer {
             continue;
         }
 
-        // Check if this signer is in the multisig's signer list
-        // Only check the first `n` signers (the valid ones)
-        let is_valid_signer = multisig
-            .signers
+        // Count each key once, even if it's passed more than once
+        if signer_accounts[..index]
             .iter()
-            .take(multisig.n as usize)
-            .any(|stored_signer| stored_signer == signer_account.key);
+            .any(|earlier| earlier.key == signer_account.key)
+        {
+            continue;
+        }
 
-        if is_valid_signer {
+        // Check if this signer is in the multisig's signer list
+        // (only the first `n` signers are valid)
+        if multisig.contains_signer(signer_account.key) {
             // Increment counter with overflow protection
             valid_signer_count = valid_signer_count
                 .checked_add(1)
@@ -285,28 +288,47 @@ fn validate_multisig(
 // OWNER OR DELEGATE VALIDATION
 // =============================================================================
 
+/// Which authority approved an owner-or-delegate operation.
+///
+/// Returned by `validate_owner_or_delegate_kind` so call sites can
+/// `match` on the path taken instead of interpreting a bare bool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthorityKind {
+    /// The token account's owner signed
+    Owner,
+    /// The approved delegate signed
+    Delegate,
+}
+
 /// Validate owner or delegate authority for token account operations.
 ///
 /// Many operations (Transfer, Burn) can be authorized by either:
 /// - The token account's owner
 /// - An approved delegate
 ///
-/// This function tries owner first, then delegate.
+/// The path is picked by key: if `authority_info` is the owner, only
+/// the owner path runs and its own error (e.g. `NotEnoughSigners` for
+/// an under-signed multisig) is returned. The delegate path is only
+/// tried for a key that isn't the owner.
+///
+/// Either path goes through `validate_authority`, so an owner or a
+/// delegate that is a multisig account needs its M signers.
 ///
 /// # Returns
 ///
-/// * `Ok(false)` - Owner authority was used
-/// * `Ok(true)` - Delegate authority was used
+/// * `Ok(AuthorityKind::Owner)` - Owner authority was used
+/// * `Ok(AuthorityKind::Delegate)` - Delegate authority was used
 /// * `Err(InvalidAuthority)` - Neither owner nor delegate
+/// * Any `validate_authority` error from the matching path
 ///
-/// # Why Return a bool?
+/// # Why Return the Kind?
 ///
 /// When delegate is used, the caller needs to:
 /// 1. Check `delegated_amount >= amount`
 /// 2. Decrement `delegated_amount`
 /// 3. Clear delegate if `delegated_amount == 0`
 ///
-/// The bool tells the caller which path was taken.
+/// The kind tells the caller which path was taken.
 ///
 /// # Arguments
 ///
@@ -319,59 +341,60 @@ fn validate_multisig(
 /// # Example
 ///
 /// ```ignore
-/// let used_delegate = validate_owner_or_delegate(
+/// match validate_owner_or_delegate_kind(
 ///     program_id,
 ///     &source.owner,
 ///     source.delegate.as_ref(),
 ///     authority_info,
 ///     &signer_accounts,
-/// )?;
-///
-/// if used_delegate {
-///     // Check and decrement delegated_amount
-///     if source.delegated_amount < amount {
-///         return Err(TokenError::InsufficientDelegatedAmount.into());
-///     }
-///     source.delegated_amount -= amount;
-///     if source.delegated_amount == 0 {
-///         source.delegate = COption::none();
+/// )? {
+///     AuthorityKind::Owner => {}
+///     AuthorityKind::Delegate => {
+///         // Check and decrement delegated_amount
+///         if source.delegated_amount < amount {
+///             return Err(TokenError::InsufficientDelegatedAmount.into());
+///         }
+///         source.delegated_amount -= amount;
+///         if source.delegated_amount == 0 {
+///             source.delegate = COption::none();
+///         }
 ///     }
 /// }
 /// ```
-pub fn validate_owner_or_delegate(
+pub fn validate_owner_or_delegate_kind(
     program_id: &Pubkey,
     account_owner: &Pubkey,
     account_delegate: Option<&Pubkey>,
     authority_info: &AccountInfo,
     signer_accounts: &[AccountInfo],
-) -> Result<bool, ProgramError> {
+) -> Result<AuthorityKind, ProgramError> {
     // =========================================================================
-    // TRY 1: Validate as owner
+    // PATH 1: Authority is the owner
     // =========================================================================
-    if validate_authority(
-        program_id,
-        account_owner,
-        authority_info,
-        signer_accounts,
-    )
-    .is_ok()
-    {
-        return Ok(false); // false = owner was used
+    // Don't fall through to the delegate on failure; that would hide
+    // the owner path's real error.
+    if authority_info.key == account_owner {
+        validate_authority(
+            program_id,
+            account_owner,
+            authority_info,
+            signer_accounts,
+        )?;
+        return Ok(AuthorityKind::Owner);
     }
 
     // =========================================================================
-    // TRY 2: Validate as delegate (if present)
+    // PATH 2: Authority is the delegate (if present)
     // =========================================================================
     if let Some(delegate) = account_delegate {
-        if validate_authority(
-            program_id,
-            delegate,
-            authority_info,
-            signer_accounts,
-        )
-        .is_ok()
-        {
-            return Ok(true); // true = delegate was used
+        if authority_info.key == delegate {
+            validate_authority(
+                program_id,
+                delegate,
+                authority_info,
+                signer_accounts,
+            )?;
+            return Ok(AuthorityKind::Delegate);
         }
     }
 
@@ -381,6 +404,88 @@ pub fn validate_owner_or_delegate(
     Err(TokenError::InvalidAuthority.into())
 }
 
+/// Validate owner or delegate authority, returning whether the delegate was used.
+///
+/// # Returns
+///
+/// * `Ok(false)` - Owner authority was used
+/// * `Ok(true)` - Delegate authority was used
+/// * `Err(InvalidAuthority)` - Neither owner nor delegate
+#[deprecated(note = "use validate_owner_or_delegate_kind, which returns an AuthorityKind")]
+pub fn validate_owner_or_delegate(
+    program_id: &Pubkey,
+    account_owner: &Pubkey,
+    account_delegate: Option<&Pubkey>,
+    authority_info: &AccountInfo,
+    signer_accounts: &[AccountInfo],
+) -> Result<bool, ProgramError> {
+    let kind = validate_owner_or_delegate_kind(
+        program_id,
+        account_owner,
+        account_delegate,
+        authority_info,
+        signer_accounts,
+    )?;
+    Ok(kind == AuthorityKind::Delegate)
+}
+
+// =============================================================================
+// TRANSACTION SIZING
+// =============================================================================
+
+/// Maximum number of accounts a transaction can lock.
+pub const MAX_TRANSACTION_ACCOUNTS: usize = 64;
+
+/// Maximum serialized size of a transaction, in bytes.
+///
+/// The IPv6 minimum MTU (1280) minus the IPv6 (40) and fragment (8)
+/// headers, as `solana_sdk::packet::PACKET_DATA_SIZE`.
+pub const PACKET_DATA_SIZE: usize = 1232;
+
+/// Accounts every multisig Transfer transaction needs besides the signers:
+/// fee payer, token program, source, destination, the multisig and the mint.
+pub const TRANSFER_FIXED_ACCOUNTS: usize = 6;
+
+/// Serialized bytes of a multisig Transfer transaction before any signer.
+///
+/// Every count is a one-byte compact-u16 while it stays under 128.
+const TRANSFER_FIXED_BYTES: usize = 1 // signature count
+    + 64 // fee payer signature
+    + 3 // message header
+    + 1 // account key count
+    + TRANSFER_FIXED_ACCOUNTS * 32 // account keys
+    + 32 // recent blockhash
+    + 1 // instruction count
+    + 1 // program id index
+    + 1 // instruction account count
+    + 4 // source, destination, multisig and mint indices
+    + 1 // instruction data length
+    + 9; // Transfer data: discriminant + u64 amount
+
+/// Serialized bytes each multisig signer adds: its signature, its account
+/// key and its index in the Transfer instruction.
+const TRANSFER_BYTES_PER_SIGNER: usize = 64 + 32 + 1;
+
+/// How many multisig signers fit in a single Transfer transaction.
+///
+/// The packet size is the binding limit: a Transfer with 9 signers
+/// serializes to 1183 bytes, a 10th would take it to 1280. The account
+/// limit and `MAX_SIGNERS` are applied too, so the result stays correct
+/// if the packet size ever grows.
+///
+/// # Example
+///
+/// ```ignore
+/// if signers.len() > max_multisig_signers_for_transfer() {
+///     return Err("too many signers for one transaction");
+/// }
+/// ```
+pub fn max_multisig_signers_for_transfer() -> usize {
+    let by_size = (PACKET_DATA_SIZE - TRANSFER_FIXED_BYTES) / TRANSFER_BYTES_PER_SIGNER;
+    let by_accounts = MAX_TRANSACTION_ACCOUNTS - TRANSFER_FIXED_ACCOUNTS;
+    by_size.min(by_accounts).min(MAX_SIGNERS)
+}
+
 // =============================================================================
 // UNIT TESTS
 // =============================================================================
@@ -483,6 +588,7 @@ mod tests {
     // =========================================================================
 
     #[test]
+    #[allow(deprecated)]
     fn test_owner_or_delegate_owner_valid() {
         let program_id = Pubkey::new_unique();
         let owner_key = Pubkey::new_unique();
@@ -509,10 +615,11 @@ mod tests {
 
         // Should succeed and return false (owner used)
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), false);
+        assert!(!result.unwrap());
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_owner_or_delegate_delegate_valid() {
         let program_id = Pubkey::new_unique();
         let owner_key = Pubkey::new_unique();
@@ -541,10 +648,11 @@ mod tests {
 
         // Should succeed and return true (delegate used)
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true);
+        assert!(result.unwrap());
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_owner_or_delegate_neither() {
         let program_id = Pubkey::new_unique();
         let owner_key = Pubkey::new_unique();
@@ -575,6 +683,331 @@ mod tests {
         // Should fail: neither owner nor delegate
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_owner_or_delegate_kind_owner() {
+        let program_id = Pubkey::new_unique();
+        let owner_key = Pubkey::new_unique();
+        let delegate_key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = vec![];
+        let random_owner = Pubkey::new_unique();
+
+        let authority = create_test_account_info(
+            &owner_key,
+            true,
+            false,
+            &mut lamports,
+            &mut data,
+            &random_owner,
+        );
+
+        let result = validate_owner_or_delegate_kind(
+            &program_id,
+            &owner_key,
+            Some(&delegate_key),
+            &authority,
+            &[],
+        );
+
+        // Owner takes priority even when a delegate is set
+        assert_eq!(result.unwrap(), AuthorityKind::Owner);
+    }
+
+    #[test]
+    fn test_owner_or_delegate_kind_delegate() {
+        let program_id = Pubkey::new_unique();
+        let owner_key = Pubkey::new_unique();
+        let delegate_key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = vec![];
+        let random_owner = Pubkey::new_unique();
+
+        let authority = create_test_account_info(
+            &delegate_key,
+            true,
+            false,
+            &mut lamports,
+            &mut data,
+            &random_owner,
+        );
+
+        let result = validate_owner_or_delegate_kind(
+            &program_id,
+            &owner_key,
+            Some(&delegate_key),
+            &authority,
+            &[],
+        );
+
+        assert_eq!(result.unwrap(), AuthorityKind::Delegate);
+    }
+
+    #[test]
+    fn test_owner_or_delegate_kind_neither() {
+        let program_id = Pubkey::new_unique();
+        let owner_key = Pubkey::new_unique();
+        let random_key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = vec![];
+        let random_owner = Pubkey::new_unique();
+
+        let authority = create_test_account_info(
+            &random_key,
+            true,
+            false,
+            &mut lamports,
+            &mut data,
+            &random_owner,
+        );
+
+        let result = validate_owner_or_delegate_kind(
+            &program_id,
+            &owner_key,
+            None,
+            &authority,
+            &[],
+        );
+
+        assert_eq!(result.unwrap_err(), TokenError::InvalidAuthority.into());
+    }
+
+    // =========================================================================
+    // MULTISIG TESTS
+    // =========================================================================
+
+    /// Build packed 2-of-3 multisig data for `signers`.
+    fn two_of_three_multisig_data(signers: &[Pubkey; 3]) -> Vec<u8> {
+        let mut multisig = Multisig {
+            m: 2,
+            n: 3,
+            is_initialized: true,
+            ..Multisig::default()
+        };
+        multisig.signers[..3].copy_from_slice(signers);
+
+        let mut data = vec![0u8; Multisig::LEN];
+        multisig.pack_into_slice(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_multisig_two_distinct_signers_valid() {
+        let program_id = Pubkey::new_unique();
+        let multisig_key = Pubkey::new_unique();
+        let signer_keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let system = Pubkey::default();
+
+        let mut multisig_lamports = 0u64;
+        let mut multisig_data = two_of_three_multisig_data(&signer_keys);
+        let multisig_info = create_test_account_info(
+            &multisig_key,
+            false,
+            false,
+            &mut multisig_lamports,
+            &mut multisig_data,
+            &program_id,
+        );
+
+        let mut lamports_a = 0u64;
+        let mut data_a = vec![];
+        let signer_a = create_test_account_info(
+            &signer_keys[0],
+            true,
+            false,
+            &mut lamports_a,
+            &mut data_a,
+            &system,
+        );
+        let mut lamports_b = 0u64;
+        let mut data_b = vec![];
+        let signer_b = create_test_account_info(
+            &signer_keys[1],
+            true,
+            false,
+            &mut lamports_b,
+            &mut data_b,
+            &system,
+        );
+
+        let result = validate_authority(
+            &program_id,
+            &multisig_key,
+            &multisig_info,
+            &[signer_a, signer_b],
+        );
+        assert!(result.is_ok());
+    }
+
+    /// The same signer passed twice must only count once.
+    #[test]
+    fn test_multisig_duplicate_signer_counted_once() {
+        let program_id = Pubkey::new_unique();
+        let multisig_key = Pubkey::new_unique();
+        let signer_keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let system = Pubkey::default();
+
+        let mut multisig_lamports = 0u64;
+        let mut multisig_data = two_of_three_multisig_data(&signer_keys);
+        let multisig_info = create_test_account_info(
+            &multisig_key,
+            false,
+            false,
+            &mut multisig_lamports,
+            &mut multisig_data,
+            &program_id,
+        );
+
+        let mut lamports = 0u64;
+        let mut data = vec![];
+        let signer = create_test_account_info(
+            &signer_keys[0],
+            true,
+            false,
+            &mut lamports,
+            &mut data,
+            &system,
+        );
+
+        let result = validate_authority(
+            &program_id,
+            &multisig_key,
+            &multisig_info,
+            &[signer.clone(), signer],
+        );
+        assert_eq!(result.unwrap_err(), TokenError::NotEnoughSigners.into());
+    }
+
+    /// A multisig owner short of signers reports NotEnoughSigners, not
+    /// the delegate path's InvalidAuthority.
+    #[test]
+    fn test_owner_or_delegate_multisig_owner_not_enough_signers() {
+        let program_id = Pubkey::new_unique();
+        let multisig_key = Pubkey::new_unique();
+        let delegate_key = Pubkey::new_unique();
+        let signer_keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let system = Pubkey::default();
+
+        let mut multisig_lamports = 0u64;
+        let mut multisig_data = two_of_three_multisig_data(&signer_keys);
+        let multisig_info = create_test_account_info(
+            &multisig_key,
+            false,
+            false,
+            &mut multisig_lamports,
+            &mut multisig_data,
+            &program_id,
+        );
+
+        let mut lamports = 0u64;
+        let mut data = vec![];
+        let signer = create_test_account_info(
+            &signer_keys[0],
+            true,
+            false,
+            &mut lamports,
+            &mut data,
+            &system,
+        );
+
+        let result = validate_owner_or_delegate_kind(
+            &program_id,
+            &multisig_key,
+            Some(&delegate_key),
+            &multisig_info,
+            &[signer],
+        );
+        assert_eq!(result.unwrap_err(), TokenError::NotEnoughSigners.into());
+    }
+
+    /// An owner that didn't sign reports MissingRequiredSignature.
+    #[test]
+    fn test_owner_or_delegate_owner_not_signer() {
+        let program_id = Pubkey::new_unique();
+        let owner_key = Pubkey::new_unique();
+        let delegate_key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = vec![];
+        let system = Pubkey::default();
+
+        let authority = create_test_account_info(
+            &owner_key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &system,
+        );
+
+        let result = validate_owner_or_delegate_kind(
+            &program_id,
+            &owner_key,
+            Some(&delegate_key),
+            &authority,
+            &[],
+        );
+        assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    // =========================================================================
+    // TRANSACTION SIZING TESTS
+    // =========================================================================
+
+    /// Serialized size of a transaction: the signatures, then the message.
+    fn transaction_size(message: &solana_program::message::Message) -> usize {
+        let signatures = message.header.num_required_signatures as usize;
+        1 + signatures * 64 + message.serialize().len()
+    }
+
+    /// Build a multisig Transfer message with `signer_count` signers.
+    fn multisig_transfer_message(signer_count: usize) -> solana_program::message::Message {
+        use crate::instruction::TokenInstruction;
+        use solana_program::instruction::{AccountMeta, Instruction};
+
+        let mut accounts = vec![
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+        ];
+        for _ in 0..signer_count {
+            accounts.push(AccountMeta::new_readonly(Pubkey::new_unique(), true));
+        }
+
+        let transfer_ix = Instruction {
+            program_id: crate::id(),
+            accounts,
+            data: TokenInstruction::Transfer { amount: 1 }.pack(),
+        };
+        solana_program::message::Message::new(&[transfer_ix], Some(&Pubkey::new_unique()))
+    }
+
+    #[test]
+    fn test_max_multisig_signers_for_transfer() {
+        let max = max_multisig_signers_for_transfer();
+        assert_eq!(max, 9);
+
+        // The computed layout matches a real serialized message
+        let fits = transaction_size(&multisig_transfer_message(max));
+        assert_eq!(fits, TRANSFER_FIXED_BYTES + max * TRANSFER_BYTES_PER_SIGNER);
+        assert!(fits <= PACKET_DATA_SIZE);
+
+        // One more signer doesn't fit
+        let too_many = transaction_size(&multisig_transfer_message(max + 1));
+        assert!(too_many > PACKET_DATA_SIZE);
+    }
 }
 
 /*
@@ -667,9 +1100,11 @@ Delegate (source.delegate):
 - Cleared when exhausted or via Revoke
 
 For Transfer/Burn, either can authorize.
-We return bool to indicate which:
-- false = owner (no limit tracking needed)
-- true = delegate (must decrement delegated_amount)
+We return an AuthorityKind to indicate which:
+- Owner = no limit tracking needed
+- Delegate = must decrement delegated_amount
+
+(The older bool-returning validate_owner_or_delegate is deprecated.)
 
 SECURITY CONSIDERATIONS
 =======================
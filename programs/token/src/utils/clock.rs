@@ -0,0 +1,65 @@
+//! Clock Access
+//!
+//! Reads the current slot from the Clock sysvar.
+//!
+//! `Clock::get()` can fail where the sysvar isn't available (e.g. some
+//! test environments). Two helpers make the failure policy explicit:
+//!
+//! - `current_slot`: propagates the error. Use for anything that
+//!   enforces a rule (cooldowns, expiries, rate limits).
+//! - `current_slot_or`: falls back to a default. Use only for
+//!   non-critical paths such as logging.
+//!
+//! # Features Requiring Clock
+//!
+//! None of the current instructions depend on the slot. Slot- or
+//! epoch-based rules added later must read it with `current_slot`.
+//!
+//! Delegate expiries (ApproveWithExpiry) are unix timestamps and read
+//! the Clock sysvar account passed to the transfer instead; see
+//! `assert_delegate_not_expired`.
+
+use solana_program::{clock::Clock, program_error::ProgramError, sysvar::Sysvar};
+
+/// The current slot, or the Clock sysvar error.
+///
+/// # Example
+///
+/// ```ignore
+/// if current_slot()? > expiry_slot {
+///     return Err(TokenError::InvalidState.into());
+/// }
+/// ```
+pub fn current_slot() -> Result<u64, ProgramError> {
+    Ok(Clock::get()?.slot)
+}
+
+/// The current slot, or `default` if the Clock sysvar is unavailable.
+///
+/// Never use this for enforcement: a missing clock would silently
+/// pass the check.
+///
+/// # Example
+///
+/// ```ignore
+/// msg!("Frozen at slot {}", current_slot_or(0));
+/// ```
+pub fn current_slot_or(default: u64) -> u64 {
+    current_slot().unwrap_or(default)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Off-chain unit tests have no Clock sysvar.
+    #[test]
+    fn test_fallback_does_not_mask_enforcement_error() {
+        assert_eq!(current_slot_or(42), 42);
+        assert!(current_slot().is_err());
+    }
+}
@@ -0,0 +1,310 @@
+//! Account Loading Helpers
+//!
+//! Most processors open with the same five steps for every token account
+//! or mint they touch:
+//!
+//! 1. `assert_owned_by` our program
+//! 2. `assert_writable` (if it will be modified)
+//! 3. Data length check
+//! 4. Unpack
+//! 5. Reject uninitialized state
+//!
+//! These helpers do all five and return the unpacked state. The `store_*`
+//! helpers write it back, holding the mutable data borrow only for the
+//! duration of the pack.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut mint = load_mint(mint_info, program_id, true)?;
+//! let mut dest = load_token_account(dest_info, program_id, true)?;
+//! ...
+//! store_mint(mint_info, &mint)?;
+//! store_token_account(dest_info, &dest)?;
+//! ```
+
+use crate::error::TokenError;
+use crate::state::{Account, Mint, Pack};
+use crate::utils::assertions::*;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Validate and unpack an initialized token account.
+///
+/// # Errors
+///
+/// - `InvalidAccountOwner` if not owned by `program_id`
+/// - `InvalidAccountData` if `writable` and the account isn't
+/// - `InvalidAccountDataLength` if not a token account size
+/// - `UninitializedAccount` if not initialized
+pub fn load_token_account(
+    info: &AccountInfo,
+    program_id: &Pubkey,
+    writable: bool,
+) -> Result<Account, ProgramError> {
+    assert_owned_by(info, program_id)?;
+    if writable {
+        assert_writable(info)?;
+    }
+    assert_account_data_length(info)?;
+
+    let account = Account::unpack_from_slice(&info.data.borrow())?;
+    if !account.is_initialized() {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    Ok(account)
+}
+
+/// Validate and unpack an initialized mint.
+///
+/// # Errors
+///
+/// Same as `load_token_account`, with the size checked against the
+/// mint layouts instead.
+pub fn load_mint(
+    info: &AccountInfo,
+    program_id: &Pubkey,
+    writable: bool,
+) -> Result<Mint, ProgramError> {
+    assert_owned_by(info, program_id)?;
+    if writable {
+        assert_writable(info)?;
+    }
+    assert_mint_data_length(info)?;
+
+    let mint = Mint::unpack_from_slice(&info.data.borrow())?;
+    if !mint.is_initialized {
+        return Err(TokenError::UninitializedAccount.into());
+    }
+
+    Ok(mint)
+}
+
+/// Pack a token account back into its account data.
+///
+/// # Errors
+///
+/// `InvalidAccountDataLength` if the data isn't a token account size,
+/// or if extension fields are set on a base-size account.
+pub fn store_token_account(info: &AccountInfo, account: &Account) -> ProgramResult {
+    assert_account_data_length(info)?;
+    account.pack_into_slice(&mut info.data.borrow_mut())
+}
+
+/// Pack a mint back into its account data.
+///
+/// # Errors
+///
+/// Same as `store_token_account`, against the mint layouts.
+pub fn store_mint(info: &AccountInfo, mint: &Mint) -> ProgramResult {
+    assert_mint_data_length(info)?;
+    mint.pack_into_slice(&mut info.data.borrow_mut())
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AccountState;
+
+    /// Helper to create a mock writable AccountInfo for testing
+    fn create_test_account_info<'a>(
+        key: &'a Pubkey,
+        lamports: &'a mut u64,
+        data: &'a mut [u8],
+        owner: &'a Pubkey,
+    ) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, true, lamports, data, owner, false, 0)
+    }
+
+    fn packed_account(state: AccountState) -> Vec<u8> {
+        let account = Account {
+            amount: 42,
+            state,
+            ..Account::new(Pubkey::new_unique(), Pubkey::new_unique())
+        };
+        let mut data = vec![0u8; Account::LEN];
+        account.pack_into_slice(&mut data).unwrap();
+        data
+    }
+
+    fn packed_mint(is_initialized: bool) -> Vec<u8> {
+        let mint = Mint {
+            supply: 42,
+            is_initialized,
+            ..Mint::new(6, None, None)
+        };
+        let mut data = vec![0u8; Mint::LEN];
+        mint.pack_into_slice(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_load_token_account_valid() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = packed_account(AccountState::Initialized);
+
+        let info = create_test_account_info(&key, &mut lamports, &mut data, &program_id);
+
+        let account = load_token_account(&info, &program_id, true).unwrap();
+        assert_eq!(account.amount, 42);
+    }
+
+    #[test]
+    fn test_load_token_account_wrong_owner() {
+        let program_id = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = packed_account(AccountState::Initialized);
+
+        let info = create_test_account_info(&key, &mut lamports, &mut data, &other_program);
+
+        assert_eq!(
+            load_token_account(&info, &program_id, false).unwrap_err(),
+            TokenError::InvalidAccountOwner.into()
+        );
+    }
+
+    #[test]
+    fn test_load_token_account_not_writable() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = packed_account(AccountState::Initialized);
+
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false, // read-only
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        assert_eq!(
+            load_token_account(&info, &program_id, true).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        assert!(load_token_account(&info, &program_id, false).is_ok());
+    }
+
+    #[test]
+    fn test_load_token_account_wrong_size() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        // A mint is not a token account
+        let mut data = packed_mint(true);
+
+        let info = create_test_account_info(&key, &mut lamports, &mut data, &program_id);
+
+        assert_eq!(
+            load_token_account(&info, &program_id, false).unwrap_err(),
+            TokenError::InvalidAccountDataLength.into()
+        );
+    }
+
+    #[test]
+    fn test_load_token_account_uninitialized() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = packed_account(AccountState::Uninitialized);
+
+        let info = create_test_account_info(&key, &mut lamports, &mut data, &program_id);
+
+        assert_eq!(
+            load_token_account(&info, &program_id, false).unwrap_err(),
+            TokenError::UninitializedAccount.into()
+        );
+    }
+
+    #[test]
+    fn test_load_mint_checks() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+
+        // Valid
+        let mut lamports = 0u64;
+        let mut data = packed_mint(true);
+        let info = create_test_account_info(&key, &mut lamports, &mut data, &program_id);
+        assert_eq!(load_mint(&info, &program_id, true).unwrap().supply, 42);
+
+        // Wrong owner
+        let other_program = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = packed_mint(true);
+        let info = create_test_account_info(&key, &mut lamports, &mut data, &other_program);
+        assert_eq!(
+            load_mint(&info, &program_id, false).unwrap_err(),
+            TokenError::InvalidAccountOwner.into()
+        );
+
+        // Wrong size
+        let mut lamports = 0u64;
+        let mut data = packed_account(AccountState::Initialized);
+        let info = create_test_account_info(&key, &mut lamports, &mut data, &program_id);
+        assert_eq!(
+            load_mint(&info, &program_id, false).unwrap_err(),
+            TokenError::InvalidAccountDataLength.into()
+        );
+
+        // Uninitialized
+        let mut lamports = 0u64;
+        let mut data = packed_mint(false);
+        let info = create_test_account_info(&key, &mut lamports, &mut data, &program_id);
+        assert_eq!(
+            load_mint(&info, &program_id, false).unwrap_err(),
+            TokenError::UninitializedAccount.into()
+        );
+    }
+
+    #[test]
+    fn test_store_token_account_roundtrip() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = packed_account(AccountState::Initialized);
+
+        let info = create_test_account_info(&key, &mut lamports, &mut data, &program_id);
+
+        let mut account = load_token_account(&info, &program_id, true).unwrap();
+        account.amount = 7;
+        store_token_account(&info, &account).unwrap();
+
+        assert_eq!(
+            load_token_account(&info, &program_id, true).unwrap(),
+            account
+        );
+    }
+
+    #[test]
+    fn test_store_wrong_size() {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = vec![0u8; Account::LEN + 1];
+
+        let info = create_test_account_info(&key, &mut lamports, &mut data, &owner);
+
+        assert_eq!(
+            store_token_account(&info, &Account::default()).unwrap_err(),
+            TokenError::InvalidAccountDataLength.into()
+        );
+        assert_eq!(
+            store_mint(&info, &Mint::default()).unwrap_err(),
+            TokenError::InvalidAccountDataLength.into()
+        );
+    }
+}
@@ -4,12 +4,36 @@
 //!
 //! # Modules
 //!
+//! - `address`: Associated-token-account-style address derivation
 //! - `assertions`: Common validation checks (ownership, signer, etc.)
 //! - `authority`: Authority validation (single signer and multisig)
+//! - `clock`: Current slot, with an explicit fallback policy
+//! - `events`: Base64 binary event logs (events feature)
+//! - `load`: Validate-and-unpack for token accounts and mints
+//! - `rent`: Rent-exempt minimums per account type
+//! - `scaling`: Rescale amounts between different decimals
+//! - `transfer_checks`: Mint and destination rules shared by every transfer
+//! - `ui_amount`: Raw amount <-> UI string conversion
 
+pub mod address;
 pub mod assertions;
 pub mod authority;
+pub mod clock;
+#[cfg(feature = "events")]
+pub mod events;
+pub mod load;
+pub mod rent;
+pub mod scaling;
+pub mod transfer_checks;
+pub mod ui_amount;
 
 // Re-export all utilities for easy access
+pub use address::*;
 pub use assertions::*;
-pub use authority::*;
\ No newline at end of file
+pub use authority::*;
+pub use clock::*;
+pub use load::*;
+pub use rent::*;
+pub use scaling::*;
+pub use transfer_checks::*;
+pub use ui_amount::*;
\ No newline at end of file
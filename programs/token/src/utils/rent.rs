@@ -0,0 +1,73 @@
+//! Rent-Exempt Minimums
+//!
+//! The lamports each account type needs to be rent-exempt.
+//!
+//! These only do arithmetic on the `Rent` they're given and never read
+//! a sysvar, so the same functions work on-chain and in clients:
+//!
+//! ```ignore
+//! // On-chain
+//! let lamports = account_rent(&Rent::get()?);
+//!
+//! // Off-chain, with rent fetched over RPC or the cluster default
+//! let rent = banks_client.get_rent().await?;
+//! let lamports = mint_rent(&rent);
+//! let lamports = multisig_rent(&Rent::default());
+//! ```
+//!
+//! Sizes are the base layouts (`Mint::LEN`, `Account::LEN`,
+//! `Multisig::LEN`). Extended mints and accounts need
+//! `rent.minimum_balance(Mint::EXTENDED_LEN)` and so on.
+
+use crate::state::{Account, Mint, Multisig};
+use solana_program::rent::Rent;
+
+/// Lamports for a rent-exempt mint (`Mint::LEN` bytes).
+pub fn mint_rent(rent: &Rent) -> u64 {
+    rent.minimum_balance(Mint::LEN)
+}
+
+/// Lamports for a rent-exempt token account (`Account::LEN` bytes).
+pub fn account_rent(rent: &Rent) -> u64 {
+    rent.minimum_balance(Account::LEN)
+}
+
+/// Lamports for a rent-exempt multisig (`Multisig::LEN` bytes).
+pub fn multisig_rent(rent: &Rent) -> u64 {
+    rent.minimum_balance(Multisig::LEN)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Default rent: (len + 128) bytes * 3480 lamports/byte-year * 2 years.
+    #[test]
+    fn test_rent_minimums_with_default_rent() {
+        let rent = Rent::default();
+
+        assert_eq!(mint_rent(&rent), 1_461_600);
+        assert_eq!(account_rent(&rent), 2_039_280);
+        assert_eq!(multisig_rent(&rent), 3_361_680);
+    }
+
+    /// Each helper matches `minimum_balance` for its own size only.
+    #[test]
+    fn test_rent_minimums_match_sizes() {
+        let rent = Rent {
+            lamports_per_byte_year: 1,
+            exemption_threshold: 1.0,
+            burn_percent: 0,
+        };
+
+        assert_eq!(mint_rent(&rent), rent.minimum_balance(Mint::LEN));
+        assert_eq!(account_rent(&rent), rent.minimum_balance(Account::LEN));
+        assert_eq!(multisig_rent(&rent), rent.minimum_balance(Multisig::LEN));
+        assert!(mint_rent(&rent) < account_rent(&rent));
+        assert!(account_rent(&rent) < multisig_rent(&rent));
+    }
+}
@@ -0,0 +1,98 @@
+//! Amount Scaling
+//!
+//! Converts raw amounts between mints with different decimals.
+//!
+//! # Example
+//!
+//! 1.5 tokens of a 6-decimal mint is 1_500_000 base units.
+//! The same value for a 9-decimal mint is 1_500_000_000.
+//!
+//! ```ignore
+//! assert_eq!(rescale(1_500_000, 6, 9)?, 1_500_000_000);
+//! assert_eq!(rescale(1_500_000_000, 9, 6)?, 1_500_000);
+//! ```
+
+use crate::error::TokenError;
+use solana_program::program_error::ProgramError;
+
+/// Rescale `amount` from `from_decimals` to `to_decimals`.
+///
+/// # Up-scaling (to > from)
+///
+/// Multiplies by 10^(to - from). Exact, but can overflow.
+///
+/// # Down-scaling (to < from)
+///
+/// Divides by 10^(from - to). The remainder is truncated (rounded
+/// toward zero), so dust below the target precision is lost:
+/// `rescale(1_234_567_891, 9, 6) == 1_234_567`.
+///
+/// # Errors
+///
+/// * `Overflow` - Result (or the scale factor) doesn't fit in a u64
+pub fn rescale(amount: u64, from_decimals: u8, to_decimals: u8) -> Result<u64, ProgramError> {
+    if to_decimals >= from_decimals {
+        let factor = pow10(to_decimals - from_decimals)?;
+        amount
+            .checked_mul(factor)
+            .ok_or_else(|| TokenError::Overflow.into())
+    } else {
+        // A factor past u64::MAX divides any u64 down to 0
+        match pow10(from_decimals - to_decimals) {
+            Ok(factor) => amount
+                .checked_div(factor)
+                .ok_or_else(|| TokenError::Overflow.into()),
+            Err(_) => Ok(0),
+        }
+    }
+}
+
+/// 10^exp, or `Overflow` if it doesn't fit in a u64 (exp > 19).
+fn pow10(exp: u8) -> Result<u64, ProgramError> {
+    10u64
+        .checked_pow(exp as u32)
+        .ok_or_else(|| TokenError::Overflow.into())
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rescale_up() {
+        // 6 -> 9
+        assert_eq!(rescale(1_500_000, 6, 9).unwrap(), 1_500_000_000);
+        assert_eq!(rescale(0, 6, 9).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_rescale_down() {
+        // 9 -> 6
+        assert_eq!(rescale(1_500_000_000, 9, 6).unwrap(), 1_500_000);
+        // Truncates toward zero
+        assert_eq!(rescale(1_234_567_891, 9, 6).unwrap(), 1_234_567);
+        assert_eq!(rescale(999, 9, 6).unwrap(), 0);
+        // Factor beyond u64 range
+        assert_eq!(rescale(u64::MAX, 30, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_rescale_equal_decimals() {
+        assert_eq!(rescale(42, 6, 6).unwrap(), 42);
+        assert_eq!(rescale(u64::MAX, 9, 9).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_rescale_overflow() {
+        assert_eq!(
+            rescale(u64::MAX, 6, 9).unwrap_err(),
+            TokenError::Overflow.into()
+        );
+        // Scale factor itself overflows
+        assert_eq!(rescale(1, 0, 20).unwrap_err(), TokenError::Overflow.into());
+    }
+}
@@ -0,0 +1,207 @@
+//! Transfer Checks
+//!
+//! Checks shared by every instruction that moves tokens from a source
+//! account to other token accounts (Transfer, TransferWithReturnData,
+//! TransferBatch, TransferWithFee). Keeping them in one place means a
+//! caller can't route around a mint's rules by picking a different
+//! transfer instruction.
+
+use crate::error::TokenError;
+use crate::instruction::transfer_hook_instruction;
+use crate::state::{Account, Mint};
+use crate::utils::assertions::{assert_memo_precedes, checked_add, checked_sub};
+use crate::utils::load::load_mint;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke,
+    program_error::ProgramError, pubkey::Pubkey, sysvar,
+};
+
+/// Validate and unpack the mint of the tokens being moved.
+///
+/// The mint is a required account of every transfer, since
+/// `non_transferable` and the transfer hook live on it. Leaving it out,
+/// or passing another mint in its place, must not skip them.
+///
+/// # Errors
+///
+/// * `MintMismatch` - `mint_info` isn't `expected_mint`
+/// * `InvalidAccountOwner` / `InvalidAccountDataLength` /
+///   `UninitializedAccount` - as `load_mint`
+/// * `NonTransferable` - The mint blocks transfers
+pub fn load_transfer_mint(
+    mint_info: &AccountInfo,
+    program_id: &Pubkey,
+    expected_mint: &Pubkey,
+) -> Result<Mint, ProgramError> {
+    if mint_info.key != expected_mint {
+        return Err(TokenError::MintMismatch.into());
+    }
+
+    let mint = load_mint(mint_info, program_id, false)?;
+    if mint.non_transferable {
+        return Err(TokenError::NonTransferable.into());
+    }
+
+    Ok(mint)
+}
+
+/// Require a preceding Memo instruction if the destination asks for one.
+///
+/// The instructions sysvar must be among `remaining_accounts`.
+///
+/// # Errors
+///
+/// * `MemoRequired` - The sysvar is missing or no Memo instruction
+///   runs earlier in the transaction
+pub fn assert_memo_if_required(
+    dest: &Account,
+    remaining_accounts: &[AccountInfo],
+) -> ProgramResult {
+    if !dest.memo_required {
+        return Ok(());
+    }
+
+    let instructions_info = remaining_accounts
+        .iter()
+        .find(|info| sysvar::instructions::check_id(info.key))
+        .ok_or(TokenError::MemoRequired)?;
+    assert_memo_precedes(instructions_info)
+}
+
+/// Move `amount` lamports along with `amount` wrapped SOL tokens.
+///
+/// A wrapped SOL balance *is* the lamports above the rent reserve, so a
+/// transfer between native accounts moves both, keeping
+/// `amount == lamports - reserve` on each. Call only when the source is
+/// native; matching mints make the receiver native too.
+///
+/// # Errors
+///
+/// * `InsufficientFunds` / `Overflow` - As `checked_sub` / `checked_add`
+pub fn move_native_lamports(
+    from_info: &AccountInfo,
+    to_info: &AccountInfo,
+    amount: u64,
+) -> ProgramResult {
+    let new_from_lamports = checked_sub(from_info.lamports(), amount)?;
+    let new_to_lamports = checked_add(to_info.lamports(), amount)?;
+    **from_info.lamports.borrow_mut() = new_from_lamports;
+    **to_info.lamports.borrow_mut() = new_to_lamports;
+    Ok(())
+}
+
+/// Run the mint's transfer hook, if it has one, on one completed leg.
+///
+/// Call this after the balances are stored, so the hook sees the
+/// post-transfer state. The hook program must be among
+/// `remaining_accounts`.
+///
+/// # Errors
+///
+/// * `NotEnoughAccountKeys` - The hook program wasn't passed
+/// * `TransferHookFailed` - The hook returned an error
+pub fn invoke_transfer_hook<'a>(
+    mint: &Mint,
+    source_info: &AccountInfo<'a>,
+    dest_info: &AccountInfo<'a>,
+    mint_info: &AccountInfo<'a>,
+    authority_info: &AccountInfo<'a>,
+    remaining_accounts: &[AccountInfo<'a>],
+    amount: u64,
+) -> ProgramResult {
+    let hook_program = match mint.transfer_hook.as_ref() {
+        Some(hook_program) => hook_program,
+        None => return Ok(()),
+    };
+
+    let hook_program_info = remaining_accounts
+        .iter()
+        .find(|info| info.key == hook_program)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let hook_ix = transfer_hook_instruction(
+        hook_program,
+        source_info.key,
+        dest_info.key,
+        mint_info.key,
+        authority_info.key,
+        amount,
+    );
+
+    invoke(
+        &hook_ix,
+        &[
+            source_info.clone(),
+            dest_info.clone(),
+            mint_info.clone(),
+            authority_info.clone(),
+            hook_program_info.clone(),
+        ],
+    )
+    .map_err(|_| TokenError::TransferHookFailed.into())
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Pack;
+
+    fn packed_mint(non_transferable: bool) -> Vec<u8> {
+        let mint = Mint {
+            non_transferable,
+            ..Mint::new(6, None, None)
+        };
+        let mut data = vec![0u8; Mint::EXTENDED_LEN];
+        mint.pack_into_slice(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_load_transfer_mint() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+
+        // Transferable
+        let mut lamports = 0u64;
+        let mut data = packed_mint(false);
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        assert!(load_transfer_mint(&info, &program_id, &key).is_ok());
+
+        // Some other account in the mint's place
+        assert_eq!(
+            load_transfer_mint(&info, &program_id, &Pubkey::new_unique()).unwrap_err(),
+            TokenError::MintMismatch.into()
+        );
+
+        // Non-transferable
+        let mut lamports = 0u64;
+        let mut data = packed_mint(true);
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        assert_eq!(
+            load_transfer_mint(&info, &program_id, &key).unwrap_err(),
+            TokenError::NonTransferable.into()
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! UI Amount Conversion
+//!
+//! Converts between raw token amounts (base units, u64) and the
+//! decimal strings shown to users.
+//!
+//! # Example
+//!
+//! With 6 decimals (like USDC):
+//!
+//! | Raw amount | UI string |
+//! |------------|-----------|
+//! | 1_500_000 | "1.5" |
+//! | 1 | "0.000001" |
+//! | 0 | "0" |
+//!
+//! All conversions are string based, so they never touch floating
+//! point and work for any number of decimals.
+
+use crate::error::TokenError;
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError};
+
+// =============================================================================
+// RAW -> UI
+// =============================================================================
+
+/// Convert a raw amount to a UI string, trimming trailing zeros.
+///
+/// # Example
+///
+/// ```ignore
+/// assert_eq!(amount_to_ui_string(1_500_000, 6), "1.5");
+/// assert_eq!(amount_to_ui_string(2_000_000, 6), "2");
+/// ```
+pub fn amount_to_ui_string(amount: u64, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let digits = amount.to_string();
+
+    if decimals == 0 {
+        return digits;
+    }
+
+    // Left-pad so there's at least one digit before the point
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (integer, fraction) = padded.split_at(padded.len() - decimals);
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+/// Convert base units to a UI string for display.
+///
+/// Same as `amount_to_ui_string`; paired with `try_ui_to_base` for
+/// clients converting in both directions.
+///
+/// # Example
+///
+/// ```ignore
+/// assert_eq!(base_to_ui(1_500_000, 6), "1.5");
+/// ```
+pub fn base_to_ui(base: u64, decimals: u8) -> String {
+    amount_to_ui_string(base, decimals)
+}
+
+// =============================================================================
+// PRECISION
+// =============================================================================
+
+/// Check a UI string has no more fractional digits than `decimals`.
+///
+/// Only counts the digits after the point; it doesn't validate
+/// the rest of the string.
+///
+/// # Errors
+///
+/// * `ExcessPrecision` - More fractional digits than `decimals`
+///
+/// # Example
+///
+/// ```ignore
+/// assert_precision_fits("1.5", 6)?;        // ok
+/// assert_precision_fits("1.1234567", 6)?;  // ExcessPrecision
+/// ```
+pub fn assert_precision_fits(ui_amount: &str, decimals: u8) -> ProgramResult {
+    let fraction_digits = match ui_amount.split_once('.') {
+        Some((_, fraction)) => fraction.len(),
+        None => 0,
+    };
+
+    if fraction_digits > decimals as usize {
+        return Err(TokenError::ExcessPrecision.into());
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// UI -> RAW
+// =============================================================================
+
+/// Parse a UI string into a raw amount.
+///
+/// Accepts `"1"`, `"1.5"`, `".5"` and `"1."`. Rejects signs,
+/// exponents, separators and anything else that isn't a digit
+/// or a single decimal point.
+///
+/// # Errors
+///
+/// * `InvalidArgument` - Not a plain decimal number
+/// * `ExcessPrecision` - More fractional digits than `decimals`
+/// * `Overflow` - Value doesn't fit in a u64
+///
+/// # Example
+///
+/// ```ignore
+/// assert_eq!(ui_string_to_amount("1.5", 6)?, 1_500_000);
+/// ```
+pub fn ui_string_to_amount(ui_amount: &str, decimals: u8) -> Result<u64, ProgramError> {
+    let (integer, fraction) = match ui_amount.split_once('.') {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (ui_amount, ""),
+    };
+
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if !is_digits(integer) || !is_digits(fraction) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    if integer.is_empty() && fraction.is_empty() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    assert_precision_fits(ui_amount, decimals)?;
+
+    // Shift the point right by `decimals` digits
+    let digits = format!("{}{:0<width$}", integer, fraction, width = decimals as usize);
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(0);
+    }
+
+    digits.parse::<u64>().map_err(|_| TokenError::Overflow.into())
+}
+
+/// Parse user input into base units.
+///
+/// Like `ui_string_to_amount`, but ignores leading and trailing
+/// whitespace, so values pasted from a form or CLI argument work as-is.
+///
+/// # Errors
+///
+/// Same as `ui_string_to_amount`.
+///
+/// # Example
+///
+/// ```ignore
+/// assert_eq!(try_ui_to_base(" 1.5\n", 6)?, 1_500_000);
+/// ```
+pub fn try_ui_to_base(ui: &str, decimals: u8) -> Result<u64, ProgramError> {
+    ui_string_to_amount(ui.trim(), decimals)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_to_ui_string() {
+        assert_eq!(amount_to_ui_string(0, 6), "0");
+        assert_eq!(amount_to_ui_string(1, 6), "0.000001");
+        assert_eq!(amount_to_ui_string(1_500_000, 6), "1.5");
+        assert_eq!(amount_to_ui_string(42, 0), "42");
+        assert_eq!(amount_to_ui_string(u64::MAX, 30), "0.000000000018446744073709551615");
+    }
+
+    #[test]
+    fn test_ui_string_to_amount() {
+        assert_eq!(ui_string_to_amount("1.5", 6).unwrap(), 1_500_000);
+        assert_eq!(ui_string_to_amount(".5", 1).unwrap(), 5);
+        assert_eq!(ui_string_to_amount("1.", 2).unwrap(), 100);
+        assert_eq!(ui_string_to_amount("000", 9).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_ui_string_to_amount_invalid() {
+        for bad in ["", ".", "-1", "+1", "1e3", "1,000", "1.2.3", " 1"] {
+            assert_eq!(
+                ui_string_to_amount(bad, 6).unwrap_err(),
+                ProgramError::InvalidArgument,
+                "{:?}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_assert_precision_fits() {
+        assert!(assert_precision_fits("1.5", 6).is_ok());
+        assert!(assert_precision_fits("1", 0).is_ok());
+        assert_eq!(
+            assert_precision_fits("1.1234567", 6).unwrap_err(),
+            TokenError::ExcessPrecision.into()
+        );
+    }
+
+    #[test]
+    fn test_ui_string_to_amount_excess_precision() {
+        assert_eq!(
+            ui_string_to_amount("1.1234567", 6).unwrap_err(),
+            TokenError::ExcessPrecision.into()
+        );
+    }
+
+    #[test]
+    fn test_ui_string_to_amount_overflow() {
+        assert_eq!(
+            ui_string_to_amount("18446744073709551616", 0).unwrap_err(),
+            TokenError::Overflow.into()
+        );
+        assert_eq!(
+            ui_string_to_amount("18446744073709551615", 0).unwrap(),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn test_try_ui_to_base_whitespace() {
+        assert_eq!(try_ui_to_base(" 1.5", 6).unwrap(), 1_500_000);
+        assert_eq!(try_ui_to_base("1.5 \n", 6).unwrap(), 1_500_000);
+        assert_eq!(try_ui_to_base("\t42\t", 0).unwrap(), 42);
+
+        // Whitespace inside the number is still rejected
+        for bad in ["", "   ", "1 .5", "1. 5"] {
+            assert_eq!(
+                try_ui_to_base(bad, 6).unwrap_err(),
+                ProgramError::InvalidArgument,
+                "{:?}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_try_ui_to_base_limits() {
+        // Exactly `decimals` fractional digits is fine, one more is not
+        assert_eq!(try_ui_to_base("0.000001", 6).unwrap(), 1);
+        assert_eq!(
+            try_ui_to_base(" 0.0000001 ", 6).unwrap_err(),
+            TokenError::ExcessPrecision.into()
+        );
+        assert_eq!(
+            try_ui_to_base("1.5", 0).unwrap_err(),
+            TokenError::ExcessPrecision.into()
+        );
+
+        // u64::MAX base units, and one past it
+        assert_eq!(
+            try_ui_to_base(" 18446744073.709551615 ", 9).unwrap(),
+            u64::MAX
+        );
+        assert_eq!(
+            try_ui_to_base("18446744073.709551616", 9).unwrap_err(),
+            TokenError::Overflow.into()
+        );
+    }
+
+    #[test]
+    fn test_base_to_ui_roundtrip() {
+        for (base, decimals) in [(0, 6), (1, 6), (1_500_000, 6), (42, 0), (u64::MAX, 9)] {
+            let ui = base_to_ui(base, decimals);
+            assert_eq!(try_ui_to_base(&ui, decimals).unwrap(), base);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! Instruction Roundtrip Tests
+//!
+//! Every `TokenInstruction` variant must survive `pack` followed by
+//! `unpack` unchanged. Variants carrying optional data are checked with
+//! both the None and Some branches, and numeric fields with their edge
+//! values, so a serialization regression in any arm shows up here.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test instruction_roundtrip
+//! ```
+
+use solana_program::pubkey::Pubkey;
+use spl_token_from_scratch::instruction::{AuthorityType, TokenInstruction};
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Edge values for u64 fields.
+const AMOUNTS: [u64; 4] = [0, 1, 1_000_000_000, u64::MAX];
+
+/// Every authority type, for SetAuthority.
+const AUTHORITY_TYPES: [AuthorityType; 4] = [
+    AuthorityType::MintTokens,
+    AuthorityType::FreezeAccount,
+    AuthorityType::AccountOwner,
+    AuthorityType::CloseAccount,
+];
+
+/// Assert that an instruction unpacks to itself after packing.
+fn assert_roundtrip(instruction: TokenInstruction) {
+    let packed = instruction.pack();
+    let unpacked = TokenInstruction::unpack(&packed)
+        .unwrap_or_else(|err| panic!("{:?} failed to unpack: {:?}", instruction, err));
+    assert_eq!(unpacked, instruction, "packed bytes: {:?}", packed);
+}
+
+// =============================================================================
+// ORIGINAL INSTRUCTIONS (0-11)
+// =============================================================================
+
+#[test]
+fn test_roundtrip_initialize_mint() {
+    for freeze_authority in [None, Some(Pubkey::new_unique())] {
+        for decimals in [0, 6, 9, u8::MAX] {
+            assert_roundtrip(TokenInstruction::InitializeMint {
+                decimals,
+                mint_authority: Pubkey::new_unique(),
+                freeze_authority,
+            });
+        }
+    }
+}
+
+#[test]
+fn test_roundtrip_account_and_multisig_initialization() {
+    assert_roundtrip(TokenInstruction::InitializeAccount);
+
+    for m in [1, 2, 11] {
+        assert_roundtrip(TokenInstruction::InitializeMultisig { m });
+    }
+}
+
+#[test]
+fn test_roundtrip_amount_instructions() {
+    for amount in AMOUNTS {
+        assert_roundtrip(TokenInstruction::Transfer { amount });
+        assert_roundtrip(TokenInstruction::TransferWithReturnData { amount });
+        assert_roundtrip(TokenInstruction::Approve { amount });
+        assert_roundtrip(TokenInstruction::MintTo { amount });
+        assert_roundtrip(TokenInstruction::Burn { amount });
+    }
+}
+
+#[test]
+fn test_roundtrip_set_authority() {
+    for authority_type in AUTHORITY_TYPES {
+        for new_authority in [None, Some(Pubkey::new_unique())] {
+            assert_roundtrip(TokenInstruction::SetAuthority {
+                authority_type,
+                new_authority,
+            });
+        }
+    }
+}
+
+#[test]
+fn test_roundtrip_unit_instructions() {
+    assert_roundtrip(TokenInstruction::Revoke);
+    assert_roundtrip(TokenInstruction::CloseAccount);
+    assert_roundtrip(TokenInstruction::CloseAccountWithReserve);
+    assert_roundtrip(TokenInstruction::FreezeAccount);
+    assert_roundtrip(TokenInstruction::ThawAccount);
+}
+
+// =============================================================================
+// EXTENSION INSTRUCTIONS (26+)
+// =============================================================================
+
+#[test]
+fn test_roundtrip_transfer_batch() {
+    assert_roundtrip(TokenInstruction::TransferBatch { amounts: vec![] });
+    assert_roundtrip(TokenInstruction::TransferBatch {
+        amounts: AMOUNTS.to_vec(),
+    });
+}
+
+#[test]
+fn test_roundtrip_mint_to_batch() {
+    assert_roundtrip(TokenInstruction::MintToBatch { amounts: vec![] });
+    assert_roundtrip(TokenInstruction::MintToBatch {
+        amounts: AMOUNTS.to_vec(),
+    });
+}
+
+#[test]
+fn test_roundtrip_revoke_batch() {
+    assert_roundtrip(TokenInstruction::RevokeBatch { count: 0 });
+    assert_roundtrip(TokenInstruction::RevokeBatch { count: 3 });
+}
+
+#[test]
+fn test_roundtrip_extension_unit_instructions() {
+    assert_roundtrip(TokenInstruction::BurnAll);
+    assert_roundtrip(TokenInstruction::ApproveAll);
+    assert_roundtrip(TokenInstruction::SetNonTransferable);
+    assert_roundtrip(TokenInstruction::GetSupply);
+    assert_roundtrip(TokenInstruction::GetMintInfo);
+    assert_roundtrip(TokenInstruction::GetAccountBalance);
+    assert_roundtrip(TokenInstruction::GetMultisigInfo);
+    assert_roundtrip(TokenInstruction::MigrateAccount);
+}
+
+#[test]
+fn test_roundtrip_reallocate() {
+    for new_len in AMOUNTS {
+        assert_roundtrip(TokenInstruction::Reallocate { new_len });
+    }
+}
+
+#[test]
+fn test_roundtrip_transfer_with_fee() {
+    for amount in AMOUNTS {
+        for fee_basis_points in [0, 100, 10_000, u16::MAX] {
+            assert_roundtrip(TokenInstruction::TransferWithFee {
+                amount,
+                fee_basis_points,
+            });
+        }
+    }
+}
+
+#[test]
+fn test_roundtrip_create_account() {
+    assert_roundtrip(TokenInstruction::CreateAccount {
+        owner: Pubkey::new_unique(),
+    });
+}
+
+#[test]
+fn test_roundtrip_initialize_account_idempotent() {
+    assert_roundtrip(TokenInstruction::InitializeAccountIdempotent {
+        owner: Pubkey::new_unique(),
+    });
+}
+
+#[test]
+fn test_roundtrip_freeze_accounts() {
+    for count in [0, 1, 3, u8::MAX] {
+        assert_roundtrip(TokenInstruction::FreezeAccounts { count });
+    }
+}
+
+#[test]
+fn test_roundtrip_flag_instructions() {
+    for flag in [false, true] {
+        assert_roundtrip(TokenInstruction::SetMemoRequired { required: flag });
+        assert_roundtrip(TokenInstruction::ReconcileSupply { strict: flag });
+        assert_roundtrip(TokenInstruction::SetMintPaused { paused: flag });
+    }
+}
+
+#[test]
+fn test_roundtrip_set_supply_cap() {
+    assert_roundtrip(TokenInstruction::SetSupplyCap { cap: None });
+    for cap in AMOUNTS {
+        assert_roundtrip(TokenInstruction::SetSupplyCap { cap: Some(cap) });
+    }
+}
+
+#[test]
+fn test_roundtrip_set_transfer_hook() {
+    assert_roundtrip(TokenInstruction::SetTransferHook { program: None });
+    assert_roundtrip(TokenInstruction::SetTransferHook {
+        program: Some(Pubkey::new_unique()),
+    });
+}
+
+#[test]
+fn test_roundtrip_close_authority_instructions() {
+    assert_roundtrip(TokenInstruction::InitializeAccountWithCloseAuthority {
+        close_authority: Pubkey::new_unique(),
+    });
+
+    for amount in AMOUNTS {
+        assert_roundtrip(TokenInstruction::ApproveAndSetCloseAuthority {
+            amount,
+            close_authority: Pubkey::new_unique(),
+        });
+    }
+}
+
+#[test]
+fn test_roundtrip_approve_with_expiry() {
+    for amount in AMOUNTS {
+        for expiry_unix_ts in [i64::MIN, -1, 0, 1_700_000_000, i64::MAX] {
+            assert_roundtrip(TokenInstruction::ApproveWithExpiry {
+                amount,
+                expiry_unix_ts,
+            });
+        }
+    }
+}
+
+#[test]
+fn test_roundtrip_verify_account() {
+    assert_roundtrip(TokenInstruction::VerifyAccount {
+        expected_mint: Pubkey::new_unique(),
+        expected_owner: Pubkey::new_unique(),
+    });
+}
@@ -12,20 +12,32 @@
 //! ```
 
 use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
     instruction::{AccountMeta, Instruction},
+    msg,
+    program_error::ProgramError,
     pubkey::Pubkey,
     system_instruction,
 };
 use solana_program_test::*;
 use solana_sdk::{
+    instruction::InstructionError,
     signature::{Keypair, Signer},
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
 use spl_token_from_scratch::{
-    instruction::{AuthorityType, TokenInstruction},
-    state::{Account as TokenAccount, AccountState, Mint, Multisig, Pack, MAX_SIGNERS},
+    error::TokenError,
+    instruction::{unpack_transfer_hook_amount, AuthorityType, TokenInstruction},
+    state::{
+        native_mint, Account as TokenAccount, AccountState, COption, Mint, Multisig, Pack,
+        MAX_DECIMALS, MAX_SIGNERS,
+    },
 };
 
+#[cfg(feature = "events")]
+use spl_token_from_scratch::utils::events;
+
 // =============================================================================
 // TEST SETUP HELPERS
 // =============================================================================
@@ -113,6 +125,8 @@ async fn create_token_account(
             AccountMeta::new_readonly(*mint, false),
             AccountMeta::new_readonly(*owner, false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            // Only read with the fresh-account-check feature
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
         ],
         data: TokenInstruction::InitializeAccount.pack(),
     };
@@ -212,7 +226,7 @@ async fn get_token_account(
         .await
         .unwrap()
         .unwrap();
-    TokenAccount::unpack(&account.data).unwrap()
+    TokenAccount::unpack_from_slice(&account.data).unwrap()
 }
 
 /// Helper to get and unpack a mint
@@ -222,7 +236,7 @@ async fn get_mint(banks_client: &mut BanksClient, address: &Pubkey) -> Mint {
         .await
         .unwrap()
         .unwrap();
-    Mint::unpack(&account.data).unwrap()
+    Mint::unpack_from_slice(&account.data).unwrap()
 }
 
 /// Helper to get and unpack a multisig
@@ -359,6 +373,59 @@ async fn test_initialize_mint_already_initialized_fails() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn test_initialize_mint_decimals_within_max() {
+    let mut context = program_test().start_with_context().await;
+
+    // Both ends of the allowed range, and the usual SOL precision
+    for decimals in [0, 9, MAX_DECIMALS] {
+        let mint = Keypair::new();
+        let blockhash = get_recent_blockhash(&mut context).await;
+
+        create_mint(
+            &mut context.banks_client,
+            &context.payer,
+            &mint,
+            &Keypair::new().pubkey(),
+            None,
+            decimals,
+            blockhash,
+        )
+        .await
+        .unwrap();
+
+        let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+        assert_eq!(mint_state.decimals, decimals);
+    }
+}
+
+#[tokio::test]
+async fn test_initialize_mint_decimals_above_max_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+
+    let result = create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &Keypair::new().pubkey(),
+        None,
+        MAX_DECIMALS + 1,
+        context.last_blockhash,
+    )
+    .await;
+
+    // Instruction 1 is the InitializeMint after the create
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(TokenError::InvalidDecimals as u32)
+        )
+    );
+}
+
 #[tokio::test]
 async fn test_initialize_account() {
     let mut context = program_test().start_with_context().await;
@@ -410,6 +477,78 @@ async fn test_initialize_account() {
     assert!(account_state.close_authority.is_none());
 }
 
+#[tokio::test]
+async fn test_initialize_account_already_initialized_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    // First initialization
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Try to initialize again with a different owner
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(Keypair::new().pubkey(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
+        ],
+        data: TokenInstruction::InitializeAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::AlreadyInitialized as u32)
+        )
+    );
+
+    // Owner unchanged
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.owner, owner.pubkey());
+}
+
 #[tokio::test]
 async fn test_initialize_multisig() {
     let mut context = program_test().start_with_context().await;
@@ -443,6 +582,60 @@ async fn test_initialize_multisig() {
     assert_eq!(multisig_state.signers[2], signer3.pubkey());
 }
 
+#[tokio::test]
+async fn test_initialize_multisig_already_initialized_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let multisig = Keypair::new();
+    let signer1 = Keypair::new();
+    let signer2 = Keypair::new();
+
+    // First initialization (2-of-2)
+    create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &multisig,
+        &[&signer1.pubkey(), &signer2.pubkey()],
+        2,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Try to initialize again as a 1-of-1 with a different signer
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(multisig.pubkey(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(Keypair::new().pubkey(), false),
+        ],
+        data: TokenInstruction::InitializeMultisig { m: 1 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::AlreadyInitialized as u32)
+        )
+    );
+
+    // Config unchanged
+    let multisig_state = get_multisig(&mut context.banks_client, &multisig.pubkey()).await;
+    assert_eq!(multisig_state.m, 2);
+    assert_eq!(multisig_state.n, 2);
+}
+
 #[tokio::test]
 async fn test_initialize_multisig_invalid_m_fails() {
     let mut context = program_test().start_with_context().await;
@@ -464,7 +657,170 @@ async fn test_initialize_multisig_invalid_m_fails() {
     )
     .await;
 
-    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(TokenError::InvalidMultisigConfig as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_initialize_multisig_no_signers_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // 1-of-0: no signer accounts passed at all
+    let result = create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &Keypair::new(),
+        &[],
+        1,
+        context.last_blockhash,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(TokenError::InvalidMultisigConfig as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_initialize_multisig_too_many_signers_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // One signer more than a multisig can hold
+    let keys: Vec<Pubkey> = (0..MAX_SIGNERS + 1).map(|_| Pubkey::new_unique()).collect();
+    let signers: Vec<&Pubkey> = keys.iter().collect();
+
+    let result = create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &Keypair::new(),
+        &signers,
+        1,
+        context.last_blockhash,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(TokenError::InvalidMultisigConfig as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_initialize_account_not_rent_exempt_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Fund the token account one lamport short of rent exemption
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &token_account.pubkey(),
+        rent.minimum_balance(TokenAccount::LEN) - 1,
+        TokenAccount::LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
+        ],
+        data: TokenInstruction::InitializeAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &token_account],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(TokenError::NotRentExempt as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_initialize_multisig_not_rent_exempt_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Fund the multisig one lamport short of rent exemption
+    let multisig = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &multisig.pubkey(),
+        rent.minimum_balance(Multisig::LEN) - 1,
+        Multisig::LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(multisig.pubkey(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(Keypair::new().pubkey(), false),
+            AccountMeta::new_readonly(Keypair::new().pubkey(), false),
+        ],
+        data: TokenInstruction::InitializeMultisig { m: 2 }.pack(),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &multisig],
+        context.last_blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(TokenError::NotRentExempt as u32)
+        )
+    );
 }
 
 // =============================================================================
@@ -668,15 +1024,10 @@ async fn test_mint_to_wrong_mint_fails() {
     assert!(result.is_err());
 }
 
-// =============================================================================
-// TRANSFER TESTS
-// =============================================================================
-
 #[tokio::test]
-async fn test_transfer() {
+async fn test_mint_to_with_delegate_fails() {
     let mut context = program_test().start_with_context().await;
 
-    // Setup
     let mint = Keypair::new();
     let mint_authority = Keypair::new();
 
@@ -692,17 +1043,303 @@ async fn test_transfer() {
     .await
     .unwrap();
 
-    let source_account = Keypair::new();
-    let source_owner = Keypair::new();
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+    let delegate = Keypair::new();
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
     create_token_account(
         &mut context.banks_client,
         &context.payer,
-        &source_account,
+        &token_account,
         &mint.pubkey(),
-        &source_owner.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Approve a delegate on the destination account
+    let approve_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::Approve { amount: 1000 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[approve_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // The delegate tries to mint; delegation doesn't apply to minting
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let result = mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &delegate,
+        100,
+        blockhash,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::InvalidAuthority as u32)
+        )
+    );
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, 0);
+}
+
+#[tokio::test]
+async fn test_mint_to_mint_as_destination_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let mint_data_before = context
+        .banks_client
+        .get_account(mint.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    // The mint passed as its own destination
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let result = mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &mint.pubkey(),
+        &mint_authority,
+        100,
+        blockhash,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::ExpectedAccount as u32)
+        )
+    );
+
+    // The mint is untouched
+    let mint_data_after = context
+        .banks_client
+        .get_account(mint.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert_eq!(mint_data_after, mint_data_before);
+}
+
+#[tokio::test]
+async fn test_mint_to_uninitialized_account_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Token-account sized and owned by the program, but never initialized
+    let dest_account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &dest_account.pubkey(),
+        rent.minimum_balance(TokenAccount::LEN),
+        TokenAccount::LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &dest_account],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let result = mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &dest_account.pubkey(),
+        &mint_authority,
+        100,
+        blockhash,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::UninitializedAccount as u32)
+        )
+    );
+
+    // Nothing was written and no supply was created
+    let dest_data = context
+        .banks_client
+        .get_account(dest_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert!(dest_data.iter().all(|&byte| byte == 0));
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, 0);
+}
+
+#[tokio::test]
+async fn test_mint_to_read_only_destination_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let dest_account = Keypair::new();
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &dest_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Destination passed read-only
+    let mint_to_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(mint_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::MintTo { amount: 100 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[mint_to_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, 0);
+}
+
+// =============================================================================
+// TRANSFER TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_transfer() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let source_account = Keypair::new();
+    let source_owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &source_account,
+        &mint.pubkey(),
+        &source_owner.pubkey(),
         blockhash,
     )
     .await
@@ -750,6 +1387,7 @@ async fn test_transfer() {
             AccountMeta::new(source_account.pubkey(), false),
             AccountMeta::new(dest_account.pubkey(), false),
             AccountMeta::new_readonly(source_owner.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
         ],
         data: TokenInstruction::Transfer {
             amount: transfer_amount,
@@ -850,6 +1488,7 @@ async fn test_transfer_insufficient_funds_fails() {
             AccountMeta::new(source_account.pubkey(), false),
             AccountMeta::new(dest_account.pubkey(), false),
             AccountMeta::new_readonly(source_owner.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
         ],
         data: TokenInstruction::Transfer { amount: 200 }.pack(),
     };
@@ -940,6 +1579,7 @@ async fn test_transfer_wrong_owner_fails() {
             AccountMeta::new(source_account.pubkey(), false),
             AccountMeta::new(dest_account.pubkey(), false),
             AccountMeta::new_readonly(wrong_owner.pubkey(), true), // Wrong!
+            AccountMeta::new_readonly(mint.pubkey(), false),
         ],
         data: TokenInstruction::Transfer { amount: 100 }.pack(),
     };
@@ -957,104 +1597,147 @@ async fn test_transfer_wrong_owner_fails() {
     assert!(result.is_err());
 }
 
-// =============================================================================
-// BURN TESTS
-// =============================================================================
-
 #[tokio::test]
-async fn test_burn() {
+async fn test_transfer_to_self_fails() {
     let mut context = program_test().start_with_context().await;
 
-    // Setup
-    let mint = Keypair::new();
-    let mint_authority = Keypair::new();
+    let (token_account, owner) = setup_funded_account(&mut context).await;
 
-    create_mint(
-        &mut context.banks_client,
-        &context.payer,
-        &mint,
-        &mint_authority.pubkey(),
-        None,
-        9,
-        context.last_blockhash,
-    )
-    .await
-    .unwrap();
+    let mint = get_token_account(&mut context.banks_client, &token_account.pubkey())
+        .await
+        .mint;
 
-    let token_account = Keypair::new();
-    let owner = Keypair::new();
+    // Source and destination are the same account
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(mint, false),
+        ],
+        data: TokenInstruction::Transfer { amount: 100 }.pack(),
+    };
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
-    create_token_account(
-        &mut context.banks_client,
-        &context.payer,
-        &token_account,
-        &mint.pubkey(),
-        &owner.pubkey(),
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
         blockhash,
-    )
-    .await
-    .unwrap();
+    );
 
-    // Mint tokens
-    let initial_amount = 1000u64;
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::SelfTransfer as u32)
+        )
+    );
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.amount, 1000);
+}
+
+#[tokio::test]
+async fn test_transfer_between_same_owner_accounts() {
+    let mut context = program_test().start_with_context().await;
+
+    let (source_account, owner) = setup_funded_account(&mut context).await;
+    let mint = get_token_account(&mut context.banks_client, &source_account.pubkey())
+        .await
+        .mint;
+
+    // Second account with the same owner
+    let dest_account = Keypair::new();
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
-    mint_tokens(
+    create_token_account(
         &mut context.banks_client,
         &context.payer,
-        &mint.pubkey(),
-        &token_account.pubkey(),
-        &mint_authority,
-        initial_amount,
+        &dest_account,
+        &mint,
+        &owner.pubkey(),
         blockhash,
     )
     .await
     .unwrap();
 
-    // Burn some tokens
-    let burn_amount = 300u64;
+    let delegate = Keypair::new();
 
-    let burn_ix = Instruction {
+    let transfer_ix = |authority: &Keypair, amount: u64| Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
-            AccountMeta::new(token_account.pubkey(), false),
-            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+            AccountMeta::new_readonly(mint, false),
+        ],
+        data: TokenInstruction::Transfer { amount }.pack(),
+    };
+
+    let approve_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), false),
             AccountMeta::new_readonly(owner.pubkey(), true),
         ],
-        data: TokenInstruction::Burn {
-            amount: burn_amount,
-        }
-        .pack(),
+        data: TokenInstruction::Approve { amount: 100 }.pack(),
     };
 
+    // Owner consolidates 300, then a delegate moves 40 between the
+    // same accounts and is still charged against its allowance
     let blockhash = get_recent_blockhash(&mut context).await;
 
     let tx = Transaction::new_signed_with_payer(
-        &[burn_ix],
+        &[transfer_ix(&owner, 300), approve_ix, transfer_ix(&delegate, 40)],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &owner],
+        &[&context.payer, &owner, &delegate],
         blockhash,
     );
     context.banks_client.process_transaction(tx).await.unwrap();
 
-    // Verify
-    let account_state =
-        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
-    assert_eq!(account_state.amount, initial_amount - burn_amount);
+    let source_state =
+        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    let dest_state = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    assert_eq!(source_state.amount, 660);
+    assert_eq!(dest_state.amount, 340);
+    assert_eq!(source_state.delegate, COption::some(delegate.pubkey()));
+    assert_eq!(source_state.delegated_amount, 60);
 
-    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
-    assert_eq!(mint_state.supply, initial_amount - burn_amount);
+    // Anyone else is still rejected
+    let stranger = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix(&stranger, 1)],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stranger],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::InvalidAuthority as u32)
+        )
+    );
 }
 
 // =============================================================================
-// APPROVE AND REVOKE TESTS
+// BURN TESTS
 // =============================================================================
 
 #[tokio::test]
-async fn test_approve() {
+async fn test_burn() {
     let mut context = program_test().start_with_context().await;
 
     // Setup
@@ -1075,7 +1758,6 @@ async fn test_approve() {
 
     let token_account = Keypair::new();
     let owner = Keypair::new();
-    let delegate = Keypair::new();
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
@@ -1090,18 +1772,35 @@ async fn test_approve() {
     .await
     .unwrap();
 
-    // Approve
-    let approve_amount = 500u64;
+    // Mint tokens
+    let initial_amount = 1000u64;
 
-    let approve_ix = Instruction {
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        initial_amount,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Burn some tokens
+    let burn_amount = 300u64;
+
+    let burn_ix = Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
             AccountMeta::new(token_account.pubkey(), false),
-            AccountMeta::new_readonly(delegate.pubkey(), false),
+            AccountMeta::new(mint.pubkey(), false),
             AccountMeta::new_readonly(owner.pubkey(), true),
         ],
-        data: TokenInstruction::Approve {
-            amount: approve_amount,
+        data: TokenInstruction::Burn {
+            amount: burn_amount,
         }
         .pack(),
     };
@@ -1109,7 +1808,7 @@ async fn test_approve() {
     let blockhash = get_recent_blockhash(&mut context).await;
 
     let tx = Transaction::new_signed_with_payer(
-        &[approve_ix],
+        &[burn_ix],
         Some(&context.payer.pubkey()),
         &[&context.payer, &owner],
         blockhash,
@@ -1119,22 +1818,25 @@ async fn test_approve() {
     // Verify
     let account_state =
         get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
-    assert_eq!(account_state.delegate.as_ref().unwrap(), &delegate.pubkey());
-    assert_eq!(account_state.delegated_amount, approve_amount);
+    assert_eq!(account_state.amount, initial_amount - burn_amount);
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, initial_amount - burn_amount);
 }
 
 #[tokio::test]
-async fn test_transfer_with_delegate() {
+async fn test_burn_mint_field_mismatch() {
     let mut context = program_test().start_with_context().await;
 
-    // Setup
-    let mint = Keypair::new();
+    // Two mints under the same authority
+    let mint_a = Keypair::new();
+    let mint_b = Keypair::new();
     let mint_authority = Keypair::new();
 
     create_mint(
         &mut context.banks_client,
         &context.payer,
-        &mint,
+        &mint_a,
         &mint_authority.pubkey(),
         None,
         9,
@@ -1143,115 +1845,120 @@ async fn test_transfer_with_delegate() {
     .await
     .unwrap();
 
-    let source_account = Keypair::new();
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint_b,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Account for mint A, and one for mint B so B has supply to lose
+    let account_a = Keypair::new();
+    let account_b = Keypair::new();
     let owner = Keypair::new();
-    let delegate = Keypair::new();
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
     create_token_account(
         &mut context.banks_client,
         &context.payer,
-        &source_account,
-        &mint.pubkey(),
+        &account_a,
+        &mint_a.pubkey(),
         &owner.pubkey(),
         blockhash,
     )
     .await
     .unwrap();
 
-    let dest_account = Keypair::new();
-
     let blockhash = get_recent_blockhash(&mut context).await;
 
     create_token_account(
         &mut context.banks_client,
         &context.payer,
-        &dest_account,
-        &mint.pubkey(),
-        &Keypair::new().pubkey(),
+        &account_b,
+        &mint_b.pubkey(),
+        &owner.pubkey(),
         blockhash,
     )
     .await
     .unwrap();
 
-    // Mint tokens
     let blockhash = get_recent_blockhash(&mut context).await;
 
     mint_tokens(
         &mut context.banks_client,
         &context.payer,
-        &mint.pubkey(),
-        &source_account.pubkey(),
+        &mint_a.pubkey(),
+        &account_a.pubkey(),
         &mint_authority,
-        1000,
+        500,
         blockhash,
     )
     .await
     .unwrap();
 
-    // Approve delegate
-    let approve_ix = Instruction {
-        program_id: spl_token_from_scratch::id(),
-        accounts: vec![
-            AccountMeta::new(source_account.pubkey(), false),
-            AccountMeta::new_readonly(delegate.pubkey(), false),
-            AccountMeta::new_readonly(owner.pubkey(), true),
-        ],
-        data: TokenInstruction::Approve { amount: 500 }.pack(),
-    };
-
     let blockhash = get_recent_blockhash(&mut context).await;
 
-    let tx = Transaction::new_signed_with_payer(
-        &[approve_ix],
-        Some(&context.payer.pubkey()),
-        &[&context.payer, &owner],
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint_b.pubkey(),
+        &account_b.pubkey(),
+        &mint_authority,
+        500,
         blockhash,
-    );
-    context.banks_client.process_transaction(tx).await.unwrap();
-
-    // Transfer using delegate
-    let transfer_amount = 200u64;
+    )
+    .await
+    .unwrap();
 
-    let transfer_ix = Instruction {
+    // Burn from account A (mint field = A) but pass mint B
+    let burn_ix = Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
-            AccountMeta::new(source_account.pubkey(), false),
-            AccountMeta::new(dest_account.pubkey(), false),
-            AccountMeta::new_readonly(delegate.pubkey(), true), // Delegate signs
+            AccountMeta::new(account_a.pubkey(), false),
+            AccountMeta::new(mint_b.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
         ],
-        data: TokenInstruction::Transfer {
-            amount: transfer_amount,
-        }
-        .pack(),
+        data: TokenInstruction::Burn { amount: 100 }.pack(),
     };
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
     let tx = Transaction::new_signed_with_payer(
-        &[transfer_ix],
+        &[burn_ix],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &delegate],
+        &[&context.payer, &owner],
         blockhash,
     );
-    context.banks_client.process_transaction(tx).await.unwrap();
 
-    // Verify
-    let source_state =
-        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
-    assert_eq!(source_state.amount, 800); // 1000 - 200
-    assert_eq!(source_state.delegated_amount, 300); // 500 - 200
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::MintMismatch as u32)
+        )
+    );
 
-    let dest_state = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
-    assert_eq!(dest_state.amount, 200);
+    // Nothing was mutated
+    let account_state = get_token_account(&mut context.banks_client, &account_a.pubkey()).await;
+    assert_eq!(account_state.amount, 500);
+
+    let mint_b_state = get_mint(&mut context.banks_client, &mint_b.pubkey()).await;
+    assert_eq!(mint_b_state.supply, 500);
 }
 
 #[tokio::test]
-async fn test_delegate_exceeds_allowance_fails() {
+async fn test_burn_delegate_exceeds_allowance_fails() {
     let mut context = program_test().start_with_context().await;
 
-    // Setup
     let mint = Keypair::new();
     let mint_authority = Keypair::new();
 
@@ -1267,7 +1974,7 @@ async fn test_delegate_exceeds_allowance_fails() {
     .await
     .unwrap();
 
-    let source_account = Keypair::new();
+    let token_account = Keypair::new();
     let owner = Keypair::new();
     let delegate = Keypair::new();
 
@@ -1276,7 +1983,7 @@ async fn test_delegate_exceeds_allowance_fails() {
     create_token_account(
         &mut context.banks_client,
         &context.payer,
-        &source_account,
+        &token_account,
         &mint.pubkey(),
         &owner.pubkey(),
         blockhash,
@@ -1284,41 +1991,25 @@ async fn test_delegate_exceeds_allowance_fails() {
     .await
     .unwrap();
 
-    let dest_account = Keypair::new();
-
-    let blockhash = get_recent_blockhash(&mut context).await;
-
-    create_token_account(
-        &mut context.banks_client,
-        &context.payer,
-        &dest_account,
-        &mint.pubkey(),
-        &Keypair::new().pubkey(),
-        blockhash,
-    )
-    .await
-    .unwrap();
-
-    // Mint tokens
+    // Plenty of balance, small allowance
     let blockhash = get_recent_blockhash(&mut context).await;
 
     mint_tokens(
         &mut context.banks_client,
         &context.payer,
         &mint.pubkey(),
-        &source_account.pubkey(),
+        &token_account.pubkey(),
         &mint_authority,
-        1000,
+        10_000,
         blockhash,
     )
     .await
     .unwrap();
 
-    // Approve delegate for only 100
     let approve_ix = Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
-            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(token_account.pubkey(), false),
             AccountMeta::new_readonly(delegate.pubkey(), false),
             AccountMeta::new_readonly(owner.pubkey(), true),
         ],
@@ -1326,7 +2017,6 @@ async fn test_delegate_exceeds_allowance_fails() {
     };
 
     let blockhash = get_recent_blockhash(&mut context).await;
-
     let tx = Transaction::new_signed_with_payer(
         &[approve_ix],
         Some(&context.payer.pubkey()),
@@ -1335,32 +2025,130 @@ async fn test_delegate_exceeds_allowance_fails() {
     );
     context.banks_client.process_transaction(tx).await.unwrap();
 
-    // Try to transfer 200 (more than allowance)
-    let transfer_ix = Instruction {
+    // Delegate burns past its allowance (balance would cover it)
+    let burn_ix = Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
-            AccountMeta::new(source_account.pubkey(), false),
-            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(mint.pubkey(), false),
             AccountMeta::new_readonly(delegate.pubkey(), true),
         ],
-        data: TokenInstruction::Transfer { amount: 200 }.pack(), // Exceeds allowance
+        data: TokenInstruction::Burn { amount: 101 }.pack(),
     };
 
     let blockhash = get_recent_blockhash(&mut context).await;
-
     let tx = Transaction::new_signed_with_payer(
-        &[transfer_ix],
+        &[burn_ix],
         Some(&context.payer.pubkey()),
         &[&context.payer, &delegate],
         blockhash,
     );
 
     let result = context.banks_client.process_transaction(tx).await;
-    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::InsufficientDelegatedAmount as u32)
+        )
+    );
+
+    // Balance, allowance and supply unchanged
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.amount, 10_000);
+    assert_eq!(account_state.delegated_amount, 100);
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, 10_000);
 }
 
 #[tokio::test]
-async fn test_revoke() {
+async fn test_burn_supply_underflow_fails() {
+    let mint = Keypair::new();
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    // Supply below the account's balance can't be reached through the
+    // program, so inject the desynced state directly.
+    let mut program_test = program_test();
+
+    let mint_state = Mint {
+        supply: 50,
+        decimals: 9,
+        is_initialized: true,
+        ..Mint::default()
+    };
+    let mut mint_data = vec![0u8; Mint::LEN];
+    mint_state.pack(&mut mint_data).unwrap();
+
+    let account_state = TokenAccount {
+        mint: mint.pubkey(),
+        owner: owner.pubkey(),
+        amount: 100,
+        state: AccountState::Initialized,
+        ..TokenAccount::default()
+    };
+    let mut account_data = vec![0u8; TokenAccount::LEN];
+    account_state.pack(&mut account_data).unwrap();
+
+    for (pubkey, data) in [
+        (mint.pubkey(), mint_data),
+        (token_account.pubkey(), account_data),
+    ] {
+        program_test.add_account(
+            pubkey,
+            solana_sdk::account::Account {
+                lamports: solana_sdk::rent::Rent::default().minimum_balance(data.len()),
+                data,
+                owner: spl_token_from_scratch::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+    }
+
+    let mut context = program_test.start_with_context().await;
+
+    // The balance covers 80, the supply doesn't
+    let burn_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::Burn { amount: 80 }.pack(),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[burn_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        context.last_blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::SupplyUnderflow as u32)
+        )
+    );
+
+    // Nothing was burned
+    let account_state = get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.amount, 100);
+}
+
+
+// =============================================================================
+// APPROVE AND REVOKE TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_approve() {
     let mut context = program_test().start_with_context().await;
 
     // Setup
@@ -1397,6 +2185,8 @@ async fn test_revoke() {
     .unwrap();
 
     // Approve
+    let approve_amount = 500u64;
+
     let approve_ix = Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
@@ -1404,7 +2194,10 @@ async fn test_revoke() {
             AccountMeta::new_readonly(delegate.pubkey(), false),
             AccountMeta::new_readonly(owner.pubkey(), true),
         ],
-        data: TokenInstruction::Approve { amount: 500 }.pack(),
+        data: TokenInstruction::Approve {
+            amount: approve_amount,
+        }
+        .pack(),
     };
 
     let blockhash = get_recent_blockhash(&mut context).await;
@@ -1417,49 +2210,20 @@ async fn test_revoke() {
     );
     context.banks_client.process_transaction(tx).await.unwrap();
 
-    // Verify delegate is set
-    let account_state =
-        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
-    assert!(account_state.delegate.is_some());
-
-    // Revoke
-    let revoke_ix = Instruction {
-        program_id: spl_token_from_scratch::id(),
-        accounts: vec![
-            AccountMeta::new(token_account.pubkey(), false),
-            AccountMeta::new_readonly(owner.pubkey(), true),
-        ],
-        data: TokenInstruction::Revoke.pack(),
-    };
-
-    let blockhash = get_recent_blockhash(&mut context).await;
-
-    let tx = Transaction::new_signed_with_payer(
-        &[revoke_ix],
-        Some(&context.payer.pubkey()),
-        &[&context.payer, &owner],
-        blockhash,
-    );
-    context.banks_client.process_transaction(tx).await.unwrap();
-
-    // Verify delegate is cleared
+    // Verify
     let account_state =
         get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
-    assert!(account_state.delegate.is_none());
-    assert_eq!(account_state.delegated_amount, 0);
+    assert_eq!(account_state.delegate.as_ref().unwrap(), &delegate.pubkey());
+    assert_eq!(account_state.delegated_amount, approve_amount);
 }
 
-// =============================================================================
-// SET AUTHORITY TESTS
-// =============================================================================
-
 #[tokio::test]
-async fn test_set_authority_mint_tokens() {
+async fn test_transfer_with_delegate() {
     let mut context = program_test().start_with_context().await;
 
+    // Setup
     let mint = Keypair::new();
     let mint_authority = Keypair::new();
-    let new_authority = Keypair::new();
 
     create_mint(
         &mut context.banks_client,
@@ -1473,67 +2237,87 @@ async fn test_set_authority_mint_tokens() {
     .await
     .unwrap();
 
-    // Change mint authority
-    let set_auth_ix = Instruction {
-        program_id: spl_token_from_scratch::id(),
-        accounts: vec![
-            AccountMeta::new(mint.pubkey(), false),
-            AccountMeta::new_readonly(mint_authority.pubkey(), true),
-        ],
-        data: TokenInstruction::SetAuthority {
-            authority_type: AuthorityType::MintTokens,
-            new_authority: Some(new_authority.pubkey()),
-        }
-        .pack(),
-    };
+    let source_account = Keypair::new();
+    let owner = Keypair::new();
+    let delegate = Keypair::new();
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
-    let tx = Transaction::new_signed_with_payer(
-        &[set_auth_ix],
-        Some(&context.payer.pubkey()),
-        &[&context.payer, &mint_authority],
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &source_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
         blockhash,
-    );
-    context.banks_client.process_transaction(tx).await.unwrap();
+    )
+    .await
+    .unwrap();
 
-    // Verify
-    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
-    assert_eq!(
-        mint_state.mint_authority.as_ref().unwrap(),
-        &new_authority.pubkey()
-    );
-}
+    let dest_account = Keypair::new();
 
-#[tokio::test]
-async fn test_set_authority_remove_mint_authority() {
-    let mut context = program_test().start_with_context().await;
+    let blockhash = get_recent_blockhash(&mut context).await;
 
-    let mint = Keypair::new();
-    let mint_authority = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &dest_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
 
-    create_mint(
+    // Mint tokens
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
         &mut context.banks_client,
         &context.payer,
-        &mint,
-        &mint_authority.pubkey(),
-        None,
-        9,
-        context.last_blockhash,
+        &mint.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        1000,
+        blockhash,
     )
     .await
     .unwrap();
 
-    // Remove mint authority (fixed supply)
-    let set_auth_ix = Instruction {
+    // Approve delegate
+    let approve_ix = Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
-            AccountMeta::new(mint.pubkey(), false),
-            AccountMeta::new_readonly(mint_authority.pubkey(), true),
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
         ],
-        data: TokenInstruction::SetAuthority {
-            authority_type: AuthorityType::MintTokens,
-            new_authority: None, // Remove!
+        data: TokenInstruction::Approve { amount: 500 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[approve_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Transfer using delegate
+    let transfer_amount = 200u64;
+
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), true), // Delegate signs
+            AccountMeta::new_readonly(mint.pubkey(), false),
+        ],
+        data: TokenInstruction::Transfer {
+            amount: transfer_amount,
         }
         .pack(),
     };
@@ -1541,24 +2325,25 @@ async fn test_set_authority_remove_mint_authority() {
     let blockhash = get_recent_blockhash(&mut context).await;
 
     let tx = Transaction::new_signed_with_payer(
-        &[set_auth_ix],
+        &[transfer_ix],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &mint_authority],
+        &[&context.payer, &delegate],
         blockhash,
     );
     context.banks_client.process_transaction(tx).await.unwrap();
 
     // Verify
-    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
-    assert!(mint_state.mint_authority.is_none());
-}
+    let source_state =
+        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source_state.amount, 800); // 1000 - 200
+    assert_eq!(source_state.delegated_amount, 300); // 500 - 200
 
-// =============================================================================
-// CLOSE ACCOUNT TESTS
-// =============================================================================
+    let dest_state = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    assert_eq!(dest_state.amount, 200);
+}
 
 #[tokio::test]
-async fn test_close_account() {
+async fn test_delegate_exceeds_allowance_fails() {
     let mut context = program_test().start_with_context().await;
 
     // Setup
@@ -1577,16 +2362,16 @@ async fn test_close_account() {
     .await
     .unwrap();
 
-    let token_account = Keypair::new();
+    let source_account = Keypair::new();
     let owner = Keypair::new();
-    let destination = context.payer.pubkey(); // Send rent to payer
+    let delegate = Keypair::new();
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
     create_token_account(
         &mut context.banks_client,
         &context.payer,
-        &token_account,
+        &source_account,
         &mint.pubkey(),
         &owner.pubkey(),
         blockhash,
@@ -1594,261 +2379,232 @@ async fn test_close_account() {
     .await
     .unwrap();
 
-    // Get initial balance
-    let initial_dest_balance = context
-        .banks_client
-        .get_account(destination)
-        .await
-        .unwrap()
-        .unwrap()
-        .lamports;
-
-    // Close account (balance is 0)
-    let close_ix = Instruction {
-        program_id: spl_token_from_scratch::id(),
-        accounts: vec![
-            AccountMeta::new(token_account.pubkey(), false),
-            AccountMeta::new(destination, false),
-            AccountMeta::new_readonly(owner.pubkey(), true),
-        ],
-        data: TokenInstruction::CloseAccount.pack(),
-    };
-
-    let blockhash = get_recent_blockhash(&mut context).await;
-
-    let tx = Transaction::new_signed_with_payer(
-        &[close_ix],
-        Some(&context.payer.pubkey()),
-        &[&context.payer, &owner],
-        blockhash,
-    );
-    context.banks_client.process_transaction(tx).await.unwrap();
-
-    // Verify account is closed (no longer exists or has 0 lamports)
-    let account = context
-        .banks_client
-        .get_account(token_account.pubkey())
-        .await
-        .unwrap();
-
-    // Account should be None or have 0 lamports
-    assert!(account.is_none() || account.unwrap().lamports == 0);
-
-    // Destination should have received the rent
-    let final_dest_balance = context
-        .banks_client
-        .get_account(destination)
-        .await
-        .unwrap()
-        .unwrap()
-        .lamports;
-
-    // Balance should have increased (accounting for transaction fee)
-    assert!(final_dest_balance > initial_dest_balance - 10000); // Allow for fee
-}
-
-#[tokio::test]
-async fn test_close_account_with_balance_fails() {
-    let mut context = program_test().start_with_context().await;
-
-    // Setup
-    let mint = Keypair::new();
-    let mint_authority = Keypair::new();
-
-    create_mint(
-        &mut context.banks_client,
-        &context.payer,
-        &mint,
-        &mint_authority.pubkey(),
-        None,
-        9,
-        context.last_blockhash,
-    )
-    .await
-    .unwrap();
-
-    let token_account = Keypair::new();
-    let owner = Keypair::new();
+    let dest_account = Keypair::new();
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
     create_token_account(
         &mut context.banks_client,
         &context.payer,
-        &token_account,
+        &dest_account,
         &mint.pubkey(),
-        &owner.pubkey(),
+        &Keypair::new().pubkey(),
         blockhash,
     )
     .await
     .unwrap();
 
-    // Mint some tokens
+    // Mint tokens
     let blockhash = get_recent_blockhash(&mut context).await;
 
     mint_tokens(
         &mut context.banks_client,
         &context.payer,
         &mint.pubkey(),
-        &token_account.pubkey(),
+        &source_account.pubkey(),
         &mint_authority,
-        100, // Non-zero balance
+        1000,
         blockhash,
     )
     .await
     .unwrap();
 
-    // Try to close account with balance
-    let close_ix = Instruction {
+    // Approve delegate for only 100
+    let approve_ix = Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
-            AccountMeta::new(token_account.pubkey(), false),
-            AccountMeta::new(context.payer.pubkey(), false),
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), false),
             AccountMeta::new_readonly(owner.pubkey(), true),
         ],
-        data: TokenInstruction::CloseAccount.pack(),
+        data: TokenInstruction::Approve { amount: 100 }.pack(),
     };
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
     let tx = Transaction::new_signed_with_payer(
-        &[close_ix],
+        &[approve_ix],
         Some(&context.payer.pubkey()),
         &[&context.payer, &owner],
         blockhash,
     );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Try to transfer 200 (more than allowance)
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+        ],
+        data: TokenInstruction::Transfer { amount: 200 }.pack(), // Exceeds allowance
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &delegate],
+        blockhash,
+    );
 
-    // Should fail
     let result = context.banks_client.process_transaction(tx).await;
     assert!(result.is_err());
 }
 
-// =============================================================================
-// FREEZE AND THAW TESTS
-// =============================================================================
-
-#[tokio::test]
-async fn test_freeze_and_thaw_account() {
+/// Fund an account with `balance`, approve a delegate for `allowance`,
+/// then have the delegate transfer `amount`. Returns the failure.
+async fn delegate_transfer_error(balance: u64, allowance: u64, amount: u64) -> TransactionError {
     let mut context = program_test().start_with_context().await;
 
-    // Setup with freeze authority
+    // Setup
     let mint = Keypair::new();
     let mint_authority = Keypair::new();
-    let freeze_authority = Keypair::new();
 
     create_mint(
         &mut context.banks_client,
         &context.payer,
         &mint,
         &mint_authority.pubkey(),
-        Some(&freeze_authority.pubkey()),
+        None,
         9,
         context.last_blockhash,
     )
     .await
     .unwrap();
 
-    let token_account = Keypair::new();
+    let source_account = Keypair::new();
+    let dest_account = Keypair::new();
     let owner = Keypair::new();
+    let delegate = Keypair::new();
+
+    let accounts = [
+        (&source_account, owner.pubkey()),
+        (&dest_account, Keypair::new().pubkey()),
+    ];
+    for (account, account_owner) in accounts {
+        let blockhash = get_recent_blockhash(&mut context).await;
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            account,
+            &mint.pubkey(),
+            &account_owner,
+            blockhash,
+        )
+        .await
+        .unwrap();
+    }
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
-    create_token_account(
+    mint_tokens(
         &mut context.banks_client,
         &context.payer,
-        &token_account,
         &mint.pubkey(),
-        &owner.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        balance,
         blockhash,
     )
     .await
     .unwrap();
 
-    // Freeze the account
-    let freeze_ix = Instruction {
+    let approve_ix = Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
-            AccountMeta::new(token_account.pubkey(), false),
-            AccountMeta::new_readonly(mint.pubkey(), false),
-            AccountMeta::new_readonly(freeze_authority.pubkey(), true),
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
         ],
-        data: TokenInstruction::FreezeAccount.pack(),
+        data: TokenInstruction::Approve { amount: allowance }.pack(),
     };
 
-    let blockhash = get_recent_blockhash(&mut context).await;
-
-    let tx = Transaction::new_signed_with_payer(
-        &[freeze_ix],
-        Some(&context.payer.pubkey()),
-        &[&context.payer, &freeze_authority],
-        blockhash,
-    );
-    context.banks_client.process_transaction(tx).await.unwrap();
-
-    // Verify frozen
-    let account_state =
-        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
-    assert!(account_state.is_frozen());
-    assert_eq!(account_state.state, AccountState::Frozen);
-
-    // Thaw the account
-    let thaw_ix = Instruction {
+    let transfer_ix = Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
-            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), true),
             AccountMeta::new_readonly(mint.pubkey(), false),
-            AccountMeta::new_readonly(freeze_authority.pubkey(), true),
         ],
-        data: TokenInstruction::ThawAccount.pack(),
+        data: TokenInstruction::Transfer { amount }.pack(),
     };
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
     let tx = Transaction::new_signed_with_payer(
-        &[thaw_ix],
+        &[approve_ix, transfer_ix],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &freeze_authority],
+        &[&context.payer, &owner, &delegate],
         blockhash,
     );
-    context.banks_client.process_transaction(tx).await.unwrap();
 
-    // Verify thawed
-    let account_state =
-        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
-    assert!(!account_state.is_frozen());
-    assert_eq!(account_state.state, AccountState::Initialized);
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err()
+        .unwrap()
 }
 
 #[tokio::test]
-async fn test_transfer_from_frozen_account_fails() {
+async fn test_delegate_transfer_beyond_balance_fails() {
+    // Allowance 1000, balance 100: the balance is the limit
+    assert_eq!(
+        delegate_transfer_error(100, 1000, 500).await,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(TokenError::InsufficientFunds as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_delegate_transfer_beyond_allowance_fails() {
+    // Balance 1000, allowance 50: the allowance is the limit
+    assert_eq!(
+        delegate_transfer_error(1000, 50, 100).await,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(TokenError::InsufficientDelegatedAmount as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_revoke() {
     let mut context = program_test().start_with_context().await;
 
     // Setup
     let mint = Keypair::new();
     let mint_authority = Keypair::new();
-    let freeze_authority = Keypair::new();
 
     create_mint(
         &mut context.banks_client,
         &context.payer,
         &mint,
         &mint_authority.pubkey(),
-        Some(&freeze_authority.pubkey()),
+        None,
         9,
         context.last_blockhash,
     )
     .await
     .unwrap();
 
-    let source_account = Keypair::new();
+    let token_account = Keypair::new();
     let owner = Keypair::new();
+    let delegate = Keypair::new();
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
     create_token_account(
         &mut context.banks_client,
         &context.payer,
-        &source_account,
+        &token_account,
         &mint.pubkey(),
         &owner.pubkey(),
         blockhash,
@@ -1856,479 +2612,8509 @@ async fn test_transfer_from_frozen_account_fails() {
     .await
     .unwrap();
 
-    let dest_account = Keypair::new();
-
-    let blockhash = get_recent_blockhash(&mut context).await;
-
-    create_token_account(
-        &mut context.banks_client,
-        &context.payer,
-        &dest_account,
-        &mint.pubkey(),
-        &Keypair::new().pubkey(),
-        blockhash,
-    )
-    .await
-    .unwrap();
-
-    // Mint tokens
-    let blockhash = get_recent_blockhash(&mut context).await;
-
-    mint_tokens(
-        &mut context.banks_client,
-        &context.payer,
-        &mint.pubkey(),
-        &source_account.pubkey(),
-        &mint_authority,
-        1000,
-        blockhash,
-    )
-    .await
-    .unwrap();
-
-    // Freeze the source account
-    let freeze_ix = Instruction {
+    // Approve
+    let approve_ix = Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
-            AccountMeta::new(source_account.pubkey(), false),
-            AccountMeta::new_readonly(mint.pubkey(), false),
-            AccountMeta::new_readonly(freeze_authority.pubkey(), true),
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
         ],
-        data: TokenInstruction::FreezeAccount.pack(),
+        data: TokenInstruction::Approve { amount: 500 }.pack(),
     };
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
     let tx = Transaction::new_signed_with_payer(
-        &[freeze_ix],
+        &[approve_ix],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &freeze_authority],
+        &[&context.payer, &owner],
         blockhash,
     );
     context.banks_client.process_transaction(tx).await.unwrap();
 
-    // Try to transfer from frozen account
-    let transfer_ix = Instruction {
+    // Verify delegate is set
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert!(account_state.delegate.is_some());
+
+    // Revoke
+    let revoke_ix = Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
-            AccountMeta::new(source_account.pubkey(), false),
-            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new(token_account.pubkey(), false),
             AccountMeta::new_readonly(owner.pubkey(), true),
         ],
-        data: TokenInstruction::Transfer { amount: 100 }.pack(),
+        data: TokenInstruction::Revoke.pack(),
     };
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
     let tx = Transaction::new_signed_with_payer(
-        &[transfer_ix],
+        &[revoke_ix],
         Some(&context.payer.pubkey()),
         &[&context.payer, &owner],
         blockhash,
     );
+    context.banks_client.process_transaction(tx).await.unwrap();
 
-    // Should fail
-    let result = context.banks_client.process_transaction(tx).await;
-    assert!(result.is_err());
+    // Verify delegate is cleared
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert!(account_state.delegate.is_none());
+    assert_eq!(account_state.delegated_amount, 0);
 }
 
-// =============================================================================
-// MULTISIG AUTHORITY TESTS
-// =============================================================================
-
 #[tokio::test]
-async fn test_mint_with_multisig_authority() {
+async fn test_revoke_by_delegate_fails() {
     let mut context = program_test().start_with_context().await;
 
-    // Create signers
-    let signer1 = Keypair::new();
-    let signer2 = Keypair::new();
-    let signer3 = Keypair::new();
+    let (token_account, owner) = setup_funded_account(&mut context).await;
+    let delegate = Keypair::new();
 
-    // Create 2-of-3 multisig
-    let multisig = Keypair::new();
+    let approve_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::Approve { amount: 500 }.pack(),
+    };
 
-    let signers = vec![&signer1.pubkey(), &signer2.pubkey(), &signer3.pubkey()];
+    let blockhash = get_recent_blockhash(&mut context).await;
 
-    create_multisig(
-        &mut context.banks_client,
-        &context.payer,
-        &multisig,
-        &signers,
-        2,
-        context.last_blockhash,
-    )
-    .await
-    .unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[approve_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
 
-    // Create mint with multisig as authority
-    let mint = Keypair::new();
+    // The delegate can't revoke its own approval
+    let revoke_ix = |authority: &Pubkey| Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: TokenInstruction::Revoke.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[revoke_ix(&delegate.pubkey())],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &delegate],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::InvalidAuthority as u32)
+        )
+    );
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.delegate, COption::some(delegate.pubkey()));
+    assert_eq!(account_state.delegated_amount, 500);
 
+    // The owner can
     let blockhash = get_recent_blockhash(&mut context).await;
 
+    let tx = Transaction::new_signed_with_payer(
+        &[revoke_ix(&owner.pubkey())],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert!(account_state.delegate.is_none());
+    assert_eq!(account_state.delegated_amount, 0);
+}
+
+// =============================================================================
+// SET AUTHORITY TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_set_authority_mint_tokens() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let new_authority = Keypair::new();
+
     create_mint(
         &mut context.banks_client,
         &context.payer,
         &mint,
-        &multisig.pubkey(), // Multisig is mint authority
+        &mint_authority.pubkey(),
         None,
         9,
-        blockhash,
+        context.last_blockhash,
     )
     .await
     .unwrap();
 
-    // Create token account
-    let token_account = Keypair::new();
+    // Change mint authority
+    let set_auth_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(mint_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::MintTokens,
+            new_authority: Some(new_authority.pubkey()),
+        }
+        .pack(),
+    };
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
-    create_token_account(
+    let tx = Transaction::new_signed_with_payer(
+        &[set_auth_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(
+        mint_state.mint_authority.as_ref().unwrap(),
+        &new_authority.pubkey()
+    );
+}
+
+#[tokio::test]
+async fn test_set_authority_remove_mint_authority() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
         &mut context.banks_client,
         &context.payer,
-        &token_account,
-        &mint.pubkey(),
-        &Keypair::new().pubkey(),
-        blockhash,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
     )
     .await
     .unwrap();
 
-    // Mint using multisig authority (2 signers)
-    let mint_to_ix = Instruction {
+    // Remove mint authority (fixed supply)
+    let set_auth_ix = Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
             AccountMeta::new(mint.pubkey(), false),
-            AccountMeta::new(token_account.pubkey(), false),
-            AccountMeta::new_readonly(multisig.pubkey(), false), // Multisig account
-            AccountMeta::new_readonly(signer1.pubkey(), true),   // Signer 1
-            AccountMeta::new_readonly(signer2.pubkey(), true),   // Signer 2
+            AccountMeta::new_readonly(mint_authority.pubkey(), true),
         ],
-        data: TokenInstruction::MintTo { amount: 1000 }.pack(),
+        data: TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::MintTokens,
+            new_authority: None, // Remove!
+        }
+        .pack(),
     };
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
     let tx = Transaction::new_signed_with_payer(
-        &[mint_to_ix],
+        &[set_auth_ix],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &signer1, &signer2], // Two signers sign
+        &[&context.payer, &mint_authority],
         blockhash,
     );
     context.banks_client.process_transaction(tx).await.unwrap();
 
     // Verify
-    let account_state =
-        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
-    assert_eq!(account_state.amount, 1000);
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert!(mint_state.mint_authority.is_none());
 }
 
 #[tokio::test]
-async fn test_mint_with_multisig_insufficient_signers_fails() {
+async fn test_set_authority_revoke_mint_authority_logs() {
     let mut context = program_test().start_with_context().await;
 
-    // Create signers
-    let signer1 = Keypair::new();
-    let signer2 = Keypair::new();
-    let signer3 = Keypair::new();
-
-    // Create 2-of-3 multisig
-    let multisig = Keypair::new();
-
-    let signers = vec![&signer1.pubkey(), &signer2.pubkey(), &signer3.pubkey()];
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
 
-    create_multisig(
+    create_mint(
         &mut context.banks_client,
         &context.payer,
-        &multisig,
-        &signers,
-        2, // Requires 2 signers
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
         context.last_blockhash,
     )
     .await
     .unwrap();
 
-    // Create mint with multisig as authority
-    let mint = Keypair::new();
+    // Revoke mint authority
+    let set_auth_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(mint_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::MintTokens,
+            new_authority: None,
+        }
+        .pack(),
+    };
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
+    let tx = Transaction::new_signed_with_payer(
+        &[set_auth_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    assert!(result.result.is_ok());
+
+    // Indexers can see the revocation in the logs
+    let log_messages = result.metadata.unwrap().log_messages;
+    assert!(log_messages
+        .iter()
+        .any(|line| line == "Program log: SetAuthority: MintTokens removed"));
+}
+
+#[tokio::test]
+async fn test_set_authority_add_freeze_authority_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Mint created without a freeze authority
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
     create_mint(
         &mut context.banks_client,
         &context.payer,
         &mint,
-        &multisig.pubkey(),
+        &mint_authority.pubkey(),
         None,
         9,
-        blockhash,
+        context.last_blockhash,
     )
     .await
     .unwrap();
 
-    // Create token account
-    let token_account = Keypair::new();
+    // Even the mint authority can't add one later
+    let set_auth_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(mint_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::FreezeAccount,
+            new_authority: Some(mint_authority.pubkey()),
+        }
+        .pack(),
+    };
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
-    create_token_account(
-        &mut context.banks_client,
-        &context.payer,
-        &token_account,
-        &mint.pubkey(),
-        &Keypair::new().pubkey(),
+    let tx = Transaction::new_signed_with_payer(
+        &[set_auth_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
         blockhash,
-    )
-    .await
-    .unwrap();
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::CannotAddFreezeAuthority as u32)
+        )
+    );
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert!(mint_state.freeze_authority.is_none());
+}
+
+// =============================================================================
+// CLOSE ACCOUNT TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_close_account() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+    let destination = context.payer.pubkey(); // Send rent to payer
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Get initial balance
+    let initial_dest_balance = context
+        .banks_client
+        .get_account(destination)
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+
+    // Close account (balance is 0)
+    let close_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::CloseAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify account is closed (no longer exists or has 0 lamports)
+    let account = context
+        .banks_client
+        .get_account(token_account.pubkey())
+        .await
+        .unwrap();
+
+    // Account should be None or have 0 lamports
+    assert!(account.is_none() || account.unwrap().lamports == 0);
+
+    // Destination should have received the rent
+    let final_dest_balance = context
+        .banks_client
+        .get_account(destination)
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+
+    // Balance should have increased (accounting for transaction fee)
+    assert!(final_dest_balance > initial_dest_balance - 10000); // Allow for fee
+}
+
+#[tokio::test]
+async fn test_close_account_with_balance_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Mint some tokens
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        100, // Non-zero balance
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Try to close account with balance
+    let close_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(context.payer.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::CloseAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    // Should fail
+    let result = context.banks_client.process_transaction(tx).await;
+    assert!(result.is_err());
+}
+
+// =============================================================================
+// FREEZE AND THAW TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_freeze_and_thaw_account() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup with freeze authority
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let freeze_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        Some(&freeze_authority.pubkey()),
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Freeze the account
+    let freeze_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(freeze_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::FreezeAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &freeze_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify frozen
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert!(account_state.is_frozen());
+    assert_eq!(account_state.state, AccountState::Frozen);
+
+    // Thaw the account
+    let thaw_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(freeze_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::ThawAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[thaw_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &freeze_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify thawed
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert!(!account_state.is_frozen());
+    assert_eq!(account_state.state, AccountState::Initialized);
+}
+
+#[tokio::test]
+async fn test_transfer_from_frozen_account_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let freeze_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        Some(&freeze_authority.pubkey()),
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let source_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &source_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let dest_account = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &dest_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Mint tokens
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        1000,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Freeze the source account
+    let freeze_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(freeze_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::FreezeAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &freeze_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Try to transfer from frozen account
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+        ],
+        data: TokenInstruction::Transfer { amount: 100 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    // Should fail
+    let result = context.banks_client.process_transaction(tx).await;
+    assert!(result.is_err());
+}
+
+/// Create a mint with a freeze authority and an empty token account for
+/// it; returns (token_account, owner, mint, freeze_authority).
+async fn setup_freezable_account(
+    context: &mut ProgramTestContext,
+) -> (Keypair, Keypair, Keypair, Keypair) {
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let freeze_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        Some(&freeze_authority.pubkey()),
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    (token_account, owner, mint, freeze_authority)
+}
+
+/// Create a mint with a freeze authority and a token account for it,
+/// then freeze the account; returns (token_account, owner).
+async fn setup_frozen_account(context: &mut ProgramTestContext) -> (Keypair, Keypair) {
+    let (token_account, owner, mint, freeze_authority) = setup_freezable_account(context).await;
+
+    let freeze_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(freeze_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::FreezeAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &freeze_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    (token_account, owner)
+}
+
+#[tokio::test]
+async fn test_approve_frozen_account_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let (token_account, owner) = setup_frozen_account(&mut context).await;
+
+    let approve_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(Keypair::new().pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::Approve { amount: 100 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[approve_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::AccountFrozen as u32)
+        )
+    );
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert!(account_state.delegate.is_none());
+}
+
+#[tokio::test]
+async fn test_set_owner_frozen_account_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let (token_account, owner) = setup_frozen_account(&mut context).await;
+
+    let set_owner_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::AccountOwner,
+            new_authority: Some(Keypair::new().pubkey()),
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[set_owner_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::AccountFrozen as u32)
+        )
+    );
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.owner, owner.pubkey());
+}
+
+/// Build a CloseAccount instruction paying the rent to `destination`.
+fn close_account_ix(account: &Pubkey, destination: &Pubkey, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: TokenInstruction::CloseAccount.pack(),
+    }
+}
+
+#[tokio::test]
+async fn test_close_frozen_account_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Empty but frozen: the balance check alone would let this through
+    let (token_account, owner) = setup_frozen_account(&mut context).await;
+
+    let close_ix = close_account_ix(
+        &token_account.pubkey(),
+        &context.payer.pubkey(),
+        &owner.pubkey(),
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::AccountFrozen as u32)
+        )
+    );
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.state, AccountState::Frozen);
+}
+
+#[tokio::test]
+async fn test_close_unfrozen_account_of_freezable_mint() {
+    let mut context = program_test().start_with_context().await;
+
+    // Same setup as above, minus the freeze
+    let (token_account, owner, _mint, _freeze_authority) =
+        setup_freezable_account(&mut context).await;
+
+    let close_ix = close_account_ix(
+        &token_account.pubkey(),
+        &context.payer.pubkey(),
+        &owner.pubkey(),
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(token_account.pubkey())
+        .await
+        .unwrap();
+    assert!(account.is_none() || account.unwrap().lamports == 0);
+}
+
+
+// =============================================================================
+// MULTISIG AUTHORITY TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_mint_with_multisig_authority() {
+    let mut context = program_test().start_with_context().await;
+
+    // Create signers
+    let signer1 = Keypair::new();
+    let signer2 = Keypair::new();
+    let signer3 = Keypair::new();
+
+    // Create 2-of-3 multisig
+    let multisig = Keypair::new();
+
+    let signers = vec![&signer1.pubkey(), &signer2.pubkey(), &signer3.pubkey()];
+
+    create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &multisig,
+        &signers,
+        2,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Create mint with multisig as authority
+    let mint = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &multisig.pubkey(), // Multisig is mint authority
+        None,
+        9,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Create token account
+    let token_account = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Mint using multisig authority (2 signers)
+    let mint_to_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(multisig.pubkey(), false), // Multisig account
+            AccountMeta::new_readonly(signer1.pubkey(), true),   // Signer 1
+            AccountMeta::new_readonly(signer2.pubkey(), true),   // Signer 2
+        ],
+        data: TokenInstruction::MintTo { amount: 1000 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[mint_to_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &signer1, &signer2], // Two signers sign
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.amount, 1000);
+}
+
+#[tokio::test]
+async fn test_mint_with_multisig_insufficient_signers_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Create signers
+    let signer1 = Keypair::new();
+    let signer2 = Keypair::new();
+    let signer3 = Keypair::new();
+
+    // Create 2-of-3 multisig
+    let multisig = Keypair::new();
+
+    let signers = vec![&signer1.pubkey(), &signer2.pubkey(), &signer3.pubkey()];
+
+    create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &multisig,
+        &signers,
+        2, // Requires 2 signers
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Create mint with multisig as authority
+    let mint = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &multisig.pubkey(),
+        None,
+        9,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Create token account
+    let token_account = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Try to mint with only 1 signer (needs 2)
+    let mint_to_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(multisig.pubkey(), false),
+            AccountMeta::new_readonly(signer1.pubkey(), true), // Only 1 signer!
+        ],
+        data: TokenInstruction::MintTo { amount: 1000 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[mint_to_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &signer1],
+        blockhash,
+    );
+
+    // Should fail - not enough signers
+    let result = context.banks_client.process_transaction(tx).await;
+    assert!(result.is_err());
+}
+
+/// Create a 2-of-3 multisig and a funded token account it owns, plus
+/// a destination; returns (source, destination, multisig, signers).
+async fn setup_multisig_owned_account(
+    context: &mut ProgramTestContext,
+) -> (Keypair, Keypair, Keypair, [Keypair; 3]) {
+    let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let multisig = Keypair::new();
+
+    create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &multisig,
+        &[
+            &signers[0].pubkey(),
+            &signers[1].pubkey(),
+            &signers[2].pubkey(),
+        ],
+        2,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let source = Keypair::new();
+    let destination = Keypair::new();
+
+    for (account, owner) in [
+        (&source, multisig.pubkey()),
+        (&destination, Keypair::new().pubkey()),
+    ] {
+        let blockhash = get_recent_blockhash(context).await;
+
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            account,
+            &mint.pubkey(),
+            &owner,
+            blockhash,
+        )
+        .await
+        .unwrap();
+    }
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source.pubkey(),
+        &mint_authority,
+        1000,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    (source, destination, multisig, signers)
+}
+
+#[tokio::test]
+async fn test_transfer_multisig_owner_not_enough_signers_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let (source, destination, multisig, signers) = setup_multisig_owned_account(&mut context).await;
+
+    let mint = get_token_account(&mut context.banks_client, &source.pubkey())
+        .await
+        .mint;
+
+    // The multisig is the owner, but only 1 of the 2 required signers signs
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source.pubkey(), false),
+            AccountMeta::new(destination.pubkey(), false),
+            AccountMeta::new_readonly(multisig.pubkey(), false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(signers[0].pubkey(), true),
+        ],
+        data: TokenInstruction::Transfer { amount: 100 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &signers[0]],
+        blockhash,
+    );
+
+    // The owner path's own error, not a generic InvalidAuthority
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::NotEnoughSigners as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_transfer_neither_owner_nor_delegate_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let (source, destination, _, _) = setup_multisig_owned_account(&mut context).await;
+
+    // A key that is neither the owner nor a delegate
+    let stranger = Keypair::new();
+
+    let mint = get_token_account(&mut context.banks_client, &source.pubkey())
+        .await
+        .mint;
+
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source.pubkey(), false),
+            AccountMeta::new(destination.pubkey(), false),
+            AccountMeta::new_readonly(stranger.pubkey(), true),
+            AccountMeta::new_readonly(mint, false),
+        ],
+        data: TokenInstruction::Transfer { amount: 100 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stranger],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::InvalidAuthority as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_transfer_multisig_delegate() {
+    let mut context = program_test().start_with_context().await;
+
+    let (source, owner) = setup_funded_account(&mut context).await;
+    let mint = get_token_account(&mut context.banks_client, &source.pubkey())
+        .await
+        .mint;
+
+    // 2-of-3 multisig as the delegate
+    let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let multisig = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &multisig,
+        &[
+            &signers[0].pubkey(),
+            &signers[1].pubkey(),
+            &signers[2].pubkey(),
+        ],
+        2,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let destination = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &destination,
+        &mint,
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let approve_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source.pubkey(), false),
+            AccountMeta::new_readonly(multisig.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::Approve { amount: 300 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[approve_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // The multisig delegate spends with 2 of its 3 signers
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source.pubkey(), false),
+            AccountMeta::new(destination.pubkey(), false),
+            AccountMeta::new_readonly(multisig.pubkey(), false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(signers[0].pubkey(), true),
+            AccountMeta::new_readonly(signers[2].pubkey(), true),
+        ],
+        data: TokenInstruction::Transfer { amount: 100 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &signers[0], &signers[2]],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let source_state = get_token_account(&mut context.banks_client, &source.pubkey()).await;
+    assert_eq!(source_state.amount, 900);
+    assert_eq!(source_state.delegate, COption::some(multisig.pubkey()));
+    assert_eq!(source_state.delegated_amount, 200);
+
+    let dest_state = get_token_account(&mut context.banks_client, &destination.pubkey()).await;
+    assert_eq!(dest_state.amount, 100);
+}
+
+#[tokio::test]
+async fn test_freeze_and_thaw_with_multisig_freeze_authority() {
+    let mut context = program_test().start_with_context().await;
+
+    // 2-of-3 multisig as the freeze authority
+    let signer1 = Keypair::new();
+    let signer2 = Keypair::new();
+    let signer3 = Keypair::new();
+    let multisig = Keypair::new();
+
+    let signers = vec![&signer1.pubkey(), &signer2.pubkey(), &signer3.pubkey()];
+
+    create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &multisig,
+        &signers,
+        2,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let mint = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &Keypair::new().pubkey(),
+        Some(&multisig.pubkey()),
+        9,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let freeze_or_thaw_ix = |instruction: TokenInstruction, signers: &[&Keypair]| {
+        let mut accounts = vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(multisig.pubkey(), false),
+        ];
+        for signer in signers {
+            accounts.push(AccountMeta::new_readonly(signer.pubkey(), true));
+        }
+        Instruction {
+            program_id: spl_token_from_scratch::id(),
+            accounts,
+            data: instruction.pack(),
+        }
+    };
+
+    // One signer is not enough
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_or_thaw_ix(TokenInstruction::FreezeAccount, &[&signer1])],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &signer1],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::NotEnoughSigners as u32)
+        )
+    );
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert!(!account_state.is_frozen());
+
+    // Two signers freeze
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_or_thaw_ix(TokenInstruction::FreezeAccount, &[&signer1, &signer2])],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &signer1, &signer2],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert!(account_state.is_frozen());
+
+    // A different two thaw
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_or_thaw_ix(TokenInstruction::ThawAccount, &[&signer2, &signer3])],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &signer2, &signer3],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert!(!account_state.is_frozen());
+}
+
+#[tokio::test]
+async fn test_rotate_multisig_mint_authority() {
+    let mut context = program_test().start_with_context().await;
+
+    // 2-of-3 multisig as the mint authority
+    let signer1 = Keypair::new();
+    let signer2 = Keypair::new();
+    let signer3 = Keypair::new();
+    let multisig = Keypair::new();
+
+    let signers = vec![&signer1.pubkey(), &signer2.pubkey(), &signer3.pubkey()];
+
+    create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &multisig,
+        &signers,
+        2,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let mint = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &multisig.pubkey(),
+        None,
+        9,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Rotate to a single key
+    let new_authority = Keypair::new();
+
+    let rotate_ix = |signers: &[&Keypair]| {
+        let mut accounts = vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(multisig.pubkey(), false),
+        ];
+        for signer in signers {
+            accounts.push(AccountMeta::new_readonly(signer.pubkey(), true));
+        }
+        Instruction {
+            program_id: spl_token_from_scratch::id(),
+            accounts,
+            data: TokenInstruction::SetAuthority {
+                authority_type: AuthorityType::MintTokens,
+                new_authority: Some(new_authority.pubkey()),
+            }
+            .pack(),
+        }
+    };
+
+    // One signer is not enough
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[rotate_ix(&[&signer1])],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &signer1],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::NotEnoughSigners as u32)
+        )
+    );
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.mint_authority, COption::some(multisig.pubkey()));
+
+    // Two signers rotate it
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[rotate_ix(&[&signer1, &signer3])],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &signer1, &signer3],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.mint_authority, COption::some(new_authority.pubkey()));
+
+    // The new key mints on its own
+    let token_account = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &new_authority,
+        100,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, 100);
+}
+
+
+// =============================================================================
+// EDGE CASE TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_transfer_zero_amount() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let source_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &source_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let dest_account = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &dest_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Transfer 0 tokens (should succeed, just a no-op)
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+        ],
+        data: TokenInstruction::Transfer { amount: 0 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    // Should succeed
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify balances unchanged
+    let source_state =
+        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source_state.amount, 0);
+
+    let dest_state = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    assert_eq!(dest_state.amount, 0);
+}
+
+#[tokio::test]
+async fn test_multiple_mints_and_transfers() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let account1 = Keypair::new();
+    let owner1 = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &account1,
+        &mint.pubkey(),
+        &owner1.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let account2 = Keypair::new();
+    let owner2 = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &account2,
+        &mint.pubkey(),
+        &owner2.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Mint 1000 to account1
+    let blockhash = get_recent_blockhash(&mut context).await;
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &account1.pubkey(),
+        &mint_authority,
+        1000,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Mint 500 more to account1
+    let blockhash = get_recent_blockhash(&mut context).await;
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &account1.pubkey(),
+        &mint_authority,
+        500,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Transfer 300 from account1 to account2
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(account1.pubkey(), false),
+            AccountMeta::new(account2.pubkey(), false),
+            AccountMeta::new_readonly(owner1.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+        ],
+        data: TokenInstruction::Transfer { amount: 300 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner1],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Transfer 100 from account2 to account1
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(account2.pubkey(), false),
+            AccountMeta::new(account1.pubkey(), false),
+            AccountMeta::new_readonly(owner2.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+        ],
+        data: TokenInstruction::Transfer { amount: 100 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner2],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify final balances
+    // account1: 1000 + 500 - 300 + 100 = 1300
+    // account2: 0 + 300 - 100 = 200
+    // supply: 1000 + 500 = 1500
+
+    let account1_state = get_token_account(&mut context.banks_client, &account1.pubkey()).await;
+    assert_eq!(account1_state.amount, 1300);
+
+    let account2_state = get_token_account(&mut context.banks_client, &account2.pubkey()).await;
+    assert_eq!(account2_state.amount, 200);
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, 1500);
+}
+
+// =============================================================================
+// INITIALIZE ACCOUNT WITH CLOSE AUTHORITY TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_initialize_account_with_close_authority() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+    let close_authority = Keypair::new();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &token_account.pubkey(),
+        rent.minimum_balance(TokenAccount::LEN),
+        TokenAccount::LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
+        ],
+        data: TokenInstruction::InitializeAccountWithCloseAuthority {
+            close_authority: close_authority.pubkey(),
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &token_account],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify close authority was set at creation
+    let account_state = get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+
+    assert_eq!(account_state.state, AccountState::Initialized);
+    assert_eq!(account_state.owner, owner.pubkey());
+    assert_eq!(
+        account_state.close_authority.as_ref().unwrap(),
+        &close_authority.pubkey()
+    );
+}
+
+#[tokio::test]
+async fn test_close_with_initial_close_authority() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+    let close_authority = Keypair::new();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &token_account.pubkey(),
+        rent.minimum_balance(TokenAccount::LEN),
+        TokenAccount::LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
+        ],
+        data: TokenInstruction::InitializeAccountWithCloseAuthority {
+            close_authority: close_authority.pubkey(),
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &token_account],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Owner alone cannot close - the close authority was delegated away
+    let close_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(context.payer.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::CloseAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert!(result.is_err());
+
+    // Close authority can close
+    let close_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(context.payer.pubkey(), false),
+            AccountMeta::new_readonly(close_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::CloseAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &close_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(token_account.pubkey())
+        .await
+        .unwrap();
+    assert!(account.is_none() || account.unwrap().lamports == 0);
+}
+
+// =============================================================================
+// TRANSFER BATCH TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_transfer_batch() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let source_account = Keypair::new();
+    let source_owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &source_account,
+        &mint.pubkey(),
+        &source_owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Three destinations
+    let dest_accounts = [Keypair::new(), Keypair::new(), Keypair::new()];
+    for dest_account in &dest_accounts {
+        let blockhash = get_recent_blockhash(&mut context).await;
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            dest_account,
+            &mint.pubkey(),
+            &Keypair::new().pubkey(),
+            blockhash,
+        )
+        .await
+        .unwrap();
+    }
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        1000,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Fan out 100 / 200 / 300
+    let amounts = vec![100u64, 200, 300];
+
+    let mut accounts = vec![
+        AccountMeta::new(source_account.pubkey(), false),
+        AccountMeta::new_readonly(source_owner.pubkey(), true),
+        AccountMeta::new_readonly(mint.pubkey(), false),
+    ];
+    for dest_account in &dest_accounts {
+        accounts.push(AccountMeta::new(dest_account.pubkey(), false));
+    }
+
+    let batch_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts,
+        data: TokenInstruction::TransferBatch {
+            amounts: amounts.clone(),
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[batch_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &source_owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify balances
+    let source_state =
+        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source_state.amount, 400);
+
+    for (dest_account, amount) in dest_accounts.iter().zip(&amounts) {
+        let dest_state =
+            get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+        assert_eq!(dest_state.amount, *amount);
+    }
+}
+
+#[tokio::test]
+async fn test_transfer_batch_overdraw_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let source_account = Keypair::new();
+    let source_owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &source_account,
+        &mint.pubkey(),
+        &source_owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let dest_accounts = [Keypair::new(), Keypair::new()];
+    for dest_account in &dest_accounts {
+        let blockhash = get_recent_blockhash(&mut context).await;
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            dest_account,
+            &mint.pubkey(),
+            &Keypair::new().pubkey(),
+            blockhash,
+        )
+        .await
+        .unwrap();
+    }
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        100,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Each amount fits on its own, but the sum (60 + 60) does not
+    let mut accounts = vec![
+        AccountMeta::new(source_account.pubkey(), false),
+        AccountMeta::new_readonly(source_owner.pubkey(), true),
+        AccountMeta::new_readonly(mint.pubkey(), false),
+    ];
+    for dest_account in &dest_accounts {
+        accounts.push(AccountMeta::new(dest_account.pubkey(), false));
+    }
+
+    let batch_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts,
+        data: TokenInstruction::TransferBatch {
+            amounts: vec![60, 60],
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[batch_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &source_owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert!(result.is_err());
+
+    // Nothing moved
+    let source_state =
+        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source_state.amount, 100);
+
+    for dest_account in &dest_accounts {
+        let dest_state =
+            get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+        assert_eq!(dest_state.amount, 0);
+    }
+}
+
+#[tokio::test]
+async fn test_transfer_batch_non_transferable_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_non_transferable_mint(&mut context, &mint, &mint_authority).await;
+
+    let source_account = Keypair::new();
+    let dest_account = Keypair::new();
+    let owner = Keypair::new();
+
+    for (account, account_owner) in [
+        (&source_account, owner.pubkey()),
+        (&dest_account, Keypair::new().pubkey()),
+    ] {
+        let blockhash = get_recent_blockhash(&mut context).await;
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            account,
+            &mint.pubkey(),
+            &account_owner,
+            blockhash,
+        )
+        .await
+        .unwrap();
+    }
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        10,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let batch_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+        ],
+        data: TokenInstruction::TransferBatch { amounts: vec![1] }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[batch_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::NonTransferable as u32)
+        )
+    );
+
+    let source = get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source.amount, 10);
+}
+
+#[tokio::test]
+async fn test_transfer_batch_to_memo_required_without_memo_fails() {
+    let mut context = program_test().start_with_context().await;
+    let (source_account, dest_account, owner) = setup_memo_required_accounts(&mut context).await;
+
+    let mint = get_token_account(&mut context.banks_client, &source_account.pubkey())
+        .await
+        .mint;
+
+    let batch_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
+        ],
+        data: TokenInstruction::TransferBatch { amounts: vec![250] }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[batch_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::MemoRequired as u32)
+        )
+    );
+
+    let dest = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    assert_eq!(dest.amount, 0);
+}
+
+#[tokio::test]
+async fn test_transfer_batch_hook_rejects() {
+    let hook_program = Pubkey::new_unique();
+    let mut program_test = program_test();
+    program_test.add_program(
+        "rejecting_transfer_hook",
+        hook_program,
+        processor!(rejecting_transfer_hook),
+    );
+    let mut context = program_test.start_with_context().await;
+
+    let (transfer_ix, owner, source, destination) =
+        setup_hooked_transfer(&mut context, hook_program).await;
+    let mint = transfer_ix.accounts[3].pubkey;
+
+    let batch_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source, false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(hook_program, false),
+        ],
+        data: TokenInstruction::TransferBatch { amounts: vec![100] }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[batch_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::TransferHookFailed as u32)
+        )
+    );
+
+    // Nothing moved
+    let source_state = get_token_account(&mut context.banks_client, &source).await;
+    let dest_state = get_token_account(&mut context.banks_client, &destination).await;
+    assert_eq!(source_state.amount, 1000);
+    assert_eq!(dest_state.amount, 0);
+}
+
+// =============================================================================
+// GET SUPPLY TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_get_supply_return_data() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        6,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        1_234_567,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Read supply through a simulated GetSupply
+    let get_supply_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![AccountMeta::new_readonly(mint.pubkey(), false)],
+        data: TokenInstruction::GetSupply.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[get_supply_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let simulation = context
+        .banks_client
+        .simulate_transaction(tx)
+        .await
+        .unwrap();
+    assert!(simulation.result.unwrap().is_ok());
+
+    let return_data = simulation
+        .simulation_details
+        .unwrap()
+        .return_data
+        .unwrap();
+    assert_eq!(return_data.program_id, spl_token_from_scratch::id());
+
+    let supply = u64::from_le_bytes(return_data.data[..8].try_into().unwrap());
+    assert_eq!(supply, 1_234_567);
+}
+
+// =============================================================================
+// BURN ALL TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_burn_all() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // A second holder, so supply doesn't drop to zero with the burn
+    let other_account = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &other_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        750,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &other_account.pubkey(),
+        &mint_authority,
+        250,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Burn everything without passing an amount
+    let burn_all_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::BurnAll.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[burn_all_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify: account emptied, only the other holder's tokens remain
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.amount, 0);
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, 250);
+}
+
+// =============================================================================
+// APPROVE ALL TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_approve_all_then_delegate_transfer() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let source_account = Keypair::new();
+    let owner = Keypair::new();
+    let delegate = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &source_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let dest_account = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &dest_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        800,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // ApproveAll
+    let approve_all_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::ApproveAll.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[approve_all_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify allowance equals the balance
+    let source_state =
+        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source_state.delegate.as_ref(), Some(&delegate.pubkey()));
+    assert_eq!(source_state.delegated_amount, 800);
+
+    // Delegate moves the full amount
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+        ],
+        data: TokenInstruction::Transfer { amount: 800 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &delegate],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify: source emptied, allowance used up and delegate cleared
+    let source_state =
+        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source_state.amount, 0);
+    assert_eq!(source_state.delegated_amount, 0);
+    assert!(source_state.delegate.is_none());
+
+    let dest_state = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    assert_eq!(dest_state.amount, 800);
+}
+
+#[tokio::test]
+async fn test_approve_all_frozen_account_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup with a freeze authority
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let freeze_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        Some(&freeze_authority.pubkey()),
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let source_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &source_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Freeze the account
+    let freeze_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(freeze_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::FreezeAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &freeze_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // ApproveAll should fail
+    let approve_all_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(Keypair::new().pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::ApproveAll.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[approve_all_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert!(result.is_err());
+}
+
+// =============================================================================
+// TRANSFER RETURN DATA TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_transfer_with_return_data() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let source_account = Keypair::new();
+    let source_owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &source_account,
+        &mint.pubkey(),
+        &source_owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let dest_account = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &dest_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        1000,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Transfer with the trailing return-balance flag
+    let data = TokenInstruction::TransferWithReturnData { amount: 350 }.pack();
+    assert_eq!(data.len(), 10);
+
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(source_owner.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+        ],
+        data,
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &source_owner],
+        blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    assert!(result.result.is_ok());
+
+    // Return data matches the remaining balance
+    let return_data = result.metadata.unwrap().return_data.unwrap();
+    assert_eq!(return_data.program_id, spl_token_from_scratch::id());
+    let returned_balance = u64::from_le_bytes(return_data.data[..8].try_into().unwrap());
+    assert_eq!(returned_balance, 650);
+
+    let source_state =
+        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source_state.amount, returned_balance);
+}
+
+// =============================================================================
+// STRICT MULTISIG TESTS (strict-multisig feature)
+// =============================================================================
+
+#[cfg(feature = "strict-multisig")]
+#[tokio::test]
+async fn test_strict_multisig_one_of_three_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let multisig = Keypair::new();
+    let signer1 = Keypair::new();
+    let signer2 = Keypair::new();
+    let signer3 = Keypair::new();
+
+    let signers = vec![&signer1.pubkey(), &signer2.pubkey(), &signer3.pubkey()];
+
+    let result = create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &multisig,
+        &signers,
+        1, // 1-of-3: rejected under the strict policy
+        context.last_blockhash,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(TokenError::MultisigThresholdTooLow as u32)
+        )
+    );
+}
+
+#[cfg(feature = "strict-multisig")]
+#[tokio::test]
+async fn test_strict_multisig_two_of_three_succeeds() {
+    let mut context = program_test().start_with_context().await;
+
+    let multisig = Keypair::new();
+    let signer1 = Keypair::new();
+    let signer2 = Keypair::new();
+    let signer3 = Keypair::new();
+
+    let signers = vec![&signer1.pubkey(), &signer2.pubkey(), &signer3.pubkey()];
+
+    create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &multisig,
+        &signers,
+        2,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let multisig_state = get_multisig(&mut context.banks_client, &multisig.pubkey()).await;
+    assert_eq!(multisig_state.m, 2);
+    assert_eq!(multisig_state.n, 3);
+}
+
+#[cfg(feature = "strict-multisig")]
+#[tokio::test]
+async fn test_strict_multisig_one_of_one_succeeds() {
+    let mut context = program_test().start_with_context().await;
+
+    let multisig = Keypair::new();
+    let signer1 = Keypair::new();
+
+    let signer1_key = signer1.pubkey();
+    let signers = vec![&signer1_key];
+
+    create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &multisig,
+        &signers,
+        1, // A genuine 1-of-1 is still allowed
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let multisig_state = get_multisig(&mut context.banks_client, &multisig.pubkey()).await;
+    assert_eq!(multisig_state.m, 1);
+    assert_eq!(multisig_state.n, 1);
+}
+
+// =============================================================================
+// OVERFLOW ATOMICITY TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_transfer_dest_overflow_leaves_source_unchanged() {
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let dest_account = Keypair::new();
+
+    // A destination already holding near u64::MAX can't be reached by
+    // minting (supply would overflow first), so inject it directly.
+    let mut program_test = program_test();
+
+    let dest_state = TokenAccount {
+        mint: mint.pubkey(),
+        owner: Keypair::new().pubkey(),
+        amount: u64::MAX - 5,
+        state: AccountState::Initialized,
+        ..TokenAccount::default()
+    };
+    let mut dest_data = vec![0u8; TokenAccount::LEN];
+    dest_state.pack(&mut dest_data).unwrap();
+
+    program_test.add_account(
+        dest_account.pubkey(),
+        solana_sdk::account::Account {
+            lamports: solana_sdk::rent::Rent::default().minimum_balance(TokenAccount::LEN),
+            data: dest_data,
+            owner: spl_token_from_scratch::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let mut context = program_test.start_with_context().await;
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let source_account = Keypair::new();
+    let source_owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &source_account,
+        &mint.pubkey(),
+        &source_owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        100,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Source can cover 10, but the destination can't hold it
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(source_owner.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+        ],
+        data: TokenInstruction::Transfer { amount: 10 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &source_owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::Overflow as u32)
+        )
+    );
+
+    // Neither side moved
+    let source_state =
+        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source_state.amount, 100);
+
+    let dest_state = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    assert_eq!(dest_state.amount, u64::MAX - 5);
+}
+
+// =============================================================================
+// FRESH ACCOUNT CHECK TESTS (fresh-account-check feature)
+// =============================================================================
+
+#[cfg(feature = "fresh-account-check")]
+#[tokio::test]
+async fn test_initialize_account_created_in_same_transaction() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // create_token_account sends CreateAccount + InitializeAccount together
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let account_state = get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.owner, owner.pubkey());
+}
+
+#[cfg(feature = "fresh-account-check")]
+#[tokio::test]
+async fn test_initialize_account_created_earlier_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Transaction 1: create the account only
+    let token_account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &token_account.pubkey(),
+        rent.minimum_balance(TokenAccount::LEN),
+        TokenAccount::LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &token_account],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Transaction 2: initialize it (what a front-runner would do)
+    let attacker = Keypair::new();
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(attacker.pubkey(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
+        ],
+        data: TokenInstruction::InitializeAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::AccountNotFreshlyCreated as u32)
+        )
+    );
+}
+
+// =============================================================================
+// OPTIONAL RENT SYSVAR TESTS
+// =============================================================================
+
+/// Test InitializeMint with and without the rent sysvar account.
+///
+/// create_mint passes the sysvar (legacy path). Leaving it out
+/// falls back to Rent::get() and must produce the same mint.
+#[tokio::test]
+async fn test_initialize_mint_optional_rent_sysvar() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint_authority = Keypair::new();
+
+    // With rent sysvar
+    let legacy_mint = Keypair::new();
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &legacy_mint,
+        &mint_authority.pubkey(),
+        None,
+        6,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Without rent sysvar
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(Mint::LEN),
+        Mint::LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![AccountMeta::new(mint.pubkey(), false)],
+        data: TokenInstruction::InitializeMint {
+            decimals: 6,
+            mint_authority: mint_authority.pubkey(),
+            freeze_authority: None,
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let legacy_state = get_mint(&mut context.banks_client, &legacy_mint.pubkey()).await;
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state, legacy_state);
+}
+
+/// Test InitializeAccount with and without the rent sysvar account.
+#[tokio::test]
+async fn test_initialize_account_optional_rent_sysvar() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let owner = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        6,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // With rent sysvar
+    let legacy_account = Keypair::new();
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &legacy_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Without rent sysvar (instructions sysvar still passed for
+    // the fresh-account-check feature)
+    let token_account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &token_account.pubkey(),
+        rent.minimum_balance(TokenAccount::LEN),
+        TokenAccount::LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
+        ],
+        data: TokenInstruction::InitializeAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &token_account],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let legacy_state = get_token_account(&mut context.banks_client, &legacy_account.pubkey()).await;
+    let account_state = get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state, legacy_state);
+}
+
+// =============================================================================
+// REALLOCATE TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_reallocate_grow_stays_rent_exempt() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        500,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Grow to the extended layout
+    let realloc_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::Reallocate {
+            new_len: TokenAccount::EXTENDED_LEN as u64,
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[realloc_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify size, rent and that the state is untouched
+    let account = context
+        .banks_client
+        .get_account(token_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    assert_eq!(account.data.len(), TokenAccount::EXTENDED_LEN);
+    assert!(rent.is_exempt(account.lamports, TokenAccount::EXTENDED_LEN));
+
+    let state = TokenAccount::unpack_from_slice(&account.data).unwrap();
+    assert_eq!(state.amount, 500);
+    assert_eq!(state.owner, owner.pubkey());
+
+    // The resized account is still usable: transfer out of it
+    let dest_account = Keypair::new();
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &dest_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+        ],
+        data: TokenInstruction::Transfer { amount: 200 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let state = get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(state.amount, 300);
+    let dest_state = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    assert_eq!(dest_state.amount, 200);
+}
+
+#[tokio::test]
+async fn test_reallocate_to_unreadable_length_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Below the account state, and between the two layouts
+    for new_len in [100, 200] {
+        let realloc_ix = Instruction {
+            program_id: spl_token_from_scratch::id(),
+            accounts: vec![
+                AccountMeta::new(token_account.pubkey(), false),
+                AccountMeta::new(context.payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(owner.pubkey(), true),
+            ],
+            data: TokenInstruction::Reallocate { new_len }.pack(),
+        };
+
+        let blockhash = get_recent_blockhash(&mut context).await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[realloc_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &owner],
+            blockhash,
+        );
+
+        let result = context.banks_client.process_transaction(tx).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::InvalidAccountDataLength as u32)
+            )
+        );
+    }
+}
+
+/// Build a Reallocate instruction with the payer and system program.
+fn reallocate_ix(account: &Pubkey, payer: &Pubkey, owner: &Pubkey, new_len: usize) -> Instruction {
+    Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data: TokenInstruction::Reallocate {
+            new_len: new_len as u64,
+        }
+        .pack(),
+    }
+}
+
+#[tokio::test]
+async fn test_reallocate_native_account_updates_reserve() {
+    let mut program_test = program_test();
+    add_native_mint(&mut program_test, None);
+
+    let mut context = program_test.start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let base_reserve = rent.minimum_balance(TokenAccount::LEN);
+    let extended_reserve = rent.minimum_balance(TokenAccount::EXTENDED_LEN);
+
+    let wrapped_account = Keypair::new();
+    let owner = Keypair::new();
+    create_wrapped_account(&mut context, &wrapped_account, &owner.pubkey(), 1_000_000_000).await;
+
+    // Grow: the payer covers the larger reserve, the balance is unchanged
+    let grow_ix = reallocate_ix(
+        &wrapped_account.pubkey(),
+        &context.payer.pubkey(),
+        &owner.pubkey(),
+        TokenAccount::EXTENDED_LEN,
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[grow_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(wrapped_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let state = TokenAccount::unpack_from_slice(&account.data).unwrap();
+    assert_eq!(state.is_native, COption::some(extended_reserve));
+    assert_eq!(state.amount, 1_000_000_000);
+    assert_eq!(account.lamports, extended_reserve + 1_000_000_000);
+
+    // Shrink: the freed reserve lamports join the balance
+    let shrink_ix = reallocate_ix(
+        &wrapped_account.pubkey(),
+        &context.payer.pubkey(),
+        &owner.pubkey(),
+        TokenAccount::LEN,
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[shrink_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(wrapped_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let state = TokenAccount::unpack_from_slice(&account.data).unwrap();
+    let amount = 1_000_000_000 + extended_reserve - base_reserve;
+    assert_eq!(state.is_native, COption::some(base_reserve));
+    assert_eq!(state.amount, amount);
+    assert_eq!(account.lamports, base_reserve + amount);
+
+    // Closing with a reserve destination splits at the updated reserve
+    let destination = Pubkey::new_unique();
+    let reserve_destination = Pubkey::new_unique();
+    let close_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(wrapped_account.pubkey(), false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new(reserve_destination, false),
+        ],
+        data: TokenInstruction::CloseAccountWithReserve.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let destination_lamports = context.banks_client.get_balance(destination).await.unwrap();
+    assert_eq!(destination_lamports, amount);
+
+    let reserve_lamports = context
+        .banks_client
+        .get_balance(reserve_destination)
+        .await
+        .unwrap();
+    assert_eq!(reserve_lamports, base_reserve);
+}
+
+#[tokio::test]
+async fn test_migrate_legacy_account() {
+    let mut context = program_test().start_with_context().await;
+
+    // A legacy 165-byte account with a balance and a delegate
+    let (token_account, owner) = setup_funded_account(&mut context).await;
+    let delegate = Pubkey::new_unique();
+
+    let approve_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate, false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::Approve { amount: 100 }.pack(),
+    };
+
+    let migrate_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::MigrateAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[approve_ix, migrate_ix.clone()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify size, rent, version byte and defaults
+    let account = context
+        .banks_client
+        .get_account(token_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    assert_eq!(account.data.len(), TokenAccount::EXTENDED_LEN);
+    assert!(rent.is_exempt(account.lamports, TokenAccount::EXTENDED_LEN));
+    assert_eq!(account.data[TokenAccount::LEN], TokenAccount::LAYOUT_VERSION);
+
+    let state = TokenAccount::unpack_from_slice(&account.data).unwrap();
+    assert_eq!(state.amount, 1000);
+    assert_eq!(state.owner, owner.pubkey());
+    assert_eq!(state.delegate, COption::some(delegate));
+    assert_eq!(state.delegated_amount, 100);
+    assert!(!state.memo_required);
+
+    // Migrating twice fails
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[migrate_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::InvalidAccountDataLength as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_native_account_updates_reserve() {
+    let mut program_test = program_test();
+    add_native_mint(&mut program_test, None);
+
+    let mut context = program_test.start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let extended_reserve = rent.minimum_balance(TokenAccount::EXTENDED_LEN);
+
+    let wrapped_account = Keypair::new();
+    let owner = Keypair::new();
+    create_wrapped_account(&mut context, &wrapped_account, &owner.pubkey(), 1_000_000_000).await;
+
+    let migrate_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(wrapped_account.pubkey(), false),
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::MigrateAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[migrate_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // The payer covered the larger reserve; the balance is unchanged
+    let account = context
+        .banks_client
+        .get_account(wrapped_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let state = TokenAccount::unpack_from_slice(&account.data).unwrap();
+    assert_eq!(state.is_native, COption::some(extended_reserve));
+    assert_eq!(state.amount, 1_000_000_000);
+    assert_eq!(account.lamports, extended_reserve + 1_000_000_000);
+
+    // Transferring the whole balance out leaves exactly the reserve
+    let dest_account = Keypair::new();
+    create_wrapped_account(&mut context, &dest_account, &Keypair::new().pubkey(), 0).await;
+
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(wrapped_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(native_mint::id(), false),
+        ],
+        data: TokenInstruction::Transfer {
+            amount: 1_000_000_000,
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let lamports = context
+        .banks_client
+        .get_balance(wrapped_account.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(lamports, extended_reserve);
+}
+
+// =============================================================================
+// NON-TRANSFERABLE TESTS
+// =============================================================================
+
+/// Helper to create an extended mint and flag it non-transferable
+async fn create_non_transferable_mint(
+    context: &mut ProgramTestContext,
+    mint: &Keypair,
+    mint_authority: &Keypair,
+) {
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(Mint::EXTENDED_LEN),
+        Mint::EXTENDED_LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![AccountMeta::new(mint.pubkey(), false)],
+        data: TokenInstruction::InitializeMint {
+            decimals: 0,
+            mint_authority: mint_authority.pubkey(),
+            freeze_authority: None,
+        }
+        .pack(),
+    };
+
+    let flag_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(mint_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::SetNonTransferable.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix, flag_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, mint, mint_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_non_transferable_mint_burn_ok_transfer_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_non_transferable_mint(&mut context, &mint, &mint_authority).await;
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert!(mint_state.non_transferable);
+
+    let source_account = Keypair::new();
+    let dest_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &source_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &dest_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Minting still works
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        10,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Transfer (with the mint passed) fails
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+        ],
+        data: TokenInstruction::Transfer { amount: 1 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::NonTransferable as u32)
+        )
+    );
+
+    // Burning still works
+    let burn_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::Burn { amount: 4 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[burn_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let source = get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source.amount, 6);
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, 6);
+}
+
+#[tokio::test]
+async fn test_non_transferable_mint_cannot_be_left_out() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_non_transferable_mint(&mut context, &mint, &mint_authority).await;
+
+    let source_account = Keypair::new();
+    let dest_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &source_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &dest_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        10,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // An ordinary, transferable mint to pass in the mint's place
+    let other_mint = Keypair::new();
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &other_mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let mut accounts = vec![
+        AccountMeta::new(source_account.pubkey(), false),
+        AccountMeta::new(dest_account.pubkey(), false),
+        AccountMeta::new_readonly(owner.pubkey(), true),
+    ];
+
+    // Mint left out, then swapped for another mint
+    let cases = [
+        (None, InstructionError::NotEnoughAccountKeys),
+        (
+            Some(other_mint.pubkey()),
+            InstructionError::Custom(TokenError::MintMismatch as u32),
+        ),
+    ];
+
+    for (mint_key, expected) in cases {
+        accounts.truncate(3);
+        accounts.extend(mint_key.map(|key| AccountMeta::new_readonly(key, false)));
+
+        let transfer_ix = Instruction {
+            program_id: spl_token_from_scratch::id(),
+            accounts: accounts.clone(),
+            data: TokenInstruction::Transfer { amount: 1 }.pack(),
+        };
+
+        let blockhash = get_recent_blockhash(&mut context).await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[transfer_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &owner],
+            blockhash,
+        );
+
+        let result = context.banks_client.process_transaction(tx).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, expected)
+        );
+    }
+
+    let source = get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source.amount, 10);
+}
+
+#[tokio::test]
+async fn test_set_non_transferable_base_mint_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        0,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let flag_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(mint_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::SetNonTransferable.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[flag_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::InvalidAccountDataLength as u32)
+        )
+    );
+}
+
+// =============================================================================
+// MEMO REQUIRED TESTS
+// =============================================================================
+
+/// Helper to set up a source with tokens and a memo-required destination.
+///
+/// Returns (source, destination, source owner).
+async fn setup_memo_required_accounts(
+    context: &mut ProgramTestContext,
+) -> (Keypair, Keypair, Keypair) {
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let source_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &source_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        1_000,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Extended destination with the memo requirement on
+    let dest_account = Keypair::new();
+    let dest_owner = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &dest_account.pubkey(),
+        rent.minimum_balance(TokenAccount::EXTENDED_LEN),
+        TokenAccount::EXTENDED_LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(dest_owner.pubkey(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
+        ],
+        data: TokenInstruction::InitializeAccount.pack(),
+    };
+
+    let memo_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(dest_owner.pubkey(), true),
+        ],
+        data: TokenInstruction::SetMemoRequired { required: true }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix, memo_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &dest_account, &dest_owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let dest = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    assert!(dest.memo_required);
+
+    (source_account, dest_account, owner)
+}
+
+#[tokio::test]
+async fn test_transfer_to_memo_required_with_memo() {
+    let mut context = program_test().start_with_context().await;
+    let (source_account, dest_account, owner) = setup_memo_required_accounts(&mut context).await;
+
+    let memo_ix = Instruction {
+        program_id: spl_token_from_scratch::utils::spl_memo::id(),
+        accounts: vec![],
+        data: b"invoice 42".to_vec(),
+    };
+
+    let mint = get_token_account(&mut context.banks_client, &source_account.pubkey())
+        .await
+        .mint;
+
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
+        ],
+        data: TokenInstruction::Transfer { amount: 250 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[memo_ix, transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let dest = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    assert_eq!(dest.amount, 250);
+}
+
+#[tokio::test]
+async fn test_transfer_to_memo_required_without_memo_fails() {
+    let mut context = program_test().start_with_context().await;
+    let (source_account, dest_account, owner) = setup_memo_required_accounts(&mut context).await;
+
+    let mint = get_token_account(&mut context.banks_client, &source_account.pubkey())
+        .await
+        .mint;
+
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
+        ],
+        data: TokenInstruction::Transfer { amount: 250 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::MemoRequired as u32)
+        )
+    );
+}
+
+// =============================================================================
+// RECONCILE SUPPLY TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_reconcile_supply_three_accounts() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        6,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Three accounts holding 100 + 200 + 300
+    let token_accounts = [Keypair::new(), Keypair::new(), Keypair::new()];
+
+    for (token_account, amount) in token_accounts.iter().zip([100u64, 200, 300]) {
+        let blockhash = get_recent_blockhash(&mut context).await;
+
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            token_account,
+            &mint.pubkey(),
+            &Keypair::new().pubkey(),
+            blockhash,
+        )
+        .await
+        .unwrap();
+
+        let blockhash = get_recent_blockhash(&mut context).await;
+
+        mint_tokens(
+            &mut context.banks_client,
+            &context.payer,
+            &mint.pubkey(),
+            &token_account.pubkey(),
+            &mint_authority,
+            amount,
+            blockhash,
+        )
+        .await
+        .unwrap();
+    }
+
+    // ReconcileSupply (strict, since every account is passed)
+    let mut accounts = vec![AccountMeta::new_readonly(mint.pubkey(), false)];
+    accounts.extend(
+        token_accounts
+            .iter()
+            .map(|account| AccountMeta::new_readonly(account.pubkey(), false)),
+    );
+
+    let reconcile_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts,
+        data: TokenInstruction::ReconcileSupply { strict: true }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[reconcile_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let simulation = context
+        .banks_client
+        .simulate_transaction(tx)
+        .await
+        .unwrap();
+    assert!(simulation.result.unwrap().is_ok());
+
+    let return_data = simulation
+        .simulation_details
+        .unwrap()
+        .return_data
+        .unwrap();
+    assert_eq!(return_data.program_id, spl_token_from_scratch::id());
+    assert_eq!(return_data.data.len(), 16);
+
+    let supply = u64::from_le_bytes(return_data.data[..8].try_into().unwrap());
+    let balance_sum = u64::from_le_bytes(return_data.data[8..16].try_into().unwrap());
+    assert_eq!(supply, 600);
+    assert_eq!(balance_sum, 600);
+}
+
+// =============================================================================
+// TRANSFER WITH FEE TESTS
+// =============================================================================
+
+/// Create a mint, a source holding `balance` tokens, a destination and a
+/// fee destination; returns (source, source_owner, destination, fee_destination).
+async fn setup_fee_accounts(
+    context: &mut ProgramTestContext,
+    balance: u64,
+) -> (Keypair, Keypair, Keypair, Keypair) {
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        6,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let source_account = Keypair::new();
+    let source_owner = Keypair::new();
+    let dest_account = Keypair::new();
+    let fee_account = Keypair::new();
+
+    for (token_account, owner) in [
+        (&source_account, source_owner.pubkey()),
+        (&dest_account, Keypair::new().pubkey()),
+        (&fee_account, Keypair::new().pubkey()),
+    ] {
+        let blockhash = get_recent_blockhash(context).await;
+
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            token_account,
+            &mint.pubkey(),
+            &owner,
+            blockhash,
+        )
+        .await
+        .unwrap();
+    }
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        balance,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    (source_account, source_owner, dest_account, fee_account)
+}
+
+/// Send a TransferWithFee signed by the source owner.
+async fn transfer_with_fee(
+    context: &mut ProgramTestContext,
+    source_account: &Keypair,
+    source_owner: &Keypair,
+    dest_account: &Keypair,
+    fee_account: &Keypair,
+    amount: u64,
+    fee_basis_points: u16,
+) {
+    let mint = get_token_account(&mut context.banks_client, &source_account.pubkey())
+        .await
+        .mint;
+
+    let fee_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new(fee_account.pubkey(), false),
+            AccountMeta::new_readonly(source_owner.pubkey(), true),
+            AccountMeta::new_readonly(mint, false),
+        ],
+        data: TokenInstruction::TransferWithFee {
+            amount,
+            fee_basis_points,
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[fee_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, source_owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_transfer_with_fee_100_bps() {
+    let mut context = program_test().start_with_context().await;
+
+    let (source_account, source_owner, dest_account, fee_account) =
+        setup_fee_accounts(&mut context, 10_000).await;
+
+    // 1% of 10_000 = 100
+    transfer_with_fee(
+        &mut context,
+        &source_account,
+        &source_owner,
+        &dest_account,
+        &fee_account,
+        10_000,
+        100,
+    )
+    .await;
+
+    let source_state =
+        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    let dest_state = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    let fee_state = get_token_account(&mut context.banks_client, &fee_account.pubkey()).await;
+
+    assert_eq!(source_state.amount, 0);
+    assert_eq!(dest_state.amount, 9_900);
+    assert_eq!(fee_state.amount, 100);
+}
+
+#[tokio::test]
+async fn test_transfer_with_fee_zero_bps() {
+    let mut context = program_test().start_with_context().await;
+
+    let (source_account, source_owner, dest_account, fee_account) =
+        setup_fee_accounts(&mut context, 10_000).await;
+
+    transfer_with_fee(
+        &mut context,
+        &source_account,
+        &source_owner,
+        &dest_account,
+        &fee_account,
+        10_000,
+        0,
+    )
+    .await;
+
+    let source_state =
+        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    let dest_state = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    let fee_state = get_token_account(&mut context.banks_client, &fee_account.pubkey()).await;
+
+    assert_eq!(source_state.amount, 0);
+    assert_eq!(dest_state.amount, 10_000);
+    assert_eq!(fee_state.amount, 0);
+}
+
+#[tokio::test]
+async fn test_transfer_with_fee_large_amount() {
+    let mut context = program_test().start_with_context().await;
+
+    let (source_account, source_owner, dest_account, fee_account) =
+        setup_fee_accounts(&mut context, u64::MAX).await;
+
+    // u64::MAX * 100 overflows a u64, but the fee itself doesn't
+    transfer_with_fee(
+        &mut context,
+        &source_account,
+        &source_owner,
+        &dest_account,
+        &fee_account,
+        u64::MAX,
+        100,
+    )
+    .await;
+
+    let dest_state = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    let fee_state = get_token_account(&mut context.banks_client, &fee_account.pubkey()).await;
+
+    assert_eq!(fee_state.amount, u64::MAX / 100);
+    assert_eq!(dest_state.amount, u64::MAX - u64::MAX / 100);
+}
+
+#[tokio::test]
+async fn test_transfer_with_fee_non_transferable_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_non_transferable_mint(&mut context, &mint, &mint_authority).await;
+
+    let source_account = Keypair::new();
+    let source_owner = Keypair::new();
+    let dest_account = Keypair::new();
+    let fee_account = Keypair::new();
+
+    for (token_account, owner) in [
+        (&source_account, source_owner.pubkey()),
+        (&dest_account, Keypair::new().pubkey()),
+        (&fee_account, Keypair::new().pubkey()),
+    ] {
+        let blockhash = get_recent_blockhash(&mut context).await;
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            token_account,
+            &mint.pubkey(),
+            &owner,
+            blockhash,
+        )
+        .await
+        .unwrap();
+    }
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source_account.pubkey(),
+        &mint_authority,
+        100,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let fee_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new(fee_account.pubkey(), false),
+            AccountMeta::new_readonly(source_owner.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+        ],
+        data: TokenInstruction::TransferWithFee {
+            amount: 100,
+            fee_basis_points: 100,
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[fee_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &source_owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::NonTransferable as u32)
+        )
+    );
+
+    let source = get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source.amount, 100);
+}
+
+#[tokio::test]
+async fn test_transfer_with_fee_hook_rejects() {
+    let hook_program = Pubkey::new_unique();
+    let mut program_test = program_test();
+    program_test.add_program(
+        "rejecting_transfer_hook",
+        hook_program,
+        processor!(rejecting_transfer_hook),
+    );
+    let mut context = program_test.start_with_context().await;
+
+    let (transfer_ix, owner, source, destination) =
+        setup_hooked_transfer(&mut context, hook_program).await;
+    let mint = transfer_ix.accounts[3].pubkey;
+
+    let fee_account = Keypair::new();
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &fee_account,
+        &mint,
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let fee_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new(fee_account.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(hook_program, false),
+        ],
+        data: TokenInstruction::TransferWithFee {
+            amount: 100,
+            fee_basis_points: 100,
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[fee_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::TransferHookFailed as u32)
+        )
+    );
+
+    // Nothing moved
+    let source_state = get_token_account(&mut context.banks_client, &source).await;
+    assert_eq!(source_state.amount, 1000);
+}
+
+// =============================================================================
+// NATIVE ACCOUNT TESTS
+// =============================================================================
+
+/// Inject the native mint (the program never creates it).
+fn add_native_mint(program_test: &mut ProgramTest, freeze_authority: Option<&Pubkey>) {
+    let native_mint_state = Mint {
+        decimals: native_mint::DECIMALS,
+        is_initialized: true,
+        freeze_authority: freeze_authority.copied().into(),
+        ..Mint::default()
+    };
+    let mut native_mint_data = vec![0u8; Mint::LEN];
+    native_mint_state.pack(&mut native_mint_data).unwrap();
+
+    program_test.add_account(
+        native_mint::id(),
+        solana_sdk::account::Account {
+            lamports: solana_sdk::rent::Rent::default().minimum_balance(Mint::LEN),
+            data: native_mint_data,
+            owner: spl_token_from_scratch::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+/// Wrap SOL: fund a token account with `lamports` above its rent
+/// reserve, then initialize it for the native mint.
+async fn create_wrapped_account(
+    context: &mut ProgramTestContext,
+    wrapped_account: &Keypair,
+    owner: &Pubkey,
+    lamports: u64,
+) {
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &wrapped_account.pubkey(),
+        rent.minimum_balance(TokenAccount::LEN) + lamports,
+        TokenAccount::LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(wrapped_account.pubkey(), false),
+            AccountMeta::new_readonly(native_mint::id(), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
+        ],
+        data: TokenInstruction::InitializeAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, wrapped_account],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_freeze_native_account_fails() {
+    let freeze_authority = Keypair::new();
+
+    // Give the native mint a freeze authority so only the native
+    // check can reject the freeze
+    let mut program_test = program_test();
+    add_native_mint(&mut program_test, Some(&freeze_authority.pubkey()));
+
+    let mut context = program_test.start_with_context().await;
+
+    // Wrap 1 SOL
+    let wrapped_account = Keypair::new();
+    let owner = Keypair::new();
+
+    create_wrapped_account(
+        &mut context,
+        &wrapped_account,
+        &owner.pubkey(),
+        1_000_000_000,
+    )
+    .await;
+
+    let wrapped_state =
+        get_token_account(&mut context.banks_client, &wrapped_account.pubkey()).await;
+    assert!(wrapped_state.is_native());
+    assert_eq!(wrapped_state.amount, 1_000_000_000);
+
+    // Freezing the wrapped SOL account fails
+    let freeze_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(wrapped_account.pubkey(), false),
+            AccountMeta::new_readonly(native_mint::id(), false),
+            AccountMeta::new_readonly(freeze_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::FreezeAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &freeze_authority],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::CannotFreezeNative as u32)
+        )
+    );
+
+    // A regular token account can still be frozen
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        Some(&freeze_authority.pubkey()),
+        9,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let freeze_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(freeze_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::FreezeAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &freeze_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert!(account_state.is_frozen());
+}
+
+#[tokio::test]
+async fn test_native_field_survives_operations() {
+    let mut program_test = program_test();
+    add_native_mint(&mut program_test, None);
+
+    let mut context = program_test.start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let rent_reserve = rent.minimum_balance(TokenAccount::LEN);
+
+    // Two wrapped SOL accounts
+    let source_account = Keypair::new();
+    let source_owner = Keypair::new();
+    let dest_account = Keypair::new();
+
+    create_wrapped_account(
+        &mut context,
+        &source_account,
+        &source_owner.pubkey(),
+        2_000_000_000,
+    )
+    .await;
+    create_wrapped_account(
+        &mut context,
+        &dest_account,
+        &Keypair::new().pubkey(),
+        500_000_000,
+    )
+    .await;
+
+    for account in [&source_account, &dest_account] {
+        let state = get_token_account(&mut context.banks_client, &account.pubkey()).await;
+        assert_eq!(state.is_native, COption::some(rent_reserve));
+    }
+
+    // Transfer into the destination
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(source_owner.pubkey(), true),
+            AccountMeta::new_readonly(native_mint::id(), false),
+        ],
+        data: TokenInstruction::Transfer {
+            amount: 1_000_000_000,
+        }
+        .pack(),
+    };
+
+    // Approve and revoke a delegate on the source
+    let approve_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(Keypair::new().pubkey(), false),
+            AccountMeta::new_readonly(source_owner.pubkey(), true),
+        ],
+        data: TokenInstruction::Approve { amount: 100 }.pack(),
+    };
+
+    let revoke_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(source_owner.pubkey(), true),
+        ],
+        data: TokenInstruction::Revoke.pack(),
+    };
+
+    // There's no SyncNative instruction in this program; check the
+    // marker after each processor that rewrites the account instead
+    for ix in [transfer_ix, approve_ix, revoke_ix] {
+        let blockhash = get_recent_blockhash(&mut context).await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &source_owner],
+            blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        for account in [&source_account, &dest_account] {
+            let state = get_token_account(&mut context.banks_client, &account.pubkey()).await;
+            assert_eq!(state.is_native, COption::some(rent_reserve));
+        }
+    }
+
+    let source_state =
+        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    let dest_state = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    assert_eq!(source_state.amount, 1_000_000_000);
+    assert_eq!(dest_state.amount, 1_500_000_000);
+}
+
+#[tokio::test]
+async fn test_close_native_account_with_balance() {
+    let mut program_test = program_test();
+    add_native_mint(&mut program_test, None);
+
+    let mut context = program_test.start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let rent_reserve = rent.minimum_balance(TokenAccount::LEN);
+
+    let wrapped_account = Keypair::new();
+    let owner = Keypair::new();
+    create_wrapped_account(&mut context, &wrapped_account, &owner.pubkey(), 1_000_000_000).await;
+
+    // No reserve destination: wrapped SOL and reserve both go to one place
+    let destination = Pubkey::new_unique();
+    let close_ix = close_account_ix(&wrapped_account.pubkey(), &destination, &owner.pubkey());
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let destination_lamports = context.banks_client.get_balance(destination).await.unwrap();
+    assert_eq!(destination_lamports, rent_reserve + 1_000_000_000);
+
+    let closed = context
+        .banks_client
+        .get_account(wrapped_account.pubkey())
+        .await
+        .unwrap();
+    assert!(closed.is_none());
+}
+
+#[tokio::test]
+async fn test_close_native_account_split_reserve() {
+    let mut program_test = program_test();
+    add_native_mint(&mut program_test, None);
+
+    let mut context = program_test.start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let rent_reserve = rent.minimum_balance(TokenAccount::LEN);
+
+    let wrapped_account = Keypair::new();
+    let owner = Keypair::new();
+    create_wrapped_account(&mut context, &wrapped_account, &owner.pubkey(), 1_000_000_000).await;
+
+    // Reserve destination in its own slot after the authority
+    let destination = Pubkey::new_unique();
+    let reserve_destination = Pubkey::new_unique();
+    let close_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(wrapped_account.pubkey(), false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new(reserve_destination, false),
+        ],
+        data: TokenInstruction::CloseAccountWithReserve.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Wrapped SOL to the destination, rent reserve split off
+    let destination_lamports = context.banks_client.get_balance(destination).await.unwrap();
+    assert_eq!(destination_lamports, 1_000_000_000);
+
+    let reserve_lamports = context
+        .banks_client
+        .get_balance(reserve_destination)
+        .await
+        .unwrap();
+    assert_eq!(reserve_lamports, rent_reserve);
+
+    let closed = context
+        .banks_client
+        .get_account(wrapped_account.pubkey())
+        .await
+        .unwrap();
+    assert!(closed.is_none());
+}
+
+#[tokio::test]
+async fn test_close_native_account_with_multisig_owner() {
+    let mut program_test = program_test();
+    add_native_mint(&mut program_test, None);
+
+    let mut context = program_test.start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let rent_reserve = rent.minimum_balance(TokenAccount::LEN);
+
+    // 2-of-3 multisig owning a wrapped SOL account
+    let signer1 = Keypair::new();
+    let signer2 = Keypair::new();
+    let signer3 = Keypair::new();
+    let multisig = Keypair::new();
+
+    let (p1, p2, p3) = (signer1.pubkey(), signer2.pubkey(), signer3.pubkey());
+    create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &multisig,
+        &[&p1, &p2, &p3],
+        2,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let wrapped_account = Keypair::new();
+    create_wrapped_account(
+        &mut context,
+        &wrapped_account,
+        &multisig.pubkey(),
+        1_000_000_000,
+    )
+    .await;
+
+    // The non-signing member comes first among the signers; it must not
+    // be mistaken for a reserve destination
+    let destination = Pubkey::new_unique();
+    let close_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(wrapped_account.pubkey(), false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(multisig.pubkey(), false),
+            AccountMeta::new(signer3.pubkey(), false),
+            AccountMeta::new_readonly(signer1.pubkey(), true),
+            AccountMeta::new_readonly(signer2.pubkey(), true),
+        ],
+        data: TokenInstruction::CloseAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &signer1, &signer2],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Everything went to the destination, nothing to the member
+    let destination_lamports = context.banks_client.get_balance(destination).await.unwrap();
+    assert_eq!(destination_lamports, rent_reserve + 1_000_000_000);
+
+    let member_lamports = context.banks_client.get_balance(signer3.pubkey()).await.unwrap();
+    assert_eq!(member_lamports, 0);
+
+    let closed = context
+        .banks_client
+        .get_account(wrapped_account.pubkey())
+        .await
+        .unwrap();
+    assert!(closed.is_none());
+}
+
+#[tokio::test]
+async fn test_close_non_native_account_with_reserve_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // An empty, regular token account
+    let mint = Keypair::new();
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &Keypair::new().pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let close_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new(Pubkey::new_unique(), false),
+        ],
+        data: TokenInstruction::CloseAccountWithReserve.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::InvalidState as u32)
+        )
+    );
+
+    // Still open
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert!(account_state.is_initialized());
+}
+
+#[tokio::test]
+async fn test_transfer_native_moves_lamports() {
+    let mut program_test = program_test();
+    add_native_mint(&mut program_test, None);
+
+    let mut context = program_test.start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let rent_reserve = rent.minimum_balance(TokenAccount::LEN);
+
+    let source_account = Keypair::new();
+    let source_owner = Keypair::new();
+    let dest_account = Keypair::new();
+
+    create_wrapped_account(
+        &mut context,
+        &source_account,
+        &source_owner.pubkey(),
+        2_000_000_000,
+    )
+    .await;
+    create_wrapped_account(
+        &mut context,
+        &dest_account,
+        &Keypair::new().pubkey(),
+        500_000_000,
+    )
+    .await;
+
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(source_owner.pubkey(), true),
+            AccountMeta::new_readonly(native_mint::id(), false),
+        ],
+        data: TokenInstruction::Transfer {
+            amount: 750_000_000,
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &source_owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Tokens and lamports moved together; each stored amount still
+    // matches the lamports above the reserve
+    for (account, expected) in [(&source_account, 1_250_000_000), (&dest_account, 1_250_000_000)] {
+        let state = get_token_account(&mut context.banks_client, &account.pubkey()).await;
+        let lamports = context
+            .banks_client
+            .get_balance(account.pubkey())
+            .await
+            .unwrap();
+
+        assert_eq!(state.amount, expected);
+        assert_eq!(lamports, rent_reserve + expected);
+        assert_eq!(state.is_native, COption::some(rent_reserve));
+        assert!(!state.needs_sync(lamports, rent_reserve));
+    }
+}
+
+#[tokio::test]
+async fn test_transfer_batch_native_moves_lamports() {
+    let mut program_test = program_test();
+    add_native_mint(&mut program_test, None);
+
+    let mut context = program_test.start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let rent_reserve = rent.minimum_balance(TokenAccount::LEN);
+
+    let source_account = Keypair::new();
+    let source_owner = Keypair::new();
+    let dest_accounts = [Keypair::new(), Keypair::new()];
+
+    create_wrapped_account(
+        &mut context,
+        &source_account,
+        &source_owner.pubkey(),
+        2_000_000_000,
+    )
+    .await;
+    for dest_account in &dest_accounts {
+        create_wrapped_account(&mut context, dest_account, &Keypair::new().pubkey(), 0).await;
+    }
+
+    let batch_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(source_owner.pubkey(), true),
+            AccountMeta::new_readonly(native_mint::id(), false),
+            AccountMeta::new(dest_accounts[0].pubkey(), false),
+            AccountMeta::new(dest_accounts[1].pubkey(), false),
+        ],
+        data: TokenInstruction::TransferBatch {
+            amounts: vec![300_000_000, 700_000_000],
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[batch_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &source_owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Each leg moved its lamports with its tokens
+    for (account, expected) in [
+        (&source_account, 1_000_000_000),
+        (&dest_accounts[0], 300_000_000),
+        (&dest_accounts[1], 700_000_000),
+    ] {
+        let state = get_token_account(&mut context.banks_client, &account.pubkey()).await;
+        let lamports = context
+            .banks_client
+            .get_balance(account.pubkey())
+            .await
+            .unwrap();
+
+        assert_eq!(state.amount, expected);
+        assert_eq!(lamports, rent_reserve + expected);
+        assert!(!state.needs_sync(lamports, rent_reserve));
+    }
+}
+
+#[tokio::test]
+async fn test_transfer_with_fee_native_moves_lamports() {
+    let mut program_test = program_test();
+    add_native_mint(&mut program_test, None);
+
+    let mut context = program_test.start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let rent_reserve = rent.minimum_balance(TokenAccount::LEN);
+
+    let source_account = Keypair::new();
+    let source_owner = Keypair::new();
+    let dest_account = Keypair::new();
+    let fee_account = Keypair::new();
+
+    create_wrapped_account(
+        &mut context,
+        &source_account,
+        &source_owner.pubkey(),
+        2_000_000_000,
+    )
+    .await;
+    for receiver in [&dest_account, &fee_account] {
+        create_wrapped_account(&mut context, receiver, &Keypair::new().pubkey(), 0).await;
+    }
+
+    // 1% of 1_000_000_000 = 10_000_000
+    transfer_with_fee(
+        &mut context,
+        &source_account,
+        &source_owner,
+        &dest_account,
+        &fee_account,
+        1_000_000_000,
+        100,
+    )
+    .await;
+
+    // Both parts of the split moved their lamports with their tokens
+    for (account, expected) in [
+        (&source_account, 1_000_000_000),
+        (&dest_account, 990_000_000),
+        (&fee_account, 10_000_000),
+    ] {
+        let state = get_token_account(&mut context.banks_client, &account.pubkey()).await;
+        let lamports = context
+            .banks_client
+            .get_balance(account.pubkey())
+            .await
+            .unwrap();
+
+        assert_eq!(state.amount, expected);
+        assert_eq!(lamports, rent_reserve + expected);
+        assert!(!state.needs_sync(lamports, rent_reserve));
+    }
+}
+
+// =============================================================================
+// SUPPLY CAP TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_supply_cap_mint_up_to_cap_then_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Extended mint with a 1_000 cap
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(Mint::EXTENDED_LEN),
+        Mint::EXTENDED_LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![AccountMeta::new(mint.pubkey(), false)],
+        data: TokenInstruction::InitializeMint {
+            decimals: 0,
+            mint_authority: mint_authority.pubkey(),
+            freeze_authority: None,
+        }
+        .pack(),
+    };
+
+    let cap_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(mint_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::SetSupplyCap { cap: Some(1_000) }.pack(),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix, cap_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint, &mint_authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply_cap, COption::some(1_000));
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Minting exactly up to the cap succeeds
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        1_000,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, 1_000);
+
+    // One more token is over the cap
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let result = mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        1,
+        blockhash,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::SupplyCapExceeded as u32)
+        )
+    );
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, 1_000);
+}
+
+// =============================================================================
+// APPROVE AND SET CLOSE AUTHORITY TESTS
+// =============================================================================
+
+/// Create a mint and a token account holding 1000 tokens;
+/// returns (token_account, owner).
+async fn setup_funded_account(context: &mut ProgramTestContext) -> (Keypair, Keypair) {
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        1000,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    (token_account, owner)
+}
+
+/// Build an ApproveAndSetCloseAuthority instruction.
+fn approve_and_set_close_authority_ix(
+    token_account: &Pubkey,
+    delegate: &Pubkey,
+    authority: &Pubkey,
+    authority_signs: bool,
+    amount: u64,
+    close_authority: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new_readonly(*delegate, false),
+            AccountMeta::new_readonly(*authority, authority_signs),
+        ],
+        data: TokenInstruction::ApproveAndSetCloseAuthority {
+            amount,
+            close_authority: *close_authority,
+        }
+        .pack(),
+    }
+}
+
+#[tokio::test]
+async fn test_approve_and_set_close_authority() {
+    let mut context = program_test().start_with_context().await;
+
+    let (token_account, owner) = setup_funded_account(&mut context).await;
+    let delegate = Keypair::new();
+    let close_authority = Keypair::new();
+
+    let ix = approve_and_set_close_authority_ix(
+        &token_account.pubkey(),
+        &delegate.pubkey(),
+        &owner.pubkey(),
+        true,
+        400,
+        &close_authority.pubkey(),
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Both fields set
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.delegate, COption::some(delegate.pubkey()));
+    assert_eq!(account_state.delegated_amount, 400);
+    assert_eq!(
+        account_state.close_authority,
+        COption::some(close_authority.pubkey())
+    );
+}
+
+#[tokio::test]
+async fn test_approve_and_set_close_authority_requires_owner_signature() {
+    let mut context = program_test().start_with_context().await;
+
+    let (token_account, owner) = setup_funded_account(&mut context).await;
+    let delegate = Keypair::new();
+
+    // Owner listed but not signing
+    let ix = approve_and_set_close_authority_ix(
+        &token_account.pubkey(),
+        &delegate.pubkey(),
+        &owner.pubkey(),
+        false,
+        400,
+        &Keypair::new().pubkey(),
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature)
+    );
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert!(account_state.delegate.is_none());
+    assert!(account_state.close_authority.is_none());
+}
+
+#[tokio::test]
+async fn test_approve_and_set_close_authority_delegate_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let (token_account, owner) = setup_funded_account(&mut context).await;
+    let delegate = Keypair::new();
+
+    // Owner approves a delegate first
+    let ix = approve_and_set_close_authority_ix(
+        &token_account.pubkey(),
+        &delegate.pubkey(),
+        &owner.pubkey(),
+        true,
+        400,
+        &owner.pubkey(),
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // The delegate tries to raise its allowance and take the close authority
+    let ix = approve_and_set_close_authority_ix(
+        &token_account.pubkey(),
+        &delegate.pubkey(),
+        &delegate.pubkey(),
+        true,
+        1000,
+        &delegate.pubkey(),
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &delegate],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::InvalidAuthority as u32)
+        )
+    );
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.delegated_amount, 400);
+    assert_eq!(account_state.close_authority, COption::some(owner.pubkey()));
+}
+
+#[tokio::test]
+async fn test_approve_and_set_close_authority_keeps_foreign_close_authority() {
+    let mut context = program_test().start_with_context().await;
+
+    let (token_account, owner) = setup_funded_account(&mut context).await;
+    let custodian = Keypair::new();
+
+    // Owner hands the close authority to a custodian
+    let ix = approve_and_set_close_authority_ix(
+        &token_account.pubkey(),
+        &Keypair::new().pubkey(),
+        &owner.pubkey(),
+        true,
+        400,
+        &custodian.pubkey(),
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // The owner alone can't take it back by calling again
+    let ix = approve_and_set_close_authority_ix(
+        &token_account.pubkey(),
+        &Keypair::new().pubkey(),
+        &owner.pubkey(),
+        true,
+        100,
+        &owner.pubkey(),
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::CloseAuthorityMismatch as u32)
+        )
+    );
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.delegated_amount, 400);
+    assert_eq!(
+        account_state.close_authority,
+        COption::some(custodian.pubkey())
+    );
+}
+
+// =============================================================================
+// VERIFY ACCOUNT TESTS
+// =============================================================================
+
+/// Create a mint with a freeze authority and a token account for it;
+/// returns (mint, token_account, owner, freeze_authority).
+async fn setup_verify_account(
+    context: &mut ProgramTestContext,
+) -> (Pubkey, Pubkey, Pubkey, Keypair) {
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let freeze_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        Some(&freeze_authority.pubkey()),
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Pubkey::new_unique();
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    (
+        mint.pubkey(),
+        token_account.pubkey(),
+        owner,
+        freeze_authority,
+    )
+}
+
+/// Simulate VerifyAccount and return the status byte.
+async fn verify_account_status(
+    context: &mut ProgramTestContext,
+    token_account: &Pubkey,
+    expected_mint: &Pubkey,
+    expected_owner: &Pubkey,
+) -> u8 {
+    let verify_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![AccountMeta::new_readonly(*token_account, false)],
+        data: TokenInstruction::VerifyAccount {
+            expected_mint: *expected_mint,
+            expected_owner: *expected_owner,
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[verify_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let simulation = context
+        .banks_client
+        .simulate_transaction(tx)
+        .await
+        .unwrap();
+    assert!(simulation.result.unwrap().is_ok());
+
+    let return_data = simulation
+        .simulation_details
+        .unwrap()
+        .return_data
+        .unwrap();
+    assert_eq!(return_data.program_id, spl_token_from_scratch::id());
+    assert_eq!(return_data.data.len(), 1);
+
+    return_data.data[0]
+}
+
+#[tokio::test]
+async fn test_verify_account_matches() {
+    let mut context = program_test().start_with_context().await;
+
+    let (mint, token_account, owner, _) = setup_verify_account(&mut context).await;
+
+    let status = verify_account_status(&mut context, &token_account, &mint, &owner).await;
+    assert_eq!(status, 0);
+}
+
+#[tokio::test]
+async fn test_verify_account_mint_mismatch() {
+    let mut context = program_test().start_with_context().await;
+
+    let (_, token_account, owner, _) = setup_verify_account(&mut context).await;
+
+    let wrong_mint = Pubkey::new_unique();
+    let status = verify_account_status(&mut context, &token_account, &wrong_mint, &owner).await;
+    assert_eq!(status, 1);
+
+    // Mint is checked first, so a wrong owner doesn't change the status
+    let wrong_owner = Pubkey::new_unique();
+    let status =
+        verify_account_status(&mut context, &token_account, &wrong_mint, &wrong_owner).await;
+    assert_eq!(status, 1);
+}
+
+#[tokio::test]
+async fn test_verify_account_owner_mismatch() {
+    let mut context = program_test().start_with_context().await;
+
+    let (mint, token_account, _, _) = setup_verify_account(&mut context).await;
+
+    let wrong_owner = Pubkey::new_unique();
+    let status = verify_account_status(&mut context, &token_account, &mint, &wrong_owner).await;
+    assert_eq!(status, 2);
+}
+
+#[tokio::test]
+async fn test_verify_account_frozen() {
+    let mut context = program_test().start_with_context().await;
+
+    let (mint, token_account, owner, freeze_authority) =
+        setup_verify_account(&mut context).await;
+
+    let freeze_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(freeze_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::FreezeAccount.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &freeze_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Matching mint and owner still report the account as unusable
+    let status = verify_account_status(&mut context, &token_account, &mint, &owner).await;
+    assert_eq!(status, 3);
+}
+
+#[tokio::test]
+async fn test_verify_account_uninitialized() {
+    let mut context = program_test().start_with_context().await;
+
+    // Program-owned account that was never initialized
+    let token_account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &token_account.pubkey(),
+        rent.minimum_balance(TokenAccount::LEN),
+        TokenAccount::LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &token_account],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let status = verify_account_status(
+        &mut context,
+        &token_account.pubkey(),
+        &Pubkey::default(),
+        &Pubkey::default(),
+    )
+    .await;
+    assert_eq!(status, 3);
+}
+
+// =============================================================================
+// CREATE ACCOUNT TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_create_account_single_instruction() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Create and initialize in one instruction, no separate system call
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let create_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new(token_account.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: TokenInstruction::CreateAccount {
+            owner: owner.pubkey(),
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &token_account],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Program-owned, rent exempt at Account::LEN
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let raw = context
+        .banks_client
+        .get_account(token_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(raw.owner, spl_token_from_scratch::id());
+    assert_eq!(raw.data.len(), TokenAccount::LEN);
+    assert_eq!(raw.lamports, rent.minimum_balance(TokenAccount::LEN));
+
+    // Initialized for the mint and owner
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.mint, mint.pubkey());
+    assert_eq!(account_state.owner, owner.pubkey());
+    assert_eq!(account_state.amount, 0);
+    assert_eq!(account_state.state, AccountState::Initialized);
+    assert!(account_state.is_native.is_none());
+}
+
+// =============================================================================
+// MINT PAUSE TESTS
+// =============================================================================
+
+/// Build a SetMintPaused instruction signed by the mint authority.
+fn set_mint_paused_ix(mint: &Pubkey, mint_authority: &Pubkey, paused: bool) -> Instruction {
+    Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+        ],
+        data: TokenInstruction::SetMintPaused { paused }.pack(),
+    }
+}
+
+#[tokio::test]
+async fn test_mint_paused_blocks_mint_to_until_resumed() {
+    let mut context = program_test().start_with_context().await;
+
+    // Extended mint (room for the pause flag)
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(Mint::EXTENDED_LEN),
+        Mint::EXTENDED_LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![AccountMeta::new(mint.pubkey(), false)],
+        data: TokenInstruction::InitializeMint {
+            decimals: 0,
+            mint_authority: mint_authority.pubkey(),
+            freeze_authority: None,
+        }
+        .pack(),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        100,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Pause
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[set_mint_paused_ix(&mint.pubkey(), &mint_authority.pubkey(), true)],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert!(mint_state.mint_paused);
+
+    // Minting fails while paused
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let result = mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        50,
+        blockhash,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::MintPaused as u32)
+        )
+    );
+
+    // Burning still works while paused
+    let burn_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::Burn { amount: 10 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[burn_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Resume, then minting succeeds again
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[set_mint_paused_ix(&mint.pubkey(), &mint_authority.pubkey(), false)],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        50,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert!(!mint_state.mint_paused);
+    assert_eq!(mint_state.supply, 140);
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.amount, 140);
+}
+
+#[tokio::test]
+async fn test_set_mint_paused_wrong_authority_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(Mint::EXTENDED_LEN),
+        Mint::EXTENDED_LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![AccountMeta::new(mint.pubkey(), false)],
+        data: TokenInstruction::InitializeMint {
+            decimals: 0,
+            mint_authority: mint_authority.pubkey(),
+            freeze_authority: None,
+        }
+        .pack(),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Someone other than the mint authority tries to pause
+    let attacker = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[set_mint_paused_ix(&mint.pubkey(), &attacker.pubkey(), true)],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &attacker],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::InvalidAuthority as u32)
+        )
+    );
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert!(!mint_state.mint_paused);
+}
+
+// =============================================================================
+// FREEZE ACCOUNTS TESTS
+// =============================================================================
+
+/// Create a mint with a freeze authority and `count` token accounts for
+/// it; returns (mint, freeze_authority, token_accounts).
+async fn setup_freezable_accounts(
+    context: &mut ProgramTestContext,
+    count: usize,
+) -> (Keypair, Keypair, Vec<Keypair>) {
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let freeze_authority = Keypair::new();
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        Some(&freeze_authority.pubkey()),
+        9,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let mut token_accounts = Vec::with_capacity(count);
+    for _ in 0..count {
+        let token_account = Keypair::new();
+        let blockhash = get_recent_blockhash(context).await;
+
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            &token_account,
+            &mint.pubkey(),
+            &Keypair::new().pubkey(),
+            blockhash,
+        )
+        .await
+        .unwrap();
+
+        token_accounts.push(token_account);
+    }
+
+    (mint, freeze_authority, token_accounts)
+}
+
+/// Build a FreezeAccounts instruction for `token_accounts`.
+fn freeze_accounts_ix(
+    mint: &Pubkey,
+    freeze_authority: &Pubkey,
+    token_accounts: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*freeze_authority, true),
+    ];
+    accounts.extend(
+        token_accounts
+            .iter()
+            .map(|account| AccountMeta::new(*account, false)),
+    );
+
+    Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts,
+        data: TokenInstruction::FreezeAccounts {
+            count: token_accounts.len() as u8,
+        }
+        .pack(),
+    }
+}
+
+#[tokio::test]
+async fn test_freeze_accounts_three_at_once() {
+    let mut context = program_test().start_with_context().await;
+
+    let (mint, freeze_authority, token_accounts) = setup_freezable_accounts(&mut context, 3).await;
+    let keys: Vec<Pubkey> = token_accounts.iter().map(|a| a.pubkey()).collect();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_accounts_ix(
+            &mint.pubkey(),
+            &freeze_authority.pubkey(),
+            &keys,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &freeze_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    for key in &keys {
+        let account_state = get_token_account(&mut context.banks_client, key).await;
+        assert_eq!(account_state.state, AccountState::Frozen);
+    }
+}
+
+#[tokio::test]
+async fn test_freeze_accounts_wrong_mint_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let (mint, freeze_authority, token_accounts) = setup_freezable_accounts(&mut context, 2).await;
+
+    // Third account belongs to a different mint
+    let (_, _, other_accounts) = setup_freezable_accounts(&mut context, 1).await;
+
+    let mut keys: Vec<Pubkey> = token_accounts.iter().map(|a| a.pubkey()).collect();
+    keys.push(other_accounts[0].pubkey());
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_accounts_ix(
+            &mint.pubkey(),
+            &freeze_authority.pubkey(),
+            &keys,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &freeze_authority],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::MintMismatch as u32)
+        )
+    );
+
+    // Nothing was frozen
+    for key in &keys {
+        let account_state = get_token_account(&mut context.banks_client, key).await;
+        assert_eq!(account_state.state, AccountState::Initialized);
+    }
+}
+
+// =============================================================================
+// GET MINT INFO TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_get_mint_info_initialized_mint() {
+    let mut context = program_test().start_with_context().await;
+
+    // Mint with a mint authority but no freeze authority
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        6,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        1_500_000,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let info_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![AccountMeta::new_readonly(mint.pubkey(), false)],
+        data: TokenInstruction::GetMintInfo.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[info_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let simulation = context
+        .banks_client
+        .simulate_transaction(tx)
+        .await
+        .unwrap();
+    assert!(simulation.result.unwrap().is_ok());
+
+    let return_data = simulation
+        .simulation_details
+        .unwrap()
+        .return_data
+        .unwrap();
+    assert_eq!(return_data.program_id, spl_token_from_scratch::id());
+    assert_eq!(return_data.data.len(), 12);
+
+    // [supply u64][decimals][is_initialized][has_mint_authority][has_freeze_authority]
+    let data = &return_data.data;
+    assert_eq!(u64::from_le_bytes(data[..8].try_into().unwrap()), 1_500_000);
+    assert_eq!(data[8], 6);
+    assert_eq!(data[9], 1);
+    assert_eq!(data[10], 1);
+    assert_eq!(data[11], 0);
+}
+
+// =============================================================================
+// MINT CLOSE AUTHORITY TESTS
+// =============================================================================
+
+/// Create an extended mint (room for a close authority) with decimals 0
+/// and no freeze authority; returns (mint, mint_authority).
+async fn setup_extended_mint(context: &mut ProgramTestContext) -> (Keypair, Keypair) {
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(Mint::EXTENDED_LEN),
+        Mint::EXTENDED_LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![AccountMeta::new(mint.pubkey(), false)],
+        data: TokenInstruction::InitializeMint {
+            decimals: 0,
+            mint_authority: mint_authority.pubkey(),
+            freeze_authority: None,
+        }
+        .pack(),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    (mint, mint_authority)
+}
+
+/// Build a SetAuthority(CloseAccount) instruction for a mint.
+fn set_mint_close_authority_ix(
+    mint: &Pubkey,
+    current_authority: &Pubkey,
+    new_authority: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(*current_authority, true),
+        ],
+        data: TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::CloseAccount,
+            new_authority,
+        }
+        .pack(),
+    }
+}
+
+/// Build a CloseAccount instruction for a mint.
+fn close_mint_ix(mint: &Pubkey, destination: &Pubkey, close_authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*close_authority, true),
+        ],
+        data: TokenInstruction::CloseAccount.pack(),
+    }
+}
+
+#[tokio::test]
+async fn test_close_mint_with_close_authority() {
+    let mut context = program_test().start_with_context().await;
+
+    let (mint, mint_authority) = setup_extended_mint(&mut context).await;
+    let close_authority = Keypair::new();
+    let destination = Pubkey::new_unique();
+
+    // The mint authority hands closing to a dedicated key
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[set_mint_close_authority_ix(
+            &mint.pubkey(),
+            &mint_authority.pubkey(),
+            Some(close_authority.pubkey()),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(
+        mint_state.close_authority,
+        COption::some(close_authority.pubkey())
+    );
+
+    let mint_lamports = context
+        .banks_client
+        .get_account(mint.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+
+    // Zero supply, so the close authority can close it
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[close_mint_ix(
+            &mint.pubkey(),
+            &destination,
+            &close_authority.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &close_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(mint.pubkey())
+        .await
+        .unwrap();
+    assert!(account.is_none() || account.unwrap().lamports == 0);
+
+    let destination_account = context
+        .banks_client
+        .get_account(destination)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(destination_account.lamports, mint_lamports);
+}
+
+#[tokio::test]
+async fn test_close_mint_with_supply_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let (mint, mint_authority) = setup_extended_mint(&mut context).await;
+
+    // The mint authority keeps closing rights for itself
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[set_mint_close_authority_ix(
+            &mint.pubkey(),
+            &mint_authority.pubkey(),
+            Some(mint_authority.pubkey()),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        1,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Supply is 1, so closing is refused
+    let blockhash = get_recent_blockhash(&mut context).await;
+    let tx = Transaction::new_signed_with_payer(
+        &[close_mint_ix(
+            &mint.pubkey(),
+            &context.payer.pubkey(),
+            &mint_authority.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::NonZeroBalance as u32)
+        )
+    );
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, 1);
+}
+
+// =============================================================================
+// GET ACCOUNT BALANCE TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_get_account_balance() {
+    let mut context = program_test().start_with_context().await;
+
+    // Token account holding 1000 tokens
+    let (token_account, _) = setup_funded_account(&mut context).await;
+
+    let balance_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![AccountMeta::new_readonly(token_account.pubkey(), false)],
+        data: TokenInstruction::GetAccountBalance.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[balance_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let simulation = context
+        .banks_client
+        .simulate_transaction(tx)
+        .await
+        .unwrap();
+    assert!(simulation.result.unwrap().is_ok());
+
+    let return_data = simulation
+        .simulation_details
+        .unwrap()
+        .return_data
+        .unwrap();
+    assert_eq!(return_data.program_id, spl_token_from_scratch::id());
+
+    let balance = u64::from_le_bytes(return_data.data[..].try_into().unwrap());
+    assert_eq!(balance, 1000);
+}
+
+// =============================================================================
+// GET MULTISIG INFO TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_get_multisig_info() {
+    let mut context = program_test().start_with_context().await;
+
+    // 2-of-3 multisig
+    let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let signer_pubkeys: Vec<Pubkey> = signers.iter().map(|signer| signer.pubkey()).collect();
+    let multisig = Keypair::new();
+
+    create_multisig(
+        &mut context.banks_client,
+        &context.payer,
+        &multisig,
+        &signer_pubkeys.iter().collect::<Vec<_>>(),
+        2,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let info_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![AccountMeta::new_readonly(multisig.pubkey(), false)],
+        data: TokenInstruction::GetMultisigInfo.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[info_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let simulation = context
+        .banks_client
+        .simulate_transaction(tx)
+        .await
+        .unwrap();
+    assert!(simulation.result.unwrap().is_ok());
+
+    let return_data = simulation
+        .simulation_details
+        .unwrap()
+        .return_data
+        .unwrap();
+    assert_eq!(return_data.program_id, spl_token_from_scratch::id());
+
+    // [m][n][n signer pubkeys]
+    let data = &return_data.data;
+    assert_eq!(data.len(), 2 + 32 * 3);
+    assert_eq!(data[0], 2);
+    assert_eq!(data[1], 3);
+
+    let decoded: Vec<Pubkey> = data[2..]
+        .chunks_exact(32)
+        .map(|key| Pubkey::try_from(key).unwrap())
+        .collect();
+    assert_eq!(decoded, signer_pubkeys);
+}
+
+// =============================================================================
+// TRANSFER HOOK TESTS
+// =============================================================================
+
+/// Transfer hook that logs the amount and accepts the transfer.
+fn logging_transfer_hook(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let amount = unpack_transfer_hook_amount(data).ok_or(ProgramError::InvalidInstructionData)?;
+    msg!("Transfer hook: {} tokens", amount);
+    Ok(())
+}
+
+/// Transfer hook that rejects every transfer.
+fn rejecting_transfer_hook(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _data: &[u8],
+) -> ProgramResult {
+    Err(ProgramError::Custom(0))
+}
+
+/// Create an extended mint hooked to `hook_program` and two token accounts
+/// for it, the source holding 1000 tokens. Returns a Transfer of 100 that
+/// passes the mint and hook program, plus (owner, source, destination).
+async fn setup_hooked_transfer(
+    context: &mut ProgramTestContext,
+    hook_program: Pubkey,
+) -> (Instruction, Keypair, Pubkey, Pubkey) {
+    let (mint, mint_authority) = setup_extended_mint(context).await;
+
+    let source = Keypair::new();
+    let destination = Keypair::new();
+    let owner = Keypair::new();
+
+    for account in [&source, &destination] {
+        let blockhash = get_recent_blockhash(context).await;
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            account,
+            &mint.pubkey(),
+            &owner.pubkey(),
+            blockhash,
+        )
+        .await
+        .unwrap();
+    }
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &source.pubkey(),
+        &mint_authority,
+        1000,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let set_hook_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(mint_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::SetTransferHook {
+            program: Some(hook_program),
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[set_hook_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.transfer_hook, COption::some(hook_program));
+
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source.pubkey(), false),
+            AccountMeta::new(destination.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(hook_program, false),
+        ],
+        data: TokenInstruction::Transfer { amount: 100 }.pack(),
+    };
+
+    (transfer_ix, owner, source.pubkey(), destination.pubkey())
+}
+
+#[tokio::test]
+async fn test_transfer_hook_accepts() {
+    let hook_program = Pubkey::new_unique();
+    let mut program_test = program_test();
+    program_test.add_program(
+        "logging_transfer_hook",
+        hook_program,
+        processor!(logging_transfer_hook),
+    );
+    let mut context = program_test.start_with_context().await;
+
+    let (transfer_ix, owner, source, destination) =
+        setup_hooked_transfer(&mut context, hook_program).await;
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    assert!(result.result.is_ok());
+
+    // The hook ran and saw the amount
+    let logs = result.metadata.unwrap().log_messages;
+    assert!(logs
+        .iter()
+        .any(|line| line == "Program log: Transfer hook: 100 tokens"));
+
+    let source_state = get_token_account(&mut context.banks_client, &source).await;
+    let dest_state = get_token_account(&mut context.banks_client, &destination).await;
+    assert_eq!(source_state.amount, 900);
+    assert_eq!(dest_state.amount, 100);
+}
+
+#[tokio::test]
+async fn test_transfer_hook_rejects() {
+    let hook_program = Pubkey::new_unique();
+    let mut program_test = program_test();
+    program_test.add_program(
+        "rejecting_transfer_hook",
+        hook_program,
+        processor!(rejecting_transfer_hook),
+    );
+    let mut context = program_test.start_with_context().await;
+
+    let (transfer_ix, owner, source, destination) =
+        setup_hooked_transfer(&mut context, hook_program).await;
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::TransferHookFailed as u32)
+        )
+    );
+
+    // Nothing moved
+    let source_state = get_token_account(&mut context.banks_client, &source).await;
+    let dest_state = get_token_account(&mut context.banks_client, &destination).await;
+    assert_eq!(source_state.amount, 1000);
+    assert_eq!(dest_state.amount, 0);
+}
+
+#[tokio::test]
+async fn test_transfer_hook_cannot_be_skipped_by_leaving_out_mint() {
+    let hook_program = Pubkey::new_unique();
+    let mut program_test = program_test();
+    program_test.add_program(
+        "rejecting_transfer_hook",
+        hook_program,
+        processor!(rejecting_transfer_hook),
+    );
+    let mut context = program_test.start_with_context().await;
+
+    let (transfer_ix, owner, source, destination) =
+        setup_hooked_transfer(&mut context, hook_program).await;
+
+    // Without the mint, the hook program lands in the mint's slot
+    let mut without_mint = transfer_ix.clone();
+    without_mint.accounts.remove(3);
+
+    // Without the mint or the hook program, an account is missing
+    let mut without_either = without_mint.clone();
+    without_either.accounts.pop();
+
+    for (ix, expected) in [
+        (
+            without_mint,
+            InstructionError::Custom(TokenError::MintMismatch as u32),
+        ),
+        (without_either, InstructionError::NotEnoughAccountKeys),
+    ] {
+        let blockhash = get_recent_blockhash(&mut context).await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &owner],
+            blockhash,
+        );
+
+        let result = context.banks_client.process_transaction(tx).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, expected)
+        );
+    }
+
+    // Nothing moved without the hook's say
+    let source_state = get_token_account(&mut context.banks_client, &source).await;
+    let dest_state = get_token_account(&mut context.banks_client, &destination).await;
+    assert_eq!(source_state.amount, 1000);
+    assert_eq!(dest_state.amount, 0);
+}
+
+// =============================================================================
+// EVENT LOG TESTS (events feature)
+// =============================================================================
+
+/// Find the first event record in a transaction's logs.
+#[cfg(feature = "events")]
+fn find_event(logs: &[String]) -> Option<(u8, Vec<u8>)> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program log: "))
+        .find_map(events::decode_event)
+}
+
+#[cfg(feature = "events")]
+#[tokio::test]
+async fn test_mint_to_and_transfer_emit_events() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let source = Keypair::new();
+    let destination = Keypair::new();
+    let owner = Keypair::new();
+
+    for account in [&source, &destination] {
+        let blockhash = get_recent_blockhash(&mut context).await;
+
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            account,
+            &mint.pubkey(),
+            &owner.pubkey(),
+            blockhash,
+        )
+        .await
+        .unwrap();
+    }
+
+    // MintTo logs (mint, destination, amount)
+    let mint_to_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new(source.pubkey(), false),
+            AccountMeta::new_readonly(mint_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::MintTo { amount: 1_000 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[mint_to_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        blockhash,
+    );
+
+    let simulation = context
+        .banks_client
+        .simulate_transaction(tx.clone())
+        .await
+        .unwrap();
+    let logs = simulation.simulation_details.unwrap().logs;
+
+    let (tag, data) = find_event(&logs).unwrap();
+    assert_eq!(tag, events::EVENT_MINT_TO);
+    assert_eq!(&data[..32], mint.pubkey().as_ref());
+    assert_eq!(&data[32..64], source.pubkey().as_ref());
+    assert_eq!(u64::from_le_bytes(data[64..].try_into().unwrap()), 1_000);
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Transfer logs (source, destination, amount)
+    let transfer_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source.pubkey(), false),
+            AccountMeta::new(destination.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+        ],
+        data: TokenInstruction::Transfer { amount: 250 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+
+    let simulation = context
+        .banks_client
+        .simulate_transaction(tx)
+        .await
+        .unwrap();
+    let logs = simulation.simulation_details.unwrap().logs;
+
+    let (tag, data) = find_event(&logs).unwrap();
+    assert_eq!(tag, events::EVENT_TRANSFER);
+    assert_eq!(&data[..32], source.pubkey().as_ref());
+    assert_eq!(&data[32..64], destination.pubkey().as_ref());
+    assert_eq!(u64::from_le_bytes(data[64..].try_into().unwrap()), 250);
+}
+
+// =============================================================================
+// ZERO AMOUNT TESTS (reject-zero-amount feature)
+// =============================================================================
+
+/// Create a mint and a token account holding 1000 tokens, then build
+/// MintTo and Burn instructions for amount 0;
+/// returns (mint_to_ix, burn_ix, mint_authority, owner, mint).
+async fn zero_amount_instructions(
+    context: &mut ProgramTestContext,
+) -> (Instruction, Instruction, Keypair, Keypair, Pubkey) {
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    let owner = Keypair::new();
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &token_account,
+        &mint.pubkey(),
+        &owner.pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    mint_tokens(
+        &mut context.banks_client,
+        &context.payer,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        1000,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let mint_to_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::MintTo { amount: 0 }.pack(),
+    };
+
+    let burn_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::Burn { amount: 0 }.pack(),
+    };
+
+    (mint_to_ix, burn_ix, mint_authority, owner, mint.pubkey())
+}
+
+#[cfg(feature = "reject-zero-amount")]
+#[tokio::test]
+async fn test_zero_amount_mint_to_and_burn_rejected() {
+    let mut context = program_test().start_with_context().await;
+
+    let (mint_to_ix, burn_ix, mint_authority, owner, mint) =
+        zero_amount_instructions(&mut context).await;
+
+    for (ix, signer) in [(mint_to_ix, &mint_authority), (burn_ix, &owner)] {
+        let blockhash = get_recent_blockhash(&mut context).await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, signer],
+            blockhash,
+        );
+
+        let result = context.banks_client.process_transaction(tx).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::ZeroAmount as u32)
+            )
+        );
+    }
+
+    let mint_state = get_mint(&mut context.banks_client, &mint).await;
+    assert_eq!(mint_state.supply, 1000);
+}
+
+#[cfg(not(feature = "reject-zero-amount"))]
+#[tokio::test]
+async fn test_zero_amount_mint_to_and_burn_succeed() {
+    let mut context = program_test().start_with_context().await;
+
+    let (mint_to_ix, burn_ix, mint_authority, owner, mint) =
+        zero_amount_instructions(&mut context).await;
+
+    // Without the feature both are accepted as no-ops
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[mint_to_ix, burn_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let mint_state = get_mint(&mut context.banks_client, &mint).await;
+    assert_eq!(mint_state.supply, 1000);
+}
+
+// =============================================================================
+// MINT TO BATCH TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_mint_to_batch() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Four destinations
+    let dest_accounts = [Keypair::new(), Keypair::new(), Keypair::new(), Keypair::new()];
+    for dest_account in &dest_accounts {
+        let blockhash = get_recent_blockhash(&mut context).await;
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            dest_account,
+            &mint.pubkey(),
+            &Keypair::new().pubkey(),
+            blockhash,
+        )
+        .await
+        .unwrap();
+    }
+
+    // Airdrop 100 / 200 / 300 / 400
+    let amounts = vec![100u64, 200, 300, 400];
+
+    let mut accounts = vec![
+        AccountMeta::new(mint.pubkey(), false),
+        AccountMeta::new_readonly(mint_authority.pubkey(), true),
+    ];
+    for dest_account in &dest_accounts {
+        accounts.push(AccountMeta::new(dest_account.pubkey(), false));
+    }
+
+    let batch_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts,
+        data: TokenInstruction::MintToBatch {
+            amounts: amounts.clone(),
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[batch_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify balances and supply
+    for (dest_account, amount) in dest_accounts.iter().zip(&amounts) {
+        let dest_state =
+            get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+        assert_eq!(dest_state.amount, *amount);
+    }
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, 1000);
+}
+
+#[tokio::test]
+async fn test_mint_to_batch_wrong_mint_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup: two mints, one destination of each
+    let mint = Keypair::new();
+    let other_mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    for m in [&mint, &other_mint] {
+        let blockhash = get_recent_blockhash(&mut context).await;
+        create_mint(
+            &mut context.banks_client,
+            &context.payer,
+            m,
+            &mint_authority.pubkey(),
+            None,
+            9,
+            blockhash,
+        )
+        .await
+        .unwrap();
+    }
+
+    let good_account = Keypair::new();
+    let wrong_account = Keypair::new();
+    for (account, account_mint) in [(&good_account, &mint), (&wrong_account, &other_mint)] {
+        let blockhash = get_recent_blockhash(&mut context).await;
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            account,
+            &account_mint.pubkey(),
+            &Keypair::new().pubkey(),
+            blockhash,
+        )
+        .await
+        .unwrap();
+    }
+
+    let batch_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new_readonly(mint_authority.pubkey(), true),
+            AccountMeta::new(good_account.pubkey(), false),
+            AccountMeta::new(wrong_account.pubkey(), false),
+        ],
+        data: TokenInstruction::MintToBatch {
+            amounts: vec![100, 100],
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[batch_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::MintMismatch as u32)
+        )
+    );
+
+    // Nothing was minted
+    let good_state = get_token_account(&mut context.banks_client, &good_account.pubkey()).await;
+    assert_eq!(good_state.amount, 0);
+
+    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
+    assert_eq!(mint_state.supply, 0);
+}
+
+// =============================================================================
+// REVOKE BATCH TESTS
+// =============================================================================
+
+#[tokio::test]
+async fn test_revoke_batch() {
+    let mut context = program_test().start_with_context().await;
+
+    // Setup
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        9,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Three accounts of one owner, each with its own delegate
+    let owner = Keypair::new();
+    let token_accounts = [Keypair::new(), Keypair::new(), Keypair::new()];
+    for token_account in &token_accounts {
+        let blockhash = get_recent_blockhash(&mut context).await;
+        create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            token_account,
+            &mint.pubkey(),
+            &owner.pubkey(),
+            blockhash,
+        )
+        .await
+        .unwrap();
+
+        let approve_ix = Instruction {
+            program_id: spl_token_from_scratch::id(),
+            accounts: vec![
+                AccountMeta::new(token_account.pubkey(), false),
+                AccountMeta::new_readonly(Keypair::new().pubkey(), false),
+                AccountMeta::new_readonly(owner.pubkey(), true),
+            ],
+            data: TokenInstruction::Approve { amount: 100 }.pack(),
+        };
+
+        let blockhash = get_recent_blockhash(&mut context).await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[approve_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &owner],
+            blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+    }
+
+    // Revoke all three at once
+    let mut accounts = vec![AccountMeta::new_readonly(owner.pubkey(), true)];
+    for token_account in &token_accounts {
+        accounts.push(AccountMeta::new(token_account.pubkey(), false));
+    }
+
+    let revoke_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts,
+        data: TokenInstruction::RevokeBatch { count: 3 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[revoke_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    for token_account in &token_accounts {
+        let account_state =
+            get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+        assert!(account_state.delegate.is_none());
+        assert_eq!(account_state.delegated_amount, 0);
+    }
+}
+
+#[tokio::test]
+async fn test_revoke_batch_frozen_account() {
+    let mut context = program_test().start_with_context().await;
+
+    // Frozen accounts are revoked too: nothing moves
+    let (token_account, owner, mint, freeze_authority) =
+        setup_freezable_account(&mut context).await;
+
+    let approve_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(Keypair::new().pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::Approve { amount: 100 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[approve_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Freeze after approving
+    let freeze_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(freeze_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::FreezeAccount.pack(),
+    };
+
+    let revoke_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(owner.pubkey(), true),
+            AccountMeta::new(token_account.pubkey(), false),
+        ],
+        data: TokenInstruction::RevokeBatch { count: 1 }.pack(),
+    };
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_ix, revoke_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &freeze_authority, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert!(account_state.is_frozen());
+    assert!(account_state.delegate.is_none());
+    assert_eq!(account_state.delegated_amount, 0);
+}
+
+// =============================================================================
+// INITIALIZE ACCOUNT IDEMPOTENT TESTS
+// =============================================================================
+
+/// Build an InitializeAccountIdempotent instruction (instructions sysvar
+/// passed for the fresh-account-check feature).
+fn initialize_account_idempotent_ix(
+    account: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
+        ],
+        data: TokenInstruction::InitializeAccountIdempotent { owner: *owner }.pack(),
+    }
+}
+
+#[tokio::test]
+async fn test_initialize_account_idempotent() {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let owner = Keypair::new();
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &mint,
+        &mint_authority.pubkey(),
+        None,
+        6,
+        context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    // Fresh account: initialized like InitializeAccount
+    let token_account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let create_ix = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &token_account.pubkey(),
+        rent.minimum_balance(TokenAccount::LEN),
+        TokenAccount::LEN as u64,
+        &spl_token_from_scratch::id(),
+    );
+
+    let init_ix = initialize_account_idempotent_ix(
+        &token_account.pubkey(),
+        &mint.pubkey(),
+        &owner.pubkey(),
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix.clone()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &token_account],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.mint, mint.pubkey());
+    assert_eq!(account_state.owner, owner.pubkey());
+    assert!(account_state.is_initialized());
+
+    // Same call again succeeds and changes nothing
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let again = get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(again, account_state);
+}
+
+#[tokio::test]
+async fn test_initialize_account_idempotent_mismatch_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let (token_account, owner) = setup_funded_account(&mut context).await;
+    let mint = get_token_account(&mut context.banks_client, &token_account.pubkey())
+        .await
+        .mint;
+
+    // Another owner
+    let wrong_owner_ix = initialize_account_idempotent_ix(
+        &token_account.pubkey(),
+        &mint,
+        &Pubkey::new_unique(),
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[wrong_owner_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::OwnerMismatch as u32)
+        )
+    );
+
+    // Another mint
+    let other_mint = Keypair::new();
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &other_mint,
+        &Pubkey::new_unique(),
+        None,
+        6,
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    let wrong_mint_ix = initialize_account_idempotent_ix(
+        &token_account.pubkey(),
+        &other_mint.pubkey(),
+        &owner.pubkey(),
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[wrong_mint_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(tx).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::MintMismatch as u32)
+        )
+    );
+
+    // Account unchanged
+    let account_state =
+        get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account_state.owner, owner.pubkey());
+    assert_eq!(account_state.amount, 1000);
+}
+
+// =============================================================================
+// DELEGATE EXPIRY TESTS
+// =============================================================================
+
+/// Helper to set up a funded extended source with a delegate approved
+/// for 100 until `expiry`, and an empty destination.
+///
+/// Returns (source, destination, delegate).
+async fn setup_expiring_delegate(
+    context: &mut ProgramTestContext,
+    expiry: i64,
+) -> (Keypair, Keypair, Keypair, Pubkey) {
+    let (source_account, owner) = setup_funded_account(context).await;
+    let mint = get_token_account(&mut context.banks_client, &source_account.pubkey())
+        .await
+        .mint;
+
+    let dest_account = Keypair::new();
+    let blockhash = get_recent_blockhash(context).await;
+
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &dest_account,
+        &mint,
+        &Keypair::new().pubkey(),
+        blockhash,
+    )
+    .await
+    .unwrap();
+
+    // The expiry lives in the extension area, so migrate first
+    let delegate = Keypair::new();
+
+    let migrate_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::MigrateAccount.pack(),
+    };
+
+    let approve_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), false),
+            AccountMeta::new_readonly(owner.pubkey(), true),
+        ],
+        data: TokenInstruction::ApproveWithExpiry {
+            amount: 100,
+            expiry_unix_ts: expiry,
+        }
+        .pack(),
+    };
+
+    let blockhash = get_recent_blockhash(context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[migrate_ix, approve_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let source = get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source.delegate, COption::some(delegate.pubkey()));
+    assert_eq!(source.delegate_expiry, COption::some(expiry));
+
+    (source_account, dest_account, delegate, mint)
+}
+
+/// Build a delegate Transfer of `amount` that passes the Clock sysvar.
+fn delegate_transfer_with_clock_ix(
+    source: &Pubkey,
+    destination: &Pubkey,
+    delegate: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*delegate, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+        ],
+        data: TokenInstruction::Transfer { amount }.pack(),
+    }
+}
+
+#[tokio::test]
+async fn test_delegate_transfer_before_expiry() {
+    let mut context = program_test().start_with_context().await;
+
+    let clock = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap();
+    let expiry = clock.unix_timestamp + 3600;
+
+    let (source_account, dest_account, delegate, mint) =
+        setup_expiring_delegate(&mut context, expiry).await;
+
+    let transfer_ix = delegate_transfer_with_clock_ix(
+        &source_account.pubkey(),
+        &dest_account.pubkey(),
+        &delegate.pubkey(),
+        &mint,
+        40,
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &delegate],
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let source = get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source.amount, 960);
+    assert_eq!(source.delegated_amount, 60);
+    assert_eq!(source.delegate_expiry, COption::some(expiry));
+
+    let dest = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
+    assert_eq!(dest.amount, 40);
+}
+
+#[tokio::test]
+async fn test_delegate_transfer_after_expiry_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    let clock = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap();
+    let expiry = clock.unix_timestamp + 3600;
 
-    // Try to mint with only 1 signer (needs 2)
-    let mint_to_ix = Instruction {
+    let (source_account, dest_account, delegate, mint) =
+        setup_expiring_delegate(&mut context, expiry).await;
+
+    // Without the Clock sysvar the expiry can't be checked
+    let transfer_ix = Instruction {
         program_id: spl_token_from_scratch::id(),
         accounts: vec![
-            AccountMeta::new(mint.pubkey(), false),
-            AccountMeta::new(token_account.pubkey(), false),
-            AccountMeta::new_readonly(multisig.pubkey(), false),
-            AccountMeta::new_readonly(signer1.pubkey(), true), // Only 1 signer!
+            AccountMeta::new(source_account.pubkey(), false),
+            AccountMeta::new(dest_account.pubkey(), false),
+            AccountMeta::new_readonly(delegate.pubkey(), true),
+            AccountMeta::new_readonly(mint, false),
         ],
-        data: TokenInstruction::MintTo { amount: 1000 }.pack(),
+        data: TokenInstruction::Transfer { amount: 40 }.pack(),
     };
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
     let tx = Transaction::new_signed_with_payer(
-        &[mint_to_ix],
+        &[transfer_ix],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &signer1],
+        &[&context.payer, &delegate],
         blockhash,
     );
+    let err = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
 
-    // Should fail - not enough signers
-    let result = context.banks_client.process_transaction(tx).await;
-    assert!(result.is_err());
+    // Move the clock one second past the expiry
+    context.set_sysvar(&solana_program::clock::Clock {
+        unix_timestamp: expiry + 1,
+        ..clock
+    });
+
+    let transfer_ix = delegate_transfer_with_clock_ix(
+        &source_account.pubkey(),
+        &dest_account.pubkey(),
+        &delegate.pubkey(),
+        &mint,
+        40,
+    );
+
+    let blockhash = get_recent_blockhash(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &delegate],
+        blockhash,
+    );
+    let err = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::DelegateExpired as u32)
+        )
+    );
+
+    // Nothing moved
+    let source = get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
+    assert_eq!(source.amount, 1000);
+    assert_eq!(source.delegated_amount, 100);
 }
 
 // =============================================================================
-// EDGE CASE TESTS
+// CPI GUARD TESTS (cpi-guard feature)
 // =============================================================================
 
-#[tokio::test]
-async fn test_transfer_zero_amount() {
-    let mut context = program_test().start_with_context().await;
+/// Program that forwards its data to the program in account 0 as a CPI,
+/// passing the remaining accounts through unchanged.
+#[cfg(feature = "cpi-guard")]
+fn forwarding_program(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let (target_program, forwarded) = accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let instruction = Instruction {
+        program_id: *target_program.key,
+        accounts: forwarded
+            .iter()
+            .map(|info| AccountMeta {
+                pubkey: *info.key,
+                is_signer: info.is_signer,
+                is_writable: info.is_writable,
+            })
+            .collect(),
+        data: data.to_vec(),
+    };
 
-    // Setup
-    let mint = Keypair::new();
-    let mint_authority = Keypair::new();
+    solana_program::program::invoke(&instruction, forwarded)
+}
 
-    create_mint(
-        &mut context.banks_client,
-        &context.payer,
-        &mint,
-        &mint_authority.pubkey(),
-        None,
-        9,
-        context.last_blockhash,
-    )
-    .await
-    .unwrap();
+/// Build a SetAuthority(AccountOwner) instruction.
+#[cfg(feature = "cpi-guard")]
+fn guarded_set_owner_ix(account: &Pubkey, owner: &Pubkey, new_owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data: TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::AccountOwner,
+            new_authority: Some(*new_owner),
+        }
+        .pack(),
+    }
+}
 
-    let source_account = Keypair::new();
-    let owner = Keypair::new();
+#[cfg(feature = "cpi-guard")]
+#[tokio::test]
+async fn test_set_authority_direct_call_with_cpi_guard() {
+    let mut context = program_test().start_with_context().await;
+    let (token_account, owner) = setup_funded_account(&mut context).await;
+    let new_owner = Pubkey::new_unique();
+
+    // A direct call succeeds without any extra accounts
+    let set_owner_ix = guarded_set_owner_ix(&token_account.pubkey(), &owner.pubkey(), &new_owner);
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
-    create_token_account(
-        &mut context.banks_client,
-        &context.payer,
-        &source_account,
-        &mint.pubkey(),
-        &owner.pubkey(),
+    let tx = Transaction::new_signed_with_payer(
+        &[set_owner_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &owner],
         blockhash,
-    )
-    .await
-    .unwrap();
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
 
-    let dest_account = Keypair::new();
+    let account = get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account.owner, new_owner);
+}
 
-    let blockhash = get_recent_blockhash(&mut context).await;
+#[cfg(feature = "cpi-guard")]
+#[tokio::test]
+async fn test_set_authority_through_cpi_fails_with_cpi_guard() {
+    let forwarder = Pubkey::new_unique();
+    let mut program_test = program_test();
+    program_test.add_program(
+        "forwarding_program",
+        forwarder,
+        processor!(forwarding_program),
+    );
+    let mut context = program_test.start_with_context().await;
 
-    create_token_account(
-        &mut context.banks_client,
-        &context.payer,
-        &dest_account,
-        &mint.pubkey(),
-        &Keypair::new().pubkey(),
-        blockhash,
-    )
-    .await
-    .unwrap();
+    let (token_account, owner) = setup_funded_account(&mut context).await;
+    let new_owner = Pubkey::new_unique();
 
-    // Transfer 0 tokens (should succeed, just a no-op)
-    let transfer_ix = Instruction {
-        program_id: spl_token_from_scratch::id(),
-        accounts: vec![
-            AccountMeta::new(source_account.pubkey(), false),
-            AccountMeta::new(dest_account.pubkey(), false),
-            AccountMeta::new_readonly(owner.pubkey(), true),
-        ],
-        data: TokenInstruction::Transfer { amount: 0 }.pack(),
+    // Same instruction, but routed through the forwarding program
+    let set_owner_ix = guarded_set_owner_ix(&token_account.pubkey(), &owner.pubkey(), &new_owner);
+    let mut accounts = vec![AccountMeta::new_readonly(spl_token_from_scratch::id(), false)];
+    accounts.extend(set_owner_ix.accounts);
+    let forward_ix = Instruction {
+        program_id: forwarder,
+        accounts,
+        data: set_owner_ix.data,
     };
 
     let blockhash = get_recent_blockhash(&mut context).await;
 
     let tx = Transaction::new_signed_with_payer(
-        &[transfer_ix],
+        &[forward_ix],
         Some(&context.payer.pubkey()),
         &[&context.payer, &owner],
         blockhash,
     );
+    let err = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::CpiGuardViolation as u32)
+        )
+    );
 
-    // Should succeed
-    context.banks_client.process_transaction(tx).await.unwrap();
-
-    // Verify balances unchanged
-    let source_state =
-        get_token_account(&mut context.banks_client, &source_account.pubkey()).await;
-    assert_eq!(source_state.amount, 0);
-
-    let dest_state = get_token_account(&mut context.banks_client, &dest_account.pubkey()).await;
-    assert_eq!(dest_state.amount, 0);
+    let account = get_token_account(&mut context.banks_client, &token_account.pubkey()).await;
+    assert_eq!(account.owner, owner.pubkey());
 }
 
-#[tokio::test]
-async fn test_multiple_mints_and_transfers() {
-    let mut context = program_test().start_with_context().await;
+// =============================================================================
+// MINT TO FROZEN TESTS (allow-mint-to-frozen feature)
+// =============================================================================
 
-    // Setup
+/// Create a freezable mint and a frozen token account for it, then try
+/// to mint 100 to that account. Returns the result and (mint, account).
+async fn mint_to_frozen_account(
+    context: &mut ProgramTestContext,
+) -> (Result<(), BanksClientError>, Pubkey, Pubkey) {
     let mint = Keypair::new();
     let mint_authority = Keypair::new();
+    let freeze_authority = Keypair::new();
 
     create_mint(
         &mut context.banks_client,
         &context.payer,
         &mint,
         &mint_authority.pubkey(),
-        None,
+        Some(&freeze_authority.pubkey()),
         9,
         context.last_blockhash,
     )
     .await
     .unwrap();
 
-    let account1 = Keypair::new();
-    let owner1 = Keypair::new();
-
-    let blockhash = get_recent_blockhash(&mut context).await;
+    let token_account = Keypair::new();
+    let blockhash = get_recent_blockhash(context).await;
 
     create_token_account(
         &mut context.banks_client,
         &context.payer,
-        &account1,
+        &token_account,
         &mint.pubkey(),
-        &owner1.pubkey(),
+        &Keypair::new().pubkey(),
         blockhash,
     )
     .await
     .unwrap();
 
-    let account2 = Keypair::new();
-    let owner2 = Keypair::new();
+    let freeze_ix = Instruction {
+        program_id: spl_token_from_scratch::id(),
+        accounts: vec![
+            AccountMeta::new(token_account.pubkey(), false),
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new_readonly(freeze_authority.pubkey(), true),
+        ],
+        data: TokenInstruction::FreezeAccount.pack(),
+    };
 
-    let blockhash = get_recent_blockhash(&mut context).await;
+    let blockhash = get_recent_blockhash(context).await;
 
-    create_token_account(
-        &mut context.banks_client,
-        &context.payer,
-        &account2,
-        &mint.pubkey(),
-        &owner2.pubkey(),
+    let tx = Transaction::new_signed_with_payer(
+        &[freeze_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &freeze_authority],
         blockhash,
-    )
-    .await
-    .unwrap();
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
 
-    // Mint 1000 to account1
-    let blockhash = get_recent_blockhash(&mut context).await;
-    mint_tokens(
-        &mut context.banks_client,
-        &context.payer,
-        &mint.pubkey(),
-        &account1.pubkey(),
-        &mint_authority,
-        1000,
-        blockhash,
-    )
-    .await
-    .unwrap();
+    let blockhash = get_recent_blockhash(context).await;
 
-    // Mint 500 more to account1
-    let blockhash = get_recent_blockhash(&mut context).await;
-    mint_tokens(
+    let result = mint_tokens(
         &mut context.banks_client,
         &context.payer,
         &mint.pubkey(),
-        &account1.pubkey(),
+        &token_account.pubkey(),
         &mint_authority,
-        500,
+        100,
         blockhash,
     )
-    .await
-    .unwrap();
+    .await;
 
-    // Transfer 300 from account1 to account2
-    let transfer_ix = Instruction {
-        program_id: spl_token_from_scratch::id(),
-        accounts: vec![
-            AccountMeta::new(account1.pubkey(), false),
-            AccountMeta::new(account2.pubkey(), false),
-            AccountMeta::new_readonly(owner1.pubkey(), true),
-        ],
-        data: TokenInstruction::Transfer { amount: 300 }.pack(),
-    };
+    (result, mint.pubkey(), token_account.pubkey())
+}
 
-    let blockhash = get_recent_blockhash(&mut context).await;
+#[cfg(not(feature = "allow-mint-to-frozen"))]
+#[tokio::test]
+async fn test_mint_to_frozen_account_fails() {
+    let mut context = program_test().start_with_context().await;
 
-    let tx = Transaction::new_signed_with_payer(
-        &[transfer_ix],
-        Some(&context.payer.pubkey()),
-        &[&context.payer, &owner1],
-        blockhash,
+    let (result, mint, token_account) = mint_to_frozen_account(&mut context).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::AccountFrozen as u32)
+        )
     );
-    context.banks_client.process_transaction(tx).await.unwrap();
-
-    // Transfer 100 from account2 to account1
-    let transfer_ix = Instruction {
-        program_id: spl_token_from_scratch::id(),
-        accounts: vec![
-            AccountMeta::new(account2.pubkey(), false),
-            AccountMeta::new(account1.pubkey(), false),
-            AccountMeta::new_readonly(owner2.pubkey(), true),
-        ],
-        data: TokenInstruction::Transfer { amount: 100 }.pack(),
-    };
 
-    let blockhash = get_recent_blockhash(&mut context).await;
+    let account = get_token_account(&mut context.banks_client, &token_account).await;
+    assert_eq!(account.amount, 0);
 
-    let tx = Transaction::new_signed_with_payer(
-        &[transfer_ix],
-        Some(&context.payer.pubkey()),
-        &[&context.payer, &owner2],
-        blockhash,
-    );
-    context.banks_client.process_transaction(tx).await.unwrap();
+    let mint_state = get_mint(&mut context.banks_client, &mint).await;
+    assert_eq!(mint_state.supply, 0);
+}
 
-    // Verify final balances
-    // account1: 1000 + 500 - 300 + 100 = 1300
-    // account2: 0 + 300 - 100 = 200
-    // supply: 1000 + 500 = 1500
+#[cfg(feature = "allow-mint-to-frozen")]
+#[tokio::test]
+async fn test_mint_to_frozen_account_succeeds() {
+    let mut context = program_test().start_with_context().await;
 
-    let account1_state = get_token_account(&mut context.banks_client, &account1.pubkey()).await;
-    assert_eq!(account1_state.amount, 1300);
+    let (result, mint, token_account) = mint_to_frozen_account(&mut context).await;
+    result.unwrap();
 
-    let account2_state = get_token_account(&mut context.banks_client, &account2.pubkey()).await;
-    assert_eq!(account2_state.amount, 200);
+    // The tokens arrive, and the account stays frozen
+    let account = get_token_account(&mut context.banks_client, &token_account).await;
+    assert_eq!(account.amount, 100);
+    assert!(account.is_frozen());
 
-    let mint_state = get_mint(&mut context.banks_client, &mint.pubkey()).await;
-    assert_eq!(mint_state.supply, 1500);
+    let mint_state = get_mint(&mut context.banks_client, &mint).await;
+    assert_eq!(mint_state.supply, 100);
 }
 
 /*
@@ -2341,6 +11127,7 @@ These tests cover:
 INITIALIZATION
 - Initialize mint with/without freeze authority
 - Initialize mint already initialized (fails)
+- Initialize mint with decimals 0, 9, 18 (ok) and 19 (fails)
 - Initialize token account
 - Initialize multisig (2-of-3)
 - Initialize multisig with invalid m > n (fails)
@@ -2412,33 +11199,55 @@ INITIALIZATION TESTS
 ✅ InitializeMint with freeze authority
 ✅ InitializeMint without freeze authority
 ✅ InitializeMint already initialized (fails)
+✅ InitializeMint with decimals 0, 9 and MAX_DECIMALS (18)
+✅ InitializeMint with decimals 19 (fails with InvalidDecimals)
 ✅ InitializeAccount
 ✅ InitializeMultisig (2-of-3)
-✅ InitializeMultisig with invalid m > n (fails)
+✅ InitializeMultisig with invalid m > n (fails with InvalidMultisigConfig)
+✅ InitializeMultisig with no signers (fails with InvalidMultisigConfig)
+✅ InitializeMultisig with more than MAX_SIGNERS signers (fails with InvalidMultisigConfig)
+✅ InitializeAccount already initialized (fails with AlreadyInitialized)
+✅ InitializeMultisig already initialized (fails with AlreadyInitialized)
+✅ InitializeAccount below rent-exempt minimum (fails with NotRentExempt)
+✅ InitializeMultisig below rent-exempt minimum (fails with NotRentExempt)
 
 MINT_TO TESTS
 ✅ Mint tokens successfully
 ✅ Mint with wrong authority (fails)
 ✅ Mint to account with wrong mint (fails)
+✅ Mint signed by the destination's delegate (fails with InvalidAuthority)
+✅ Mint with the mint as destination (fails with ExpectedAccount)
+✅ Mint to an uninitialized account (fails with UninitializedAccount)
+✅ Mint to a read-only destination (fails with InvalidAccountData)
 
 TRANSFER TESTS
 ✅ Transfer tokens successfully
 ✅ Transfer with insufficient funds (fails)
 ✅ Transfer with wrong owner (fails)
 ✅ Transfer zero amount (succeeds - no-op)
+✅ Transfer to the same account (fails with SelfTransfer)
+✅ Transfer between two accounts of one owner, by the owner and by a delegate
 
 BURN TESTS
 ✅ Burn tokens successfully
+✅ Burn with mismatched mint (fails with MintMismatch)
+✅ Delegate burn past allowance with ample balance (fails with InsufficientDelegatedAmount)
+✅ Burn with supply below the balance (fails with SupplyUnderflow)
 
 APPROVE / REVOKE TESTS
 ✅ Approve delegate
 ✅ Transfer using delegate
 ✅ Delegate exceeds allowance (fails)
+✅ Delegate within allowance but beyond balance (fails with InsufficientFunds)
+✅ Delegate beyond allowance with ample balance (fails with InsufficientDelegatedAmount)
 ✅ Revoke delegate
+✅ Revoke by the delegate (fails with InvalidAuthority)
 
 SET AUTHORITY TESTS
 ✅ Change mint authority
 ✅ Remove mint authority (fixed supply)
+✅ Revoking mint authority logs "SetAuthority: MintTokens removed"
+✅ Add a freeze authority to a mint without one (fails with CannotAddFreezeAuthority)
 
 CLOSE ACCOUNT TESTS
 ✅ Close empty account successfully
@@ -2448,15 +11257,176 @@ FREEZE / THAW TESTS
 ✅ Freeze account
 ✅ Thaw account
 ✅ Transfer from frozen account (fails)
+✅ Approve on frozen account (fails with AccountFrozen)
+✅ Change owner of frozen account (fails with AccountFrozen)
+✅ Close empty frozen account (fails with AccountFrozen)
+✅ Close empty unfrozen account of a freezable mint
 
 MULTISIG TESTS
 ✅ Mint with multisig authority (2-of-3)
 ✅ Mint with insufficient multisig signers (fails)
+✅ Transfer by multisig owner short of signers reports NotEnoughSigners
+✅ Transfer by neither owner nor delegate reports InvalidAuthority
+✅ Transfer by a multisig delegate with M signers
+✅ Freeze and thaw by a 2-of-3 multisig freeze authority (1 signer fails)
+✅ Rotate a 2-of-3 multisig mint authority to a single key (1 signer fails)
 
 EDGE CASE TESTS
 ✅ Multiple mints and transfers in sequence
 ✅ Transfer zero amount
 
+INITIALIZE ACCOUNT WITH CLOSE AUTHORITY TESTS
+✅ Close authority set at creation
+✅ Close authority can close, owner alone cannot
+
+TRANSFER BATCH TESTS
+✅ Transfer to 3 destinations in one instruction
+✅ Batch that overdraws the source (fails, nothing moves)
+✅ Batch of a non-transferable mint (NonTransferable)
+✅ Batch to a memo-required destination without a memo (MemoRequired)
+✅ Batch with a rejecting transfer hook (TransferHookFailed, nothing moves)
+
+GET SUPPLY TESTS
+✅ Supply read back via simulated GetSupply return data
+
+BURN ALL TESTS
+✅ BurnAll empties the account and its share of supply
+
+APPROVE ALL TESTS
+✅ ApproveAll then delegate transfers the full balance
+✅ ApproveAll on frozen account (fails)
+
+TRANSFER RETURN DATA TESTS
+✅ Transfer with trailing flag returns the new source balance
+
+STRICT MULTISIG TESTS (strict-multisig feature)
+✅ 1-of-3 rejected (MultisigThresholdTooLow)
+✅ 2-of-3 allowed
+✅ 1-of-1 allowed
+
+OVERFLOW ATOMICITY TESTS
+✅ Destination overflow fails with Overflow and leaves the source unchanged
+
+FRESH ACCOUNT CHECK TESTS (fresh-account-check feature)
+✅ Create + initialize in one transaction succeeds
+✅ Initialize without a preceding create fails (AccountNotFreshlyCreated)
+
+OPTIONAL RENT SYSVAR TESTS
+✅ InitializeMint with and without rent sysvar account
+✅ InitializeAccount with and without rent sysvar account
+
+REALLOCATE TESTS
+✅ Grow to Account::EXTENDED_LEN stays rent exempt, state intact, transfer out works
+✅ Any length other than Account::LEN / EXTENDED_LEN fails (100 and 200 bytes)
+✅ Resizing a wrapped SOL account moves its rent reserve, amount stays lamports - reserve
+✅ MigrateAccount upgrades a legacy account to the extended layout (only once)
+✅ MigrateAccount raises a wrapped SOL account's rent reserve, payer covers it
+
+NON-TRANSFERABLE TESTS
+✅ Non-transferable mint: mint ok, transfer fails, burn ok
+✅ Non-transferable mint left out (NotEnoughAccountKeys) or swapped for another (MintMismatch)
+✅ SetNonTransferable on a base 82-byte mint fails
+
+MEMO REQUIRED TESTS
+✅ Transfer into memo-required account with a preceding memo
+✅ Transfer into memo-required account without a memo fails (MemoRequired)
+
+RECONCILE SUPPLY TESTS
+✅ Supply and balance sum of three accounts via return data
+
+TRANSFER WITH FEE TESTS
+✅ 100 bps fee on 10_000 tokens (9_900 to destination, 100 to fee account)
+✅ Zero fee sends everything to the destination
+✅ u64::MAX at 100 bps (fee computed without overflowing)
+✅ Fee transfer of a non-transferable mint (NonTransferable)
+✅ Fee transfer with a rejecting transfer hook (TransferHookFailed, nothing moves)
+
+NATIVE ACCOUNT TESTS
+✅ Freezing a wrapped SOL account fails (CannotFreezeNative); a regular account still freezes
+✅ is_native stays Some(rent_reserve) through transfer, approve and revoke
+✅ Close a wrapped SOL account with a balance (all lamports to destination)
+✅ Close a wrapped SOL account with CloseAccountWithReserve (reserve split off)
+✅ Close a multisig-owned wrapped SOL account (a non-signing member is never paid)
+✅ CloseAccountWithReserve on a non-native account fails (InvalidState)
+✅ Transfer between wrapped SOL accounts moves lamports (amount == lamports - reserve on both)
+✅ TransferBatch between wrapped SOL accounts moves each leg's lamports
+✅ TransferWithFee between wrapped SOL accounts moves the net and fee lamports
+
+SUPPLY CAP TESTS
+✅ Cap set, mint up to the cap, over-cap mint fails (SupplyCapExceeded)
+
+APPROVE AND SET CLOSE AUTHORITY TESTS
+✅ Delegate, allowance and close authority set in one call
+✅ Owner signature required (fails without it)
+✅ Delegate can't invoke it (InvalidAuthority)
+✅ Owner can't overwrite a close authority held by someone else (CloseAuthorityMismatch)
+
+VERIFY ACCOUNT TESTS
+✅ VerifyAccount reports 0 when mint and owner match
+✅ VerifyAccount reports 1 on mint mismatch
+✅ VerifyAccount reports 2 on owner mismatch
+✅ VerifyAccount reports 3 for frozen and uninitialized accounts
+
+CREATE ACCOUNT TESTS
+✅ CreateAccount creates, funds and initializes in one instruction
+
+MINT PAUSE TESTS
+✅ Pause blocks MintTo (MintPaused), burn still works, resume re-enables minting
+✅ SetMintPaused by a non-authority fails
+
+FREEZE ACCOUNTS TESTS
+✅ FreezeAccounts freezes 3 accounts of one mint at once
+✅ FreezeAccounts with an account from another mint fails (MintMismatch), nothing frozen
+
+GET MINT INFO TESTS
+✅ GetMintInfo return data decodes to supply, decimals and flags
+
+MINT CLOSE AUTHORITY
+✅ test_close_mint_with_close_authority
+✅ test_close_mint_with_supply_fails
+
+GET ACCOUNT BALANCE
+✅ GetAccountBalance return data decodes to the balance
+
+GET MULTISIG INFO TESTS
+✅ GetMultisigInfo return data decodes to m=2, n=3 and the signers
+
+TRANSFER HOOK TESTS
+✅ Transfer invokes an accepting hook, which logs the amount
+✅ Transfer with a rejecting hook fails with TransferHookFailed
+✅ Transfer leaving out the hooked mint fails instead of skipping the hook
+
+EVENT LOG TESTS (events feature)
+✅ MintTo and Transfer log decodable event records
+
+ZERO AMOUNT TESTS (reject-zero-amount feature)
+✅ MintTo and Burn of 0 fail with ZeroAmount (with the feature)
+✅ MintTo and Burn of 0 succeed as no-ops (without the feature)
+
+MINT TO BATCH TESTS
+✅ Mint to 4 destinations in one instruction, supply updated once
+✅ Destination of another mint (fails with MintMismatch, nothing minted)
+
+REVOKE BATCH TESTS
+✅ Revoke delegates on 3 accounts of one owner at once
+✅ Frozen account is revoked too
+
+INITIALIZE ACCOUNT IDEMPOTENT TESTS
+✅ Fresh account initialized, same call again succeeds unchanged
+✅ Existing account with another owner or mint (fails with OwnerMismatch / MintMismatch)
+
+DELEGATE EXPIRY TESTS
+✅ Delegate transfer before the expiry succeeds
+✅ Delegate transfer needs the Clock sysvar, and fails after the expiry
+
+CPI GUARD TESTS (cpi-guard feature)
+✅ SetAuthority called directly succeeds without extra accounts
+✅ SetAuthority through CPI fails with CpiGuardViolation
+
+MINT TO FROZEN TESTS (allow-mint-to-frozen feature)
+✅ MintTo a frozen account fails with AccountFrozen by default
+✅ MintTo a frozen account succeeds under the feature, account stays frozen
+
 =============================================================================
 HOW TO RUN TESTS
 =============================================================================
@@ -2496,6 +11466,7 @@ spl-token-from-scratch/
 │           ├── entrypoint.rs               # Program entrypoint
 │           ├── error.rs                    # Custom error types
 │           ├── instruction.rs              # Instruction definitions
+│           ├── instruction_builder.rs      # Instruction builders (accounts + data)
 │           ├── state/
 │           │   ├── mod.rs                  # State module, Pack trait, COption
 │           │   ├── mint.rs                 # Mint account (82 bytes)
@@ -2514,13 +11485,34 @@ spl-token-from-scratch/
 │           │   ├── set_authority.rs        # SetAuthority
 │           │   ├── close_account.rs        # CloseAccount
 │           │   ├── freeze_account.rs       # FreezeAccount
+│           │   ├── transfer_batch.rs       # TransferBatch
+│           │   ├── get_supply.rs           # GetSupply
+│           │   ├── burn_all.rs             # BurnAll
+│           │   ├── approve_all.rs          # ApproveAll
+│           │   ├── reallocate.rs           # Reallocate (resize for extensions)
+│           │   ├── set_non_transferable.rs # SetNonTransferable (soulbound mints)
+│           │   ├── set_memo_required.rs    # SetMemoRequired (inbound memo flag)
+│           │   ├── reconcile_supply.rs     # ReconcileSupply (audit supply vs balances)
+│           │   ├── transfer_with_fee.rs    # TransferWithFee (basis-point fee split)
+│           │   ├── set_supply_cap.rs       # SetSupplyCap (maximum supply)
+│           │   ├── approve_and_set_close_authority.rs # ApproveAndSetCloseAuthority
+│           │   ├── verify_account.rs       # VerifyAccount (mint/owner status)
+│           │   ├── create_account.rs       # CreateAccount (create + initialize via CPI)
+│           │   ├── set_mint_paused.rs      # SetMintPaused (halt minting)
+│           │   ├── freeze_accounts.rs      # FreezeAccounts (batched freeze)
+│           │   ├── get_mint_info.rs        # GetMintInfo (mint summary)
 │           │   └── thaw_account.rs         # ThawAccount
 │           └── utils/
 │               ├── mod.rs                  # Utils module
 │               ├── assertions.rs           # Validation helpers
+│               ├── ui_amount.rs            # UI amount conversion
+│               ├── scaling.rs              # Cross-decimal rescaling
+│               ├── address.rs              # ATA-style address derivation
+│               ├── clock.rs                # Current slot (with fallback)
 │               └── authority.rs            # Authority validation
 └── tests/
-    └── integration_tests.rs                # Integration tests
+    ├── integration_tests.rs                # Integration tests
+    └── instruction_roundtrip.rs            # pack/unpack roundtrip for every instruction
 
 =============================================================================
 BUILD AND DEPLOY COMMANDS